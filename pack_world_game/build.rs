@@ -115,6 +115,9 @@ use std::collections::HashMap;
         }
     }
     writeln!(output_file, "}}").unwrap();
+
+    let tiles = gen_tiles();
+    gen_drop_tables(&tiles);
 }
 
 fn write_texture(output_file: &mut File, id: &str, path: &str) {
@@ -130,3 +133,1192 @@ fn write_model(output_file: &mut File, id: &str, path: &str) {
     write!(output_file, "\"../../../../resources/{}\", ", path).unwrap();
     writeln!(output_file, "render_api);").unwrap();
 }
+
+/// Pack tables are saved with this index plus the pack index
+const PACK_TABLE_SAVE_INDEX: i32 = 6;
+
+/// Turns the designer drop table data into GEN_fixed_tables.rs.
+/// Unknown items, tiles, packs or tables and duplicate ids stop the build.
+fn gen_drop_tables(tiles: &[String]) {
+    let items = enum_variants("src/item.rs", "ItemType");
+    let packs = enum_variants("src/pack/pack_id.rs", "PackID");
+
+    let ids_file =
+        std::fs::read_to_string("design/table_ids.csv").expect("Require table ids file.");
+    let tables_file =
+        std::fs::read_to_string("design/drop_tables.csv").expect("Require drop tables file.");
+
+    // table name and save index
+    let mut ids: Vec<(String, i32)> = vec![];
+    for (line, cols) in csv_rows(&ids_file) {
+        if cols.len() != 2 {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                "expected table,save_index".into(),
+            );
+        }
+
+        let name = cols[0].clone();
+        let index: i32 = cols[1].parse().unwrap_or_else(|_| {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                format!("invalid save index {}", cols[1]),
+            )
+        });
+
+        if !is_identifier(&name) {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                format!("table name {} must be CamelCase letters and numbers", name),
+            );
+        }
+        if name == "Pack" {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                "Pack is used for pack tables. Name them Pack.<PackID> in drop_tables.csv".into(),
+            );
+        }
+        if index == PACK_TABLE_SAVE_INDEX {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                format!("save index {} is reserved for pack tables", index),
+            );
+        }
+        if ids.iter().any(|(other, _)| *other == name) {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                format!("duplicate table {}", name),
+            );
+        }
+        if let Some((other, _)) = ids.iter().find(|(_, other)| *other == index) {
+            data_error(
+                "design/table_ids.csv",
+                line,
+                format!("save index {} is already used by {}", index, other),
+            );
+        }
+
+        ids.push((name, index));
+    }
+
+    let is_table = |name: &str| -> bool {
+        match name.strip_prefix("Pack.") {
+            Some(pack) => packs.iter().any(|p| p == pack),
+            None => ids.iter().any(|(id, _)| id == name),
+        }
+    };
+
+    // table name and its entries, in the order they first show up
+    let mut tables: Vec<(String, Vec<String>)> = vec![];
+    for (line, cols) in csv_rows(&tables_file) {
+        if cols.len() != 5 {
+            data_error(
+                "design/drop_tables.csv",
+                line,
+                "expected table,output,id,amount,weight".into(),
+            );
+        }
+
+        let (table, output, id) = (&cols[0], &cols[1], &cols[2]);
+        if !is_table(table) {
+            data_error(
+                "design/drop_tables.csv",
+                line,
+                format!("unknown table {}. Add it to design/table_ids.csv", table),
+            );
+        }
+
+        let amount: i64 = cols[3].parse().unwrap_or_else(|_| {
+            data_error(
+                "design/drop_tables.csv",
+                line,
+                format!("invalid amount {}", cols[3]),
+            )
+        });
+        let weight: f64 = cols[4].parse().unwrap_or_else(|_| {
+            data_error(
+                "design/drop_tables.csv",
+                line,
+                format!("invalid weight {}", cols[4]),
+            )
+        });
+
+        let entry = match output.as_str() {
+            "gold" => format!("EntryOutput::new_gold({})", amount),
+            "item" => {
+                if !items.contains(id) {
+                    data_error(
+                        "design/drop_tables.csv",
+                        line,
+                        format!("unknown item {}", id),
+                    );
+                }
+                format!("EntryOutput::new_item(ItemType::{}, {})", id, amount)
+            }
+            "tile" => {
+                if !tiles.contains(id) {
+                    data_error(
+                        "design/drop_tables.csv",
+                        line,
+                        format!("unknown tile {}", id),
+                    );
+                }
+                format!("EntryOutput::new_tile(TileType::{}, {})", id, amount)
+            }
+            "table" => {
+                if !is_table(id) {
+                    data_error(
+                        "design/drop_tables.csv",
+                        line,
+                        format!("unknown table {}", id),
+                    );
+                }
+                format!("EntryOutput::new_table({}, {})", table_id(id), amount)
+            }
+            _ => data_error(
+                "design/drop_tables.csv",
+                line,
+                format!("unknown output {}. Use gold, item, tile or table", output),
+            ),
+        };
+        let entry = format!("({}, {:?})", entry, weight);
+
+        match tables.iter_mut().find(|(name, _)| name == table) {
+            Some((_, entries)) => entries.push(entry),
+            None => tables.push((table.clone(), vec![entry])),
+        }
+    }
+
+    for (name, _) in &ids {
+        if !tables.iter().any(|(table, _)| table == name) {
+            panic!("design/drop_tables.csv: table {} has no entries", name);
+        }
+    }
+    for pack in &packs {
+        let name = format!("Pack.{}", pack);
+        if !tables.iter().any(|(table, _)| *table == name) {
+            panic!("design/drop_tables.csv: pack {} has no table", pack);
+        }
+    }
+
+    let mut output_file = File::create("src/drop_table/fixed_tables/GEN_fixed_tables.rs")
+        .expect("Couldn't open output file");
+
+    let imp = r#"// Generated by build.rs from design/drop_tables.csv and design/table_ids.csv. Edit those instead.
+
+use crate::{drop_table::*, item::*, pack::pack_id::*, tile::*};
+use std::sync::LazyLock;
+"#;
+    writeln!(output_file, "{}", imp).unwrap();
+
+    writeln!(
+        output_file,
+        "/// Pack tables are saved with this index plus the pack index"
+    )
+    .unwrap();
+    writeln!(
+        output_file,
+        "pub const PACK_TABLE_SAVE_INDEX: i32 = {};\n",
+        PACK_TABLE_SAVE_INDEX
+    )
+    .unwrap();
+
+    // ids
+    writeln!(
+        output_file,
+        "#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]"
+    )
+    .unwrap();
+    writeln!(output_file, "pub enum FixedTableID {{").unwrap();
+    for (name, _) in &ids {
+        writeln!(output_file, "    {},", name).unwrap();
+    }
+    writeln!(output_file, "    Pack(PackID),\n\n    // testing").unwrap();
+    for test_table in ["TestTable", "TestGold", "TestCycleA", "TestCycleB"] {
+        writeln!(output_file, "    #[cfg(test)]\n    {},", test_table).unwrap();
+    }
+    writeln!(output_file, "}}\n").unwrap();
+
+    writeln!(
+        output_file,
+        "/// Every fixed table the game uses, including pack tables. Excludes test tables."
+    )
+    .unwrap();
+    writeln!(
+        output_file,
+        "pub const ALL_FIXED_TABLES: LazyLock<Vec<FixedTableID>> = LazyLock::new(|| {{"
+    )
+    .unwrap();
+    writeln!(output_file, "    let mut ret = vec![").unwrap();
+    for (name, _) in &ids {
+        writeln!(output_file, "        FixedTableID::{},", name).unwrap();
+    }
+    writeln!(output_file, "    ];").unwrap();
+    writeln!(
+        output_file,
+        "    ret.extend(ALL_PACKS.iter().map(|pack_id| FixedTableID::Pack(*pack_id)));"
+    )
+    .unwrap();
+    writeln!(output_file, "    ret\n}});\n").unwrap();
+
+    // save indices
+    writeln!(output_file, "impl FixedTableID {{").unwrap();
+    writeln!(output_file, "    pub fn save_index(&self) -> i32 {{").unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for (name, index) in &ids {
+        writeln!(output_file, "            Self::{} => {},", name, index).unwrap();
+    }
+    writeln!(
+        output_file,
+        "            Self::Pack(_) => PACK_TABLE_SAVE_INDEX,"
+    )
+    .unwrap();
+    writeln!(output_file, "            #[cfg(test)]").unwrap();
+    writeln!(
+        output_file,
+        "            _ => panic!(\"Should never try to serialize a test table.\"),"
+    )
+    .unwrap();
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(output_file, "    /// Pack tables aren't included").unwrap();
+    writeln!(
+        output_file,
+        "    pub fn from_save_index(index: i32) -> Option<Self> {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match index {{").unwrap();
+    for (name, index) in &ids {
+        writeln!(
+            output_file,
+            "            {} => Some(Self::{}),",
+            index, name
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "            _ => None,").unwrap();
+    writeln!(output_file, "        }}\n    }}\n}}\n").unwrap();
+
+    // tables
+    for (name, entries) in &tables {
+        writeln!(
+            output_file,
+            "pub static {}: LazyLock<DropTable> = LazyLock::new(|| {{",
+            static_name(name)
+        )
+        .unwrap();
+        writeln!(output_file, "    DropTable::new(vec![").unwrap();
+        for entry in entries {
+            writeln!(output_file, "        {},", entry).unwrap();
+        }
+        writeln!(output_file, "    ])\n}});\n").unwrap();
+    }
+
+    writeln!(
+        output_file,
+        "pub fn generated_table<'a>(id: FixedTableID) -> &'a DropTable {{"
+    )
+    .unwrap();
+    writeln!(output_file, "    match id {{").unwrap();
+    for (name, _) in &tables {
+        writeln!(
+            output_file,
+            "        {} => &{},",
+            table_id(name),
+            static_name(name)
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "        #[cfg(test)]").unwrap();
+    writeln!(
+        output_file,
+        "        _ => panic!(\"Test tables aren't generated\"),"
+    )
+    .unwrap();
+    writeln!(output_file, "    }}\n}}").unwrap();
+}
+
+const TILES_FILE: &str = "design/tiles.txt";
+
+/// One tile from design/tiles.txt, already turned into Rust expressions
+struct TileData {
+    name: String,
+    save_index: i32,
+    methods_index: i32,
+    title: String,
+    description: String,
+    layer: String,
+    footprint: String,
+    draw_footprint: bool,
+    constraints: Vec<String>,
+    global_mods: Vec<String>,
+
+    /// Statements which build the instance components
+    components: Vec<String>,
+
+    custom: Option<String>,
+    snapshot_fields: Vec<(String, String)>,
+}
+
+/// Turns design/tiles.txt into GEN_tiles.rs. Returns the tile names for the drop tables.
+/// Unknown keys, layers, tables or tiles and duplicate save indices stop the build.
+fn gen_tiles() -> Vec<String> {
+    let items = enum_variants("src/item.rs", "ItemType");
+    let packs = enum_variants("src/pack/pack_id.rs", "PackID");
+    let layers = enum_variants("src/world/world_layer.rs", "WorldLayer");
+    let mod_kinds = enum_tuple_variants("src/world/global_mod.rs", "GlobalModKind");
+
+    let ids_file =
+        std::fs::read_to_string("design/table_ids.csv").expect("Require table ids file.");
+    let table_names: Vec<String> = csv_rows(&ids_file)
+        .into_iter()
+        .map(|(_, cols)| cols[0].clone())
+        .collect();
+    let is_table = |name: &str| -> bool {
+        match name.strip_prefix("Pack.") {
+            Some(pack) => packs.iter().any(|p| p == pack),
+            None => table_names.iter().any(|t| t == name),
+        }
+    };
+
+    let tiles_file = std::fs::read_to_string(TILES_FILE).expect("Require tiles file.");
+
+    // Split into blocks first. Conditions can reference tiles further down the file.
+    // tile name, line, then each (line, key, rest of the line)
+    let mut blocks: Vec<(String, usize, Vec<(usize, String, String)>)> = vec![];
+    for (i, line) in tiles_file.lines().enumerate() {
+        let line_num = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, rest) = match line.split_once(char::is_whitespace) {
+            Some((key, rest)) => (key.to_string(), rest.trim().to_string()),
+            None => (line.to_string(), String::new()),
+        };
+
+        if key == "tile" {
+            if !is_identifier(&rest) {
+                data_error(
+                    TILES_FILE,
+                    line_num,
+                    format!("tile name {} must be CamelCase", rest),
+                );
+            }
+            if blocks.iter().any(|(name, _, _)| *name == rest) {
+                data_error(TILES_FILE, line_num, format!("duplicate tile {}", rest));
+            }
+            blocks.push((rest, line_num, vec![]));
+        } else {
+            match blocks.last_mut() {
+                Some((_, _, lines)) => lines.push((line_num, key, rest)),
+                None => data_error(TILES_FILE, line_num, "expected tile <Name> first".into()),
+            }
+        }
+    }
+
+    // tile name and its snapshot fields
+    let mut snapshots: Vec<(String, Vec<(String, String)>)> = vec![];
+    for (name, _, lines) in &blocks {
+        let mut fields = vec![];
+        for (line, key, rest) in lines {
+            if key == "snapshot_field" {
+                let words: Vec<&str> = rest.split_whitespace().collect();
+                if words.len() != 2 || !["bool", "i32"].contains(&words[1]) {
+                    data_error(
+                        TILES_FILE,
+                        *line,
+                        "expected snapshot_field <name> <bool|i32>".into(),
+                    );
+                }
+                fields.push((words[0].to_string(), words[1].to_string()));
+            }
+        }
+        snapshots.push((name.clone(), fields));
+    }
+
+    let mut tiles: Vec<TileData> = vec![];
+    for (name, tile_line, lines) in &blocks {
+        let mut save_index: Option<i32> = None;
+        let mut methods_index: Option<i32> = None;
+        let mut title: Option<String> = None;
+        let mut description: Option<String> = None;
+        let mut layer: Option<String> = None;
+        let mut footprint_size: Option<(i32, i32)> = None;
+        let mut draw_footprint = false;
+        let mut constraints: Vec<String> = vec![];
+        let mut global_mods: Vec<(usize, String, String, String)> = vec![];
+        let mut harvest: Option<String> = None;
+        let mut harvest_destroy = false;
+        let mut harvest_conditions: Vec<String> = vec![];
+        let mut harvest_others: Option<(usize, String, String)> = None;
+        let mut wander: Option<i32> = None;
+        let mut auto_death: Option<String> = None;
+        let mut custom: Option<String> = None;
+
+        for (line, key, rest) in lines {
+            let line = *line;
+            let err = |msg: String| -> ! { data_error(TILES_FILE, line, msg) };
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            let once = |set: bool| {
+                if set {
+                    err(format!("{} is already set for {}", key, name));
+                }
+            };
+
+            match key.as_str() {
+                "save_index" => {
+                    once(save_index.is_some());
+                    save_index = Some(parse_num(rest, line));
+                }
+                "methods_index" => {
+                    once(methods_index.is_some());
+                    methods_index = Some(parse_num(rest, line));
+                }
+                "title" => {
+                    once(title.is_some());
+                    title = Some(rest.clone());
+                }
+                "description" => {
+                    once(description.is_some());
+                    description = Some(rest.clone());
+                }
+                "layer" => {
+                    once(layer.is_some());
+                    if !layers.contains(rest) {
+                        err(format!("unknown layer {}", rest));
+                    }
+                    layer = Some(rest.clone());
+                }
+                "footprint" => {
+                    once(footprint_size.is_some());
+                    if words.len() != 2 {
+                        err("expected footprint <w> <h>".into());
+                    }
+                    footprint_size = Some((parse_num(words[0], line), parse_num(words[1], line)));
+                }
+                "draw_footprint" => draw_footprint = true,
+                "constraint" => constraints.push(tile_condition(&words, &snapshots, line)),
+                "global_mod" => {
+                    if words.len() != 3 {
+                        err("expected global_mod <kind> <value> <positions>".into());
+                    }
+                    if !mod_kinds.iter().any(|k| k == words[0]) {
+                        err(format!("unknown global mod {}", words[0]));
+                    }
+                    let value: f64 = parse_num(words[1], line);
+                    global_mods.push((
+                        line,
+                        words[0].to_string(),
+                        format!("{:?}", value),
+                        words[2].to_string(),
+                    ));
+                }
+                "harvest" => {
+                    once(harvest.is_some());
+                    let self_harvest = match words.get(2) {
+                        None => false,
+                        Some(&"self_harvest") => true,
+                        Some(other) => err(format!("unknown harvest option {}", other)),
+                    };
+                    if words.len() < 2 || words.len() > 3 {
+                        err("expected harvest <time> <table> [self_harvest]".into());
+                    }
+                    if !is_table(words[1]) {
+                        err(format!("unknown table {}", words[1]));
+                    }
+                    harvest = Some(format!(
+                        "TileCompHarvest::new({}, {}, {})",
+                        tile_time(words[0], line),
+                        table_id(words[1]),
+                        self_harvest
+                    ));
+                }
+                "harvest_destroy" => harvest_destroy = true,
+                "harvest_length_condition" | "harvest_drop_count_condition" => {
+                    if words.len() < 2 {
+                        err(format!("expected {} <mod> <condition>", key));
+                    }
+                    let value: f64 = parse_num(words[0], line);
+                    let method = match key.as_str() {
+                        "harvest_length_condition" => "add_length_condition",
+                        _ => "add_drop_count_condition",
+                    };
+                    harvest_conditions.push(format!(
+                        "harvest.{}({:?}, {});",
+                        method,
+                        value,
+                        tile_condition(&words[1..], &snapshots, line)
+                    ));
+                }
+                "harvest_drop_condition" => {
+                    if words.len() < 5 {
+                        err("expected harvest_drop_condition <output> <id> <amount> <weight> <condition>".into());
+                    }
+                    let (output, id) = (words[0], words[1]);
+                    let amount: i64 = parse_num(words[2], line);
+                    let weight: f64 = parse_num(words[3], line);
+
+                    let entry = match output {
+                        "gold" => format!("EntryOutput::new_gold({})", amount),
+                        "item" => {
+                            if !items.iter().any(|i| i == id) {
+                                err(format!("unknown item {}", id));
+                            }
+                            format!("EntryOutput::new_item(ItemType::{}, {})", id, amount)
+                        }
+                        "tile" => {
+                            if !blocks.iter().any(|(t, _, _)| t == id) {
+                                err(format!("unknown tile {}", id));
+                            }
+                            format!("EntryOutput::new_tile(TileType::{}, {})", id, amount)
+                        }
+                        "table" => {
+                            if !is_table(id) {
+                                err(format!("unknown table {}", id));
+                            }
+                            format!("EntryOutput::new_table({}, {})", table_id(id), amount)
+                        }
+                        _ => err(format!(
+                            "unknown output {}. Use gold, item, tile or table",
+                            output
+                        )),
+                    };
+
+                    harvest_conditions.push(format!(
+                        "harvest.add_drop_condition(({}, {:?}), {});",
+                        entry,
+                        weight,
+                        tile_condition(&words[4..], &snapshots, line)
+                    ));
+                }
+                "harvest_others" => {
+                    once(harvest_others.is_some());
+                    if words.len() != 2 {
+                        err("expected harvest_others <time> <positions>".into());
+                    }
+                    harvest_others = Some((line, tile_time(words[0], line), words[1].to_string()));
+                }
+                "wander" => {
+                    once(wander.is_some());
+                    wander = Some(parse_num(rest, line));
+                }
+                "auto_death" => {
+                    once(auto_death.is_some());
+                    auto_death = Some(tile_time(rest, line));
+                }
+                "custom" => {
+                    once(custom.is_some());
+                    if !is_identifier(rest) {
+                        err(format!("custom struct {} must be CamelCase", rest));
+                    }
+                    custom = Some(rest.clone());
+                }
+                "snapshot_field" => {}
+                _ => err(format!("unknown key {}", key)),
+            }
+        }
+
+        let missing = |what: &str| -> ! {
+            data_error(
+                TILES_FILE,
+                *tile_line,
+                format!("{} is missing {}", name, what),
+            )
+        };
+        let save_index = save_index.unwrap_or_else(|| missing("save_index"));
+        let methods_index = methods_index.unwrap_or_else(|| missing("methods_index"));
+
+        if let Some(other) = tiles.iter().find(|t| t.save_index == save_index) {
+            data_error(
+                TILES_FILE,
+                *tile_line,
+                format!(
+                    "save index {} is already used by {}",
+                    save_index, other.name
+                ),
+            );
+        }
+        if let Some(other) = tiles.iter().find(|t| t.methods_index == methods_index) {
+            data_error(
+                TILES_FILE,
+                *tile_line,
+                format!(
+                    "methods index {} is already used by {}",
+                    methods_index, other.name
+                ),
+            );
+        }
+        if harvest.is_none() && (harvest_destroy || !harvest_conditions.is_empty()) {
+            data_error(
+                TILES_FILE,
+                *tile_line,
+                format!("{} has harvest options but no harvest", name),
+            );
+        }
+
+        let (w, h) = footprint_size.unwrap_or((1, 1));
+        let footprint = if (w, h) == (1, 1) {
+            "vec![GridPos::new(0, 0)]".to_string()
+        } else {
+            format!("GridPos::new(0, 0).to_rect_iter({}, {}).collect()", w, h)
+        };
+
+        let positions = |line: usize, positions: &str| -> String {
+            match positions {
+                "origin" => "vec![GridPos::new(0, 0)]".to_string(),
+                "adjacents" => "GridPos::new(0, 0).to_adjacents_iter().collect()".to_string(),
+                "footprint" => footprint.clone(),
+                _ => data_error(
+                    TILES_FILE,
+                    line,
+                    format!(
+                        "unknown positions {}. Use origin, adjacents or footprint",
+                        positions
+                    ),
+                ),
+            }
+        };
+
+        let mut components: Vec<String> = vec![];
+        if let Some(harvest) = harvest {
+            if harvest_conditions.is_empty() && !harvest_destroy {
+                components.push(format!("inst.comp_harvest = Some({});", harvest));
+            } else {
+                components.push(format!("let mut harvest = {};", harvest));
+                components.extend(harvest_conditions);
+                if harvest_destroy {
+                    components.push("harvest.destroy_after_harvest = true;".into());
+                }
+                components.push("inst.comp_harvest = Some(harvest);".into());
+            }
+        }
+        if let Some((line, time, pos)) = harvest_others {
+            components.push(format!(
+                "inst.comp_harvest_others = Some(TileCompHarvestOthers::new({}, {}));",
+                time,
+                positions(line, &pos)
+            ));
+        }
+        if let Some(range) = wander {
+            components.push(format!(
+                "inst.comp_wander = Some(TileCompWander {{ range: {}, target_grid_offset: GridPos::new(1, 1), curr_world_pos: grid_to_world(&grid_pos) }});",
+                range
+            ));
+        }
+        if let Some(time) = auto_death {
+            components.push(format!(
+                "inst.comp_auto_death = Some(TileCompAutoDeath::new({}));",
+                time
+            ));
+        }
+
+        let global_mods = global_mods
+            .iter()
+            .map(|(line, kind, value, pos)| {
+                format!(
+                    "GlobalMod::new(GlobalModKind::{}({}), {})",
+                    kind,
+                    value,
+                    positions(*line, pos)
+                )
+            })
+            .collect();
+
+        let snapshot_fields = snapshots
+            .iter()
+            .find(|(tile, _)| tile == name)
+            .map(|(_, fields)| fields.clone())
+            .unwrap_or_default();
+        if !snapshot_fields.is_empty() && custom.is_none() {
+            data_error(
+                TILES_FILE,
+                *tile_line,
+                format!("{} has snapshot fields so must be custom", name),
+            );
+        }
+
+        tiles.push(TileData {
+            name: name.clone(),
+            save_index,
+            methods_index,
+            title: title.unwrap_or_else(|| missing("title")),
+            description: description.unwrap_or_else(|| missing("description")),
+            layer: layer.unwrap_or_else(|| missing("layer")),
+            footprint,
+            draw_footprint,
+            constraints,
+            global_mods,
+            components,
+            custom,
+            snapshot_fields,
+        });
+    }
+
+    write_tiles(&tiles);
+
+    tiles.into_iter().map(|tile| tile.name).collect()
+}
+
+fn write_tiles(tiles: &[TileData]) {
+    let mut output_file = File::create("src/tile/GEN_tiles.rs").expect("Couldn't open output file");
+
+    let imp = r#"// Generated by build.rs from design/tiles.txt. Edit that instead.
+
+use crate::{
+    drop_table::*,
+    error::*,
+    grid::*,
+    item::*,
+    save_file::*,
+    tile::{tiles::*, *},
+    world::*,
+};
+use elara_engine::time::*;
+use std::sync::LazyLock;
+"#;
+    writeln!(output_file, "{}", imp).unwrap();
+
+    // enums
+    writeln!(
+        output_file,
+        "#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]"
+    )
+    .unwrap();
+    writeln!(output_file, "pub enum TileType {{").unwrap();
+    for tile in tiles {
+        writeln!(output_file, "    {},", tile.name).unwrap();
+    }
+    writeln!(output_file, "}}\n").unwrap();
+
+    writeln!(
+        output_file,
+        "pub const ALL_TILE_TYPES: LazyLock<Vec<TileType>> = LazyLock::new(|| {{"
+    )
+    .unwrap();
+    writeln!(output_file, "    vec![").unwrap();
+    for tile in tiles {
+        writeln!(output_file, "        TileType::{},", tile.name).unwrap();
+    }
+    writeln!(output_file, "    ]\n}});\n").unwrap();
+
+    writeln!(
+        output_file,
+        "/// This is just manual dynamic dispatch because Dyn breaks hot realoding."
+    )
+    .unwrap();
+    writeln!(output_file, "#[derive(Debug, Clone)]").unwrap();
+    writeln!(output_file, "pub enum TileMethods {{").unwrap();
+    for tile in tiles {
+        match &tile.custom {
+            Some(custom) => writeln!(output_file, "    {}({}),", tile.name, custom).unwrap(),
+            None => writeln!(output_file, "    {},", tile.name).unwrap(),
+        }
+    }
+    writeln!(output_file, "}}\n").unwrap();
+
+    writeln!(output_file, "#[derive(Copy, Clone, Debug, Eq, PartialEq)]").unwrap();
+    writeln!(output_file, "pub enum TileSnapshot {{").unwrap();
+    for tile in tiles {
+        if tile.snapshot_fields.is_empty() {
+            writeln!(output_file, "    {},", tile.name).unwrap();
+        } else {
+            let fields: Vec<String> = tile
+                .snapshot_fields
+                .iter()
+                .map(|(name, ty)| format!("{}: {}", name, ty))
+                .collect();
+            writeln!(
+                output_file,
+                "    {} {{ {} }},",
+                tile.name,
+                fields.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    writeln!(output_file, "}}\n").unwrap();
+
+    // tile type
+    writeln!(output_file, "impl TileType {{").unwrap();
+    writeln!(
+        output_file,
+        "    pub fn get_definition(&self) -> &'static TileDefinition {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for tile in tiles {
+        writeln!(
+            output_file,
+            "            TileType::{} => &{},",
+            tile.name,
+            def_name(&tile.name)
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(output_file, "    pub fn to_index(&self) -> i32 {{").unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for tile in tiles {
+        writeln!(
+            output_file,
+            "            Self::{} => {},",
+            tile.name, tile.save_index
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(
+        output_file,
+        "    pub fn from_index(idx: i32) -> Result<Self, Error> {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match idx {{").unwrap();
+    for tile in tiles {
+        writeln!(
+            output_file,
+            "            {} => Ok(Self::{}),",
+            tile.save_index, tile.name
+        )
+        .unwrap();
+    }
+    writeln!(
+        output_file,
+        "            _ => Err(Error::InvalidTileTypeIndex(idx)),"
+    )
+    .unwrap();
+    writeln!(output_file, "        }}\n    }}\n}}\n").unwrap();
+
+    // tile methods
+    writeln!(output_file, "impl TileMethods {{").unwrap();
+    writeln!(output_file, "    pub fn to_index(&self) -> i32 {{").unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for tile in tiles {
+        writeln!(
+            output_file,
+            "            TileMethods::{}{} => {},",
+            tile.name,
+            if tile.custom.is_some() { "(_)" } else { "" },
+            tile.methods_index
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(output_file, "    /// Only custom tiles have state to save").unwrap();
+    writeln!(
+        output_file,
+        "    pub fn save_state(&self, state_key: String, save_file: &mut SaveFile) -> Result<(), Error> {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for tile in tiles.iter().filter(|tile| tile.custom.is_some()) {
+        writeln!(
+            output_file,
+            "            TileMethods::{}(state) => state.save_file_write(state_key, save_file),",
+            tile.name
+        )
+        .unwrap();
+    }
+    writeln!(output_file, "            _ => Ok(()),").unwrap();
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(
+        output_file,
+        "    pub fn load_state(index: i32, state_key: String, save_file: &SaveFile) -> Result<Self, Error> {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match index {{").unwrap();
+    for tile in tiles {
+        match &tile.custom {
+            Some(custom) => writeln!(
+                output_file,
+                "            {} => {}::save_file_load(state_key, save_file),",
+                tile.methods_index, custom
+            )
+            .unwrap(),
+            None => writeln!(
+                output_file,
+                "            {} => Ok(Self::{}),",
+                tile.methods_index, tile.name
+            )
+            .unwrap(),
+        }
+    }
+    writeln!(
+        output_file,
+        "            _ => Err(Error::UnknownTileMethodID(index)),"
+    )
+    .unwrap();
+    writeln!(output_file, "        }}\n    }}\n").unwrap();
+
+    writeln!(
+        output_file,
+        "    pub fn into_snapshot(&self) -> TileSnapshot {{"
+    )
+    .unwrap();
+    writeln!(output_file, "        match self {{").unwrap();
+    for tile in tiles {
+        match (&tile.custom, tile.snapshot_fields.is_empty()) {
+            (Some(_), false) => writeln!(
+                output_file,
+                "            TileMethods::{}(state) => state.snapshot(),",
+                tile.name
+            ),
+            (Some(_), true) => writeln!(
+                output_file,
+                "            TileMethods::{}(_) => TileSnapshot::{},",
+                tile.name, tile.name
+            ),
+            (None, _) => writeln!(
+                output_file,
+                "            TileMethods::{} => TileSnapshot::{},",
+                tile.name, tile.name
+            ),
+        }
+        .unwrap();
+    }
+    writeln!(output_file, "        }}\n    }}\n}}\n").unwrap();
+
+    // definitions
+    for tile in tiles {
+        writeln!(
+            output_file,
+            "pub static {}: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {{",
+            def_name(&tile.name)
+        )
+        .unwrap();
+        writeln!(output_file, "    title: {:?},", tile.title).unwrap();
+        writeln!(output_file, "    description: {:?},", tile.description).unwrap();
+        writeln!(output_file, "    world_layer: WorldLayer::{},", tile.layer).unwrap();
+        writeln!(output_file, "    footprint: {},", tile.footprint).unwrap();
+        writeln!(
+            output_file,
+            "    placing_draw_footprint: {},",
+            tile.draw_footprint
+        )
+        .unwrap();
+        writeln!(
+            output_file,
+            "    placement_constraints: vec![{}],",
+            tile.constraints.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            output_file,
+            "    placement_global_mod: vec![{}],",
+            tile.global_mods.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            output_file,
+            "    new_instance: {},",
+            instance_fn_name(&tile.name)
+        )
+        .unwrap();
+        writeln!(output_file, "}});\n").unwrap();
+
+        let methods = match &tile.custom {
+            Some(custom) => format!("TileMethods::{}({}::new())", tile.name, custom),
+            None => format!("TileMethods::{}", tile.name),
+        };
+        let new_inst = format!(
+            "TileInstance::new(TileType::{}, grid_pos, {})",
+            tile.name, methods
+        );
+
+        writeln!(
+            output_file,
+            "fn {}(grid_pos: GridPos) -> TileInstance {{",
+            instance_fn_name(&tile.name)
+        )
+        .unwrap();
+        if tile.components.is_empty() {
+            writeln!(output_file, "    {}", new_inst).unwrap();
+        } else {
+            writeln!(output_file, "    let mut inst = {};", new_inst).unwrap();
+            for comp in &tile.components {
+                writeln!(output_file, "    {}", comp).unwrap();
+            }
+            writeln!(output_file, "    inst").unwrap();
+        }
+        writeln!(output_file, "}}\n").unwrap();
+    }
+}
+
+/// 18s, 4m, 3h or 1.5d
+fn tile_time(time: &str, line: usize) -> String {
+    let unit = match time.chars().last() {
+        Some('s') => "Seconds",
+        Some('m') => "Minutes",
+        Some('h') => "Hours",
+        Some('d') => "Days",
+        _ => data_error(
+            TILES_FILE,
+            line,
+            format!("invalid time {}. Use 18s, 4m, 3h or 1.5d", time),
+        ),
+    };
+    let value: f64 = parse_num(&time[..time.len() - 1], line);
+
+    format!("Time::new(TimeUnit::{}({:?}))", unit, value)
+}
+
+/// valid_position, adjacent_to <Tile> or origin_contains <Tile>, with field=value for tile snapshot fields
+fn tile_condition(
+    words: &[&str],
+    snapshots: &[(String, Vec<(String, String)>)],
+    line: usize,
+) -> String {
+    let err = |msg: String| -> ! { data_error(TILES_FILE, line, msg) };
+
+    let kind = match words.first() {
+        Some(&"valid_position") if words.len() == 1 => {
+            return "WorldCondition::ValidPosition()".into()
+        }
+        Some(&"adjacent_to") => "AdjacentTo",
+        Some(&"origin_contains") => "OriginContains",
+        _ => err(format!(
+            "invalid condition {}. Use valid_position, adjacent_to or origin_contains",
+            words.join(" ")
+        )),
+    };
+
+    let tile = words
+        .get(1)
+        .unwrap_or_else(|| err("condition needs a tile".into()));
+    let fields = match snapshots.iter().find(|(name, _)| name == tile) {
+        Some((_, fields)) => fields,
+        None => err(format!("unknown tile {}", tile)),
+    };
+
+    let values = &words[2..];
+    if values.len() != fields.len() {
+        err(format!("{} needs values for every snapshot field", tile));
+    }
+
+    let snapshot = if fields.is_empty() {
+        format!("TileSnapshot::{}", tile)
+    } else {
+        let mut set = vec![];
+        for (name, ty) in fields {
+            let value = values
+                .iter()
+                .find_map(|v| v.strip_prefix(&format!("{}=", name)))
+                .unwrap_or_else(|| err(format!("missing {}=<value> for {}", name, tile)));
+
+            let valid = match ty.as_str() {
+                "bool" => value.parse::<bool>().is_ok(),
+                _ => value.parse::<i32>().is_ok(),
+            };
+            if !valid {
+                err(format!("{} isn't a valid {} for {}", value, ty, name));
+            }
+
+            set.push(format!("{}: {}", name, value));
+        }
+        format!("TileSnapshot::{} {{ {} }}", tile, set.join(", "))
+    };
+
+    format!("WorldCondition::{}({})", kind, snapshot)
+}
+
+fn parse_num<T: std::str::FromStr>(value: &str, line: usize) -> T {
+    value
+        .parse()
+        .unwrap_or_else(|_| data_error(TILES_FILE, line, format!("invalid number {}", value)))
+}
+
+/// MudHenge -> MUD_HENGE_DEF. Keeps clear of the drop table statics.
+fn def_name(name: &str) -> String {
+    format!("{}_DEF", static_name(name))
+}
+
+/// MudHenge -> new_mud_henge
+fn instance_fn_name(name: &str) -> String {
+    format!("new_{}", static_name(name).to_lowercase())
+}
+
+fn data_error(file: &str, line: usize, msg: String) -> ! {
+    panic!("{} line {}: {}", file, line, msg)
+}
+
+/// Header and blank lines are skipped. Returns the line number with the columns.
+fn csv_rows(file: &str) -> Vec<(usize, Vec<String>)> {
+    file.lines()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let cols = line.split(',').map(|col| col.trim().to_string()).collect();
+            (i + 1, cols)
+        })
+        .collect()
+}
+
+/// Unit variants of a Rust enum, read from its source file
+fn enum_variants(path: &str, enum_name: &str) -> Vec<String> {
+    let source = std::fs::read_to_string(path).expect(&format!("Require {}", path));
+    let start = source
+        .find(&format!("pub enum {} {{", enum_name))
+        .expect(&format!("Couldn't find {} in {}", enum_name, path));
+
+    source[start..]
+        .lines()
+        .skip(1)
+        .take_while(|line| line.trim() != "}")
+        .map(|line| line.trim().trim_end_matches(',').to_string())
+        .filter(|variant| is_identifier(variant))
+        .collect()
+}
+
+/// Variants carrying data, like DropCount(f64)
+fn enum_tuple_variants(path: &str, enum_name: &str) -> Vec<String> {
+    let source = std::fs::read_to_string(path).expect(&format!("Require {}", path));
+    let start = source
+        .find(&format!("pub enum {} {{", enum_name))
+        .expect(&format!("Couldn't find {} in {}", enum_name, path));
+
+    source[start..]
+        .lines()
+        .skip(1)
+        .take_while(|line| line.trim() != "}")
+        .filter_map(|line| line.trim().split_once('('))
+        .map(|(variant, _)| variant.to_string())
+        .filter(|variant| is_identifier(variant))
+        .collect()
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Pack.Starter -> FixedTableID::Pack(PackID::Starter)
+fn table_id(name: &str) -> String {
+    match name.strip_prefix("Pack.") {
+        Some(pack) => format!("FixedTableID::Pack(PackID::{})", pack),
+        None => format!("FixedTableID::{}", name),
+    }
+}
+
+/// MudHenge -> MUD_HENGE, Pack.Starter -> PACK_STARTER
+fn static_name(name: &str) -> String {
+    let mut ret = String::new();
+    for c in name.replace('.', "").chars() {
+        if c.is_ascii_uppercase() && !ret.is_empty() {
+            ret.push('_');
+        }
+        ret.push(c.to_ascii_uppercase());
+    }
+    ret
+}
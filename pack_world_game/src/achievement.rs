@@ -0,0 +1,96 @@
+use crate::{error::*, item::*, state::assets::*};
+use std::sync::LazyLock;
+
+pub mod achievement_log;
+
+pub use achievement_log::*;
+
+/// Springs needed for the spring achievement
+pub const SPRINGS_COUNT: usize = 20;
+
+/// Gold needed for the rich achievement
+pub const RICH_GOLD: i64 = 100_000;
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum AchievementID {
+    FirstPack,
+    DiscoverAllItems,
+    PullDragonEgg,
+    TwentySprings,
+    Rich,
+}
+
+pub const ALL_ACHIEVEMENTS: LazyLock<Vec<AchievementID>> = LazyLock::new(|| {
+    vec![
+        AchievementID::FirstPack,
+        AchievementID::DiscoverAllItems,
+        AchievementID::PullDragonEgg,
+        AchievementID::TwentySprings,
+        AchievementID::Rich,
+    ]
+});
+
+impl AchievementID {
+    pub fn to_index(&self) -> i32 {
+        match self {
+            Self::FirstPack => 0,
+            Self::DiscoverAllItems => 1,
+            Self::PullDragonEgg => 2,
+            Self::TwentySprings => 3,
+            Self::Rich => 4,
+        }
+    }
+
+    pub fn from_index(index: i32) -> Result<Self, Error> {
+        match index {
+            0 => Ok(Self::FirstPack),
+            1 => Ok(Self::DiscoverAllItems),
+            2 => Ok(Self::PullDragonEgg),
+            3 => Ok(Self::TwentySprings),
+            4 => Ok(Self::Rich),
+            _ => Err(Error::UnknownAchievementID(index)),
+        }
+    }
+
+    pub fn to_string_id(&self) -> String {
+        format!("achievement_{:?}", self).to_lowercase()
+    }
+
+    pub fn title(&self) -> &str {
+        match self {
+            Self::FirstPack => "Unboxing",
+            Self::DiscoverAllItems => "Collector",
+            Self::PullDragonEgg => "Here Be Dragons",
+            Self::TwentySprings => "Hot Springs",
+            Self::Rich => "Rich",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::FirstPack => "Open a pack.".into(),
+            Self::DiscoverAllItems => "Discover every item.".into(),
+            Self::PullDragonEgg => "Find a dragon egg.".into(),
+            Self::TwentySprings => format!("Have {} springs at once.", SPRINGS_COUNT),
+            Self::Rich => format!("Have {} gold.", RICH_GOLD),
+        }
+    }
+
+    /// Hidden achievements don't show what they are until unlocked
+    pub fn hidden(&self) -> bool {
+        match self {
+            Self::PullDragonEgg => true,
+            _ => false,
+        }
+    }
+
+    pub fn icon(&self, assets: &mut Assets) -> u32 {
+        match self {
+            Self::FirstPack => assets.image_pack_starter.gl_id.unwrap(),
+            Self::DiscoverAllItems => assets.get_item_icon(&ItemType::Pearl),
+            Self::PullDragonEgg => assets.get_item_icon(&ItemType::DragonEgg),
+            Self::TwentySprings => assets.get_item_icon(&ItemType::Dew),
+            Self::Rich => assets.image_gold.gl_id.unwrap(),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    achievement::*, drop_table::*, error::*, item::*, save_file::*, state::inventory::*, tile::*,
+    update_signal::*, world::*,
+};
+use std::collections::HashMap;
+
+/// Which achievements have been unlocked
+#[derive(Debug)]
+pub struct AchievementLog {
+    unlocked: HashMap<AchievementID, bool>,
+}
+
+impl AchievementLog {
+    pub fn new() -> Self {
+        Self {
+            unlocked: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.unlocked.clear();
+    }
+
+    pub fn is_unlocked(&self, id: AchievementID) -> bool {
+        *self.unlocked.get(&id).unwrap_or(&false)
+    }
+
+    /// Check achievements after a signal has been handled.
+    /// Only the achievements the signal could affect are checked.
+    /// Returns the newly unlocked achievements.
+    pub fn signal_handled(
+        &mut self,
+        signal: &UpdateSignal,
+        world: &World,
+        inventory: &Inventory,
+    ) -> Vec<AchievementID> {
+        let mut ret: Vec<AchievementID> = vec![];
+
+        match signal {
+            UpdateSignal::GiveDrop { drop, .. } => {
+                let dragon_egg = DropType::Item {
+                    item_type: ItemType::DragonEgg,
+                };
+                self.try_unlock(
+                    AchievementID::PullDragonEgg,
+                    drop.drop_type == dragon_egg,
+                    &mut ret,
+                );
+                self.check_inventory(inventory, &mut ret);
+            }
+
+            UpdateSignal::GiveItem { .. } | UpdateSignal::GiveGold { .. } => {
+                self.check_inventory(inventory, &mut ret);
+            }
+
+            UpdateSignal::OpenPack(_) => {
+                self.try_unlock(AchievementID::FirstPack, true, &mut ret);
+            }
+
+            UpdateSignal::TilePlaced {
+                tile_type: TileType::Spring,
+            } => {
+                if !self.is_unlocked(AchievementID::TwentySprings) {
+                    let springs = world
+                        .entities
+                        .values()
+                        .filter(|inst| inst.tile_type == TileType::Spring)
+                        .count();
+
+                    self.try_unlock(
+                        AchievementID::TwentySprings,
+                        springs >= SPRINGS_COUNT,
+                        &mut ret,
+                    );
+                }
+            }
+
+            _ => {}
+        }
+
+        ret
+    }
+
+    fn check_inventory(&mut self, inventory: &Inventory, ret: &mut Vec<AchievementID>) {
+        self.try_unlock(AchievementID::Rich, inventory.gold >= RICH_GOLD, ret);
+
+        if !self.is_unlocked(AchievementID::DiscoverAllItems) {
+            let all_seen = ALL_ITEM_TYPES
+                .iter()
+                .all(|item_type| *inventory.items_seen.get(item_type).unwrap_or(&false));
+            self.try_unlock(AchievementID::DiscoverAllItems, all_seen, ret);
+        }
+    }
+
+    fn try_unlock(&mut self, id: AchievementID, condition: bool, ret: &mut Vec<AchievementID>) {
+        if condition && !self.is_unlocked(id) {
+            self.unlocked.insert(id, true);
+            ret.push(id);
+        }
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        for id in ALL_ACHIEVEMENTS.iter() {
+            if self.is_unlocked(*id) {
+                save_file.save_bool(&format!("{}.a.{}", key_parent, id.to_index()), true);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        for id in ALL_ACHIEVEMENTS.iter() {
+            if let Ok(unlocked) =
+                save_file.load_bool(&format!("{}.a.{}", key_parent, id.to_index()))
+            {
+                ret.unlocked.insert(*id, unlocked);
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{grid::*, pack::*};
+
+    #[test]
+    fn unlocks_once() {
+        let world = World::new();
+        let inventory = Inventory::new();
+        let mut log = AchievementLog::new();
+
+        let sig = UpdateSignal::OpenPack(PackID::Starter);
+        assert_eq!(
+            log.signal_handled(&sig, &world, &inventory),
+            vec![AchievementID::FirstPack]
+        );
+        assert!(log.signal_handled(&sig, &world, &inventory).is_empty());
+    }
+
+    #[test]
+    fn dragon_egg() {
+        let world = World::new();
+        let mut inventory = Inventory::new();
+        let mut log = AchievementLog::new();
+
+        let drop = Drop::new_item(ItemType::Stick, 1);
+        inventory.give_drop(drop).unwrap();
+        assert!(log
+            .signal_handled(
+                &UpdateSignal::GiveDrop {
+                    drop,
+                    source: DropSource::Other
+                },
+                &world,
+                &inventory
+            )
+            .is_empty());
+
+        let drop = Drop::new_item(ItemType::DragonEgg, 1);
+        inventory.give_drop(drop).unwrap();
+        assert_eq!(
+            log.signal_handled(
+                &UpdateSignal::GiveDrop {
+                    drop,
+                    source: DropSource::Other
+                },
+                &world,
+                &inventory
+            ),
+            vec![AchievementID::PullDragonEgg]
+        );
+    }
+
+    #[test]
+    fn discover_all() {
+        let world = World::new();
+        let mut inventory = Inventory::new();
+        inventory.limit = 1000;
+        let mut log = AchievementLog::new();
+
+        for item_type in ALL_ITEM_TYPES.iter().skip(1) {
+            inventory.give_item(*item_type, 1).unwrap();
+        }
+        let sig = UpdateSignal::GiveItem {
+            item_type: ItemType::Stick,
+            count: 1,
+        };
+        assert!(!log
+            .signal_handled(&sig, &world, &inventory)
+            .contains(&AchievementID::DiscoverAllItems));
+
+        inventory.give_item(ALL_ITEM_TYPES[0], 1).unwrap();
+        assert!(log
+            .signal_handled(&sig, &world, &inventory)
+            .contains(&AchievementID::DiscoverAllItems));
+    }
+
+    #[test]
+    fn springs() {
+        let mut world = World::new();
+        let inventory = Inventory::new();
+        let mut log = AchievementLog::new();
+
+        let sig = UpdateSignal::TilePlaced {
+            tile_type: TileType::Spring,
+        };
+
+        for x in 0..SPRINGS_COUNT as i32 {
+            assert!(log.signal_handled(&sig, &world, &inventory).is_empty());
+            let _ = world.insert_tile_type(GridPos::new(x * 10, 0), TileType::Spring);
+        }
+
+        assert_eq!(
+            log.signal_handled(&sig, &world, &inventory),
+            vec![AchievementID::TwentySprings]
+        );
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut log = AchievementLog::new();
+        log.unlocked.insert(AchievementID::Rich, true);
+        log.save_file_write("".into(), &mut save_file).unwrap();
+
+        let loaded = AchievementLog::save_file_load("".into(), &save_file).unwrap();
+        assert!(loaded.is_unlocked(AchievementID::Rich));
+        assert!(!loaded.is_unlocked(AchievementID::FirstPack));
+    }
+}
@@ -12,6 +12,9 @@ pub const BANK_LIMIT_COST_BASE: i64 = 50;
 /// starting bank slots count
 pub const BANK_LIMIT_START: usize = 15;
 
+/// gold the merchant pays for any tile
+pub const TILE_SELL_VALUE: i64 = 10;
+
 /// Time between drops. When processing a list of drops, Wait XX seconds before dropping the next one.
 pub const DROP_TIME_GUTTER_S: f64 = 0.01;
 
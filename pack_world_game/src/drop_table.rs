@@ -1,5 +1,9 @@
 use crate::{
+    error::*,
     item::*,
+    pack::*,
+    quest::*,
+    save_file::*,
     state::{assets::*, inventory::*},
     tile::*,
 };
@@ -7,8 +11,9 @@ use elara_engine::{color::*, platform_api::*, rect::*, ui::*, vectors::*};
 use std::{collections::HashMap, sync::LazyLock};
 
 mod fixed_tables;
+mod table_graph;
 mod table_instance;
-pub use {fixed_tables::*, table_instance::*};
+pub use {fixed_tables::*, table_graph::*, table_instance::*};
 
 // external. this will get sent out to other systems.
 #[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
@@ -45,6 +50,45 @@ impl Drop {
         Drop::new(DropType::Gold, amount)
     }
 
+    pub fn display(&self) -> String {
+        match self.drop_type {
+            DropType::Gold => format!("{} gold", self.amount),
+            DropType::Item { item_type } => format!("{} {}", self.amount, item_type.user_title()),
+        }
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        save_file.save_i64(&format!("{}.n", key_parent), self.amount);
+
+        match self.drop_type {
+            DropType::Gold => {
+                save_file.save_i32(&format!("{}.k", key_parent), 0);
+            }
+            DropType::Item { item_type } => {
+                save_file.save_i32(&format!("{}.k", key_parent), 1);
+                item_type.save_file_write(format!("{}.i", key_parent), save_file)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let amount = save_file.load_i64(&format!("{}.n", key_parent))?;
+
+        match save_file.load_i32(&format!("{}.k", key_parent))? {
+            0 => Ok(Drop::new_gold(amount)),
+            _ => Ok(Drop::new_item(
+                ItemType::save_file_load(format!("{}.i", key_parent), save_file)?,
+                amount,
+            )),
+        }
+    }
+
     /// Convert one drop with an amount into vector of drops with one amount
     pub fn to_individual(&self) -> Vec<Drop> {
         let mut ret: Vec<Drop> = vec![];
@@ -55,6 +99,85 @@ impl Drop {
     }
 }
 
+/// Where a drop came from
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum DropSource {
+    Pack(PackID),
+    Tile(TileType),
+    Quest(QuestID),
+
+    /// Starting items, refunds, debug
+    Other,
+}
+
+impl DropSource {
+    pub fn display(&self) -> String {
+        match self {
+            Self::Pack(pack_id) => format!("{} Pack", pack_id.get_pack_info().display_name),
+            Self::Tile(tile_type) => tile_type.get_definition().title.to_string(),
+            Self::Quest(quest_id) => format!("Quest {}", quest_id.get_quest_info().title),
+            Self::Other => "Unknown".into(),
+        }
+    }
+
+    /// Chance of pulling this drop type from the source. Only packs are pulls.
+    pub fn pull_chance(&self, drop_type: DropType) -> Option<f64> {
+        match self {
+            Self::Pack(pack_id) => {
+                Some(get_fixed_table(pack_id.get_pack_info().table_id).drop_type_chance(drop_type))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        let kind_key = format!("{}.k", key_parent);
+        let val_key = format!("{}.v", key_parent);
+
+        match self {
+            Self::Pack(pack_id) => {
+                save_file.save_i32(&kind_key, 0);
+                save_file.save_i32(&val_key, pack_id.to_index());
+            }
+            Self::Tile(tile_type) => {
+                save_file.save_i32(&kind_key, 1);
+                save_file.save_i32(&val_key, tile_type.to_index());
+            }
+            Self::Quest(quest_id) => {
+                save_file.save_i32(&kind_key, 2);
+                save_file.save_i32(&val_key, quest_id.to_index());
+            }
+            Self::Other => {
+                save_file.save_i32(&kind_key, 3);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let kind_key = format!("{}.k", key_parent);
+        let val_key = format!("{}.v", key_parent);
+
+        match save_file.load_i32(&kind_key)? {
+            0 => Ok(Self::Pack(PackID::from_index(
+                save_file.load_i32(&val_key)?,
+            ))),
+            1 => Ok(Self::Tile(TileType::from_index(
+                save_file.load_i32(&val_key)?,
+            )?)),
+            2 => Ok(Self::Quest(QuestID::from_index(
+                save_file.load_i32(&val_key)?,
+            )?)),
+            _ => Ok(Self::Other),
+        }
+    }
+}
+
 pub fn draw_drop_icon(
     icon_size: f64,
     pos: VecTwo,
@@ -109,6 +232,27 @@ pub fn draw_drop_icon(
     }
 }
 
+/// One final drop and the chance of pulling it
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DropOdds {
+    pub drop: Drop,
+
+    /// Chance on a single pull. Between 0 and 1.
+    pub chance: f64,
+}
+
+impl DropOdds {
+    /// Expected amount received over this many pulls
+    pub fn expected_count(&self, pulls: i64) -> f64 {
+        self.chance * self.drop.amount as f64 * pulls as f64
+    }
+
+    /// Chance of getting this drop at least once in this many pulls
+    pub fn chance_within(&self, pulls: i64) -> f64 {
+        1.0 - (1.0 - self.chance).powi(pulls as i32)
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum EntryOutputType {
     Gold,
@@ -143,6 +287,50 @@ impl EntryOutput {
     pub fn new_gold(amount: i64) -> Self {
         Self::new(EntryOutputType::Gold, amount)
     }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        let kind_key = format!("{}.k", key_parent);
+        save_file.save_i64(&format!("{}.n", key_parent), self.amount);
+
+        match self.ty {
+            EntryOutputType::Gold => {
+                save_file.save_i32(&kind_key, 0);
+            }
+            EntryOutputType::Item(item_type) => {
+                save_file.save_i32(&kind_key, 1);
+                item_type.save_file_write(format!("{}.i", key_parent), save_file)?;
+            }
+            EntryOutputType::Table(table_id) => {
+                save_file.save_i32(&kind_key, 2);
+                table_id.save_file_write(format!("{}.f", key_parent), save_file)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let amount = save_file.load_i64(&format!("{}.n", key_parent))?;
+
+        let ty = match save_file.load_i32(&format!("{}.k", key_parent))? {
+            0 => EntryOutputType::Gold,
+            1 => EntryOutputType::Item(ItemType::save_file_load(
+                format!("{}.i", key_parent),
+                save_file,
+            )?),
+            2 => EntryOutputType::Table(FixedTableID::save_file_load(
+                format!("{}.f", key_parent),
+                save_file,
+            )?),
+            id => return Err(Error::UnknownEntryOutputID(id)),
+        };
+
+        Ok(Self::new(ty, amount))
+    }
 }
 
 // todo rename this to EntryState
@@ -237,6 +425,49 @@ impl DropTable {
         self.calc_chance_values();
     }
 
+    /// Entries and their weights, in pull order
+    pub fn list_entries(&self) -> Vec<(EntryOutput, f64)> {
+        self.entries
+            .iter()
+            .map(|e| (e.output.clone(), e.orig_chance))
+            .collect()
+    }
+
+    pub fn set_weight(&mut self, index: usize, weight: f64) {
+        self.entries[index].orig_chance = weight;
+        self.calc_chance_values();
+    }
+
+    /// Entries are written in pull order with their original weights
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        save_file.save_i64(&format!("{}.n", key_parent), self.entries.len() as i64);
+
+        for (i, e) in self.entries.iter().enumerate() {
+            save_file.save_f64(&format!("{}.{}.w", key_parent, i), e.orig_chance);
+            e.output
+                .save_file_write(format!("{}.{}", key_parent, i), save_file)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let count = save_file.load_i64(&format!("{}.n", key_parent))?;
+
+        let mut entries: Vec<(EntryOutput, f64)> = vec![];
+        for i in 0..count {
+            let weight = save_file.load_f64(&format!("{}.{}.w", key_parent, i))?;
+            let output = EntryOutput::save_file_load(format!("{}.{}", key_parent, i), save_file)?;
+            entries.push((output, weight));
+        }
+
+        Ok(Self::new(entries))
+    }
+
     fn calc_chance_values(&mut self) {
         let mut accum: f64 = 0.0;
         for e in &mut self.entries {
@@ -247,6 +478,37 @@ impl DropTable {
         self.max = accum;
     }
 
+    /// Chance of pulling the drop type, including through nested tables. Between 0 and 1.
+    /// Does not check against cycles
+    pub fn drop_type_chance(&self, drop_type: DropType) -> f64 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        let mut ret: f64 = 0.0;
+        for e in &self.entries {
+            let chance = e.orig_chance / self.max;
+
+            match e.output.ty {
+                EntryOutputType::Gold => {
+                    if drop_type == DropType::Gold {
+                        ret += chance;
+                    }
+                }
+                EntryOutputType::Item(item_type) => {
+                    if drop_type == (DropType::Item { item_type }) {
+                        ret += chance;
+                    }
+                }
+                EntryOutputType::Table(table_id) => {
+                    ret += chance * get_fixed_table(table_id).drop_type_chance(drop_type);
+                }
+            }
+        }
+
+        ret
+    }
+
     /// flatten the drop table into one list of all possible drops
     /// Does not check against cycles
     pub fn list_drops(&self) -> Vec<Drop> {
@@ -267,6 +529,41 @@ impl DropTable {
 
         return ret;
     }
+
+    /// Exact chance of every final drop on one pull, with nested tables expanded.
+    /// Like pull, a nested table is pulled once and the entry amount isn't used.
+    /// Identical drops reached through different entries are merged.
+    /// Does not check against cycles
+    pub fn drop_odds(&self) -> Vec<DropOdds> {
+        let mut ret: Vec<DropOdds> = vec![];
+        if self.max <= 0.0 {
+            return ret;
+        }
+
+        let mut add = |drop: Drop, chance: f64| match ret.iter_mut().find(|odds| odds.drop == drop)
+        {
+            Some(odds) => odds.chance += chance,
+            None => ret.push(DropOdds { drop, chance }),
+        };
+
+        for entry in &self.entries {
+            let chance = entry.orig_chance / self.max;
+
+            match entry.output.ty {
+                EntryOutputType::Gold => add(Drop::new_gold(entry.output.amount), chance),
+                EntryOutputType::Item(item_type) => {
+                    add(Drop::new_item(item_type, entry.output.amount), chance)
+                }
+                EntryOutputType::Table(table_id) => {
+                    for odds in get_fixed_table(table_id).drop_odds() {
+                        add(odds.drop, odds.chance * chance);
+                    }
+                }
+            }
+        }
+
+        ret
+    }
 }
 
 #[cfg(test)]
@@ -328,6 +625,74 @@ mod tests {
         let pull = get_drop(FixedTableID::TestCycleA, &plat_api);
     }
 
+    #[test]
+    fn drop_type_chance() {
+        let table = DropTable::new(vec![
+            (EntryOutput::new_item(ItemType::DirtClod, 1), 10.0),
+            (EntryOutput::new_item(ItemType::DirtClod, 2), 5.0),
+            (EntryOutput::new_table(FixedTableID::TestTable, 1), 5.0),
+        ]);
+
+        let dirt = DropType::Item {
+            item_type: ItemType::DirtClod,
+        };
+        assert_eq!(table.drop_type_chance(dirt), 0.75);
+        assert_eq!(table.drop_type_chance(DropType::Gold), 0.25);
+
+        let stick = DropType::Item {
+            item_type: ItemType::Stick,
+        };
+        assert_eq!(table.drop_type_chance(stick), 0.0);
+    }
+
+    #[test]
+    fn drop_odds() {
+        let table = DropTable::new(vec![
+            (EntryOutput::new_item(ItemType::DirtClod, 1), 10.0),
+            (EntryOutput::new_item(ItemType::DirtClod, 2), 5.0),
+            (EntryOutput::new_table(FixedTableID::TestTable, 3), 5.0),
+        ]);
+
+        let odds = table.drop_odds();
+        assert_eq!(odds.len(), 3);
+        assert_eq!(odds[0].drop, Drop::new_item(ItemType::DirtClod, 1));
+        assert_eq!(odds[0].chance, 0.5);
+        assert_eq!(odds[1].chance, 0.25);
+
+        // nested table gives its own drop, same as pull
+        assert_eq!(odds[2].drop, Drop::new_gold(1));
+        assert_eq!(odds[2].chance, 0.25);
+        assert_eq!(odds[2].expected_count(4), 1.0);
+
+        let total: f64 = odds.iter().map(|odds| odds.chance).sum();
+        assert!((total - 1.0).abs() < 0.0001);
+
+        // same drop through two entries is merged
+        let merged = DropTable::new(vec![
+            (EntryOutput::new_gold(1), 1.0),
+            (EntryOutput::new_table(FixedTableID::TestGold, 1), 1.0),
+        ]);
+        let odds = merged.drop_odds();
+        assert_eq!(odds.len(), 1);
+        assert_eq!(odds[0].chance, 1.0);
+        assert_eq!(odds[0].chance_within(3), 1.0);
+    }
+
+    #[test]
+    fn nested_pull_matches_odds() {
+        let plat_api = windows_plaform_api();
+        let table = DropTable::new(vec![(
+            EntryOutput::new_table(FixedTableID::TestTable, 3),
+            1.0,
+        )]);
+
+        let mut tables_visited: Vec<FixedTableID> = vec![];
+        assert_eq!(
+            table.pull(&mut tables_visited, &plat_api),
+            table.drop_odds()[0].drop
+        );
+    }
+
     // create teble by using the
     #[test]
     fn add_entry() {
@@ -1,4 +1,4 @@
-use crate::{drop_table::*, error::*, pack::*, save_file::*};
+use crate::{drop_table::*, error::*, pack::*, pack_id::*, save_file::*};
 use elara_engine::platform_api::*;
 
 #[cfg(test)]
@@ -6,80 +6,10 @@ mod test_tables;
 #[cfg(test)]
 pub use test_tables::*;
 
-// item
-mod drop_table_boulder;
-mod drop_table_cave;
-mod drop_table_clam;
-mod drop_table_dirt;
-mod drop_table_frog;
-mod drop_table_grass;
-mod drop_table_mud_henge;
-mod drop_table_mud_pig;
-mod drop_table_mud_pit;
-mod drop_table_newt;
-mod drop_table_oak_tree;
-mod drop_table_shrub;
-mod drop_table_small_gold;
-mod drop_table_tall_grass;
-
-use drop_table_boulder::*;
-use drop_table_cave::*;
-use drop_table_clam::*;
-use drop_table_dirt::*;
-use drop_table_frog::*;
-use drop_table_grass::*;
-use drop_table_mud_henge::*;
-use drop_table_mud_pig::*;
-use drop_table_mud_pit::*;
-use drop_table_newt::*;
-use drop_table_oak_tree::*;
-use drop_table_shrub::*;
-use drop_table_small_gold::*;
-use drop_table_tall_grass::*;
-
-// packs
-mod drop_table_pack_mud;
-mod drop_table_pack_mud_heart;
-mod drop_table_pack_starter;
-mod drop_table_pack_stick;
-mod drop_table_pack_water;
-
-use drop_table_pack_mud::*;
-use drop_table_pack_mud_heart::*;
-use drop_table_pack_starter::*;
-use drop_table_pack_stick::*;
-use drop_table_pack_water::*;
-
-#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
-pub enum FixedTableID {
-    // tiles
-    Grass,
-    Boulder,
-    OakTree,
-    SmallGold,
-    Cave,
-    Shrub,
-    MudPit,
-    TallGrass,
-    Frog,
-    Clam,
-    Dirt,
-    MudHenge,
-    MudPig,
-    Newt,
-
-    Pack(PackID),
-
-    // testing
-    #[cfg(test)]
-    TestTable,
-    #[cfg(test)]
-    TestGold,
-    #[cfg(test)]
-    TestCycleA,
-    #[cfg(test)]
-    TestCycleB,
-}
+#[allow(non_snake_case)]
+#[rustfmt::skip]
+mod GEN_fixed_tables;
+pub use GEN_fixed_tables::*;
 
 impl FixedTableID {
     pub fn save_file_write(
@@ -88,67 +18,12 @@ impl FixedTableID {
         save_file: &mut SaveFile,
     ) -> Result<(), Error> {
         let type_key = format!("{}.t", key_parent);
+        save_file.save_i32(&type_key, self.save_index());
 
-        match self {
-            FixedTableID::Grass => {
-                save_file.save_i32(&type_key, 0);
-            }
-            FixedTableID::Boulder => {
-                save_file.save_i32(&type_key, 1);
-            }
-            FixedTableID::OakTree => {
-                save_file.save_i32(&type_key, 2);
-            }
-            FixedTableID::SmallGold => {
-                save_file.save_i32(&type_key, 3);
-            }
-            FixedTableID::Cave => {
-                save_file.save_i32(&type_key, 4);
-            }
-            FixedTableID::Shrub => {
-                save_file.save_i32(&type_key, 5);
-            }
-
-            FixedTableID::Pack(pack_id) => {
-                save_file.save_i32(&type_key, 6);
-
-                let pack_type_key = format!("{}.t.p", key_parent);
-                save_file.save_i32(&pack_type_key, pack_id.to_index());
-            }
-
-            FixedTableID::MudPit => {
-                save_file.save_i32(&type_key, 7);
-            }
-            FixedTableID::TallGrass => {
-                save_file.save_i32(&type_key, 8);
-            }
-            FixedTableID::Frog => {
-                save_file.save_i32(&type_key, 9);
-            }
-            FixedTableID::Clam => {
-                save_file.save_i32(&type_key, 10);
-            }
-            FixedTableID::Dirt => {
-                save_file.save_i32(&type_key, 11);
-            }
-            FixedTableID::MudHenge => {
-                save_file.save_i32(&type_key, 12);
-            }
-            FixedTableID::MudPig => {
-                save_file.save_i32(&type_key, 13);
-            }
-            FixedTableID::Newt => {
-                save_file.save_i32(&type_key, 14);
-            }
-
-            #[cfg(test)]
-            FixedTableID::TestTable
-            | FixedTableID::TestGold
-            | FixedTableID::TestCycleA
-            | FixedTableID::TestCycleB => {
-                panic!("Should never try to serialize a test table.");
-            }
-        };
+        if let FixedTableID::Pack(pack_id) = self {
+            let pack_type_key = format!("{}.t.p", key_parent);
+            save_file.save_i32(&pack_type_key, pack_id.to_index());
+        }
 
         Ok(())
     }
@@ -157,32 +32,14 @@ impl FixedTableID {
         let type_key = format!("{}.t", key_parent);
         let ty = save_file.load_i32(&type_key)?;
 
-        let fixed_id = match ty {
-            0 => FixedTableID::Grass,
-            1 => FixedTableID::Boulder,
-            2 => FixedTableID::OakTree,
-            3 => FixedTableID::SmallGold,
-            4 => FixedTableID::Cave,
-            5 => FixedTableID::Shrub,
-            6 => {
-                let pack_type_key = format!("{}.t.p", key_parent);
-                let pack_type = save_file.load_i32(&pack_type_key)?;
-
-                FixedTableID::Pack(PackID::from_index(pack_type))
-            }
-            7 => FixedTableID::MudPit,
-            8 => FixedTableID::TallGrass,
-            9 => FixedTableID::Frog,
-            10 => FixedTableID::Clam,
-            11 => FixedTableID::Dirt,
-            12 => FixedTableID::MudHenge,
-            13 => FixedTableID::MudPig,
-            14 => FixedTableID::Newt,
-
-            _ => return Err(Error::UnknownFixedTableID(ty)),
-        };
-
-        Ok(fixed_id)
+        if ty == PACK_TABLE_SAVE_INDEX {
+            let pack_type_key = format!("{}.t.p", key_parent);
+            let pack_type = save_file.load_i32(&pack_type_key)?;
+
+            return Ok(FixedTableID::Pack(PackID::from_index(pack_type)));
+        }
+
+        FixedTableID::from_save_index(ty).ok_or(Error::UnknownFixedTableID(ty))
     }
 }
 
@@ -202,29 +59,6 @@ pub fn get_drop_cycle_check(
 
 pub fn get_fixed_table<'a>(id: FixedTableID) -> &'a DropTable {
     match id {
-        FixedTableID::Grass => &GRASS,
-        FixedTableID::Boulder => &BOULDER,
-        FixedTableID::OakTree => &OAK_TREE,
-        FixedTableID::SmallGold => &SMALL_GOLD,
-        FixedTableID::Cave => &CAVE,
-        FixedTableID::Shrub => &SHRUB,
-        FixedTableID::MudPit => &MUD_PIT,
-        FixedTableID::TallGrass => &TALL_GRASS,
-        FixedTableID::Frog => &FROG,
-        FixedTableID::Clam => &CLAM,
-        FixedTableID::Dirt => &DIRT,
-        FixedTableID::MudHenge => &MUD_HENGE,
-        FixedTableID::MudPig => &MUD_PIG,
-        FixedTableID::Newt => &NEWT,
-
-        FixedTableID::Pack(pack_id) => match pack_id {
-            PackID::Starter => &PACK_STARTER,
-            PackID::Stick => &PACK_STICK,
-            PackID::Mud => &PACK_MUD,
-            PackID::Water => &PACK_WATER,
-            PackID::MudHeart => &PACK_MUD_HEART,
-        },
-
         #[cfg(test)]
         FixedTableID::TestTable => &TEST_TABLE,
         #[cfg(test)]
@@ -233,13 +67,15 @@ pub fn get_fixed_table<'a>(id: FixedTableID) -> &'a DropTable {
         FixedTableID::TestCycleA => &TEST_CYCLE_A,
         #[cfg(test)]
         FixedTableID::TestCycleB => &TEST_CYCLE_B,
+
+        _ => generated_table(id),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::save_file::*;
+    use crate::{save_file::*, testing_infra::*};
 
     #[test]
     fn save_load() {
@@ -308,4 +144,35 @@ mod tests {
         let loaded = FixedTableID::save_file_load("tall_grass".into(), &mut file).unwrap();
         assert_eq!(loaded, FixedTableID::TallGrass);
     }
+
+    #[test]
+    fn save_load_all() {
+        let mut file = SaveFile::new();
+
+        for (i, id) in ALL_FIXED_TABLES.iter().enumerate() {
+            id.save_file_write(format!("{}", i), &mut file).unwrap();
+        }
+
+        for (i, id) in ALL_FIXED_TABLES.iter().enumerate() {
+            let loaded = FixedTableID::save_file_load(format!("{}", i), &file).unwrap();
+            assert_eq!(loaded, *id);
+        }
+    }
+
+    #[test]
+    fn unknown_save_index() {
+        let mut file = SaveFile::new();
+        file.save_i32("0.t", 999);
+
+        assert!(FixedTableID::save_file_load("0".into(), &file).is_err());
+    }
+
+    #[test]
+    fn check_cycles() {
+        let plat_api = windows_plaform_api();
+
+        for id in ALL_FIXED_TABLES.iter() {
+            get_fixed_table(*id).check_cycle(&plat_api);
+        }
+    }
 }
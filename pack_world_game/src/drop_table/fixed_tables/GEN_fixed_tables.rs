@@ -0,0 +1,294 @@
+// Generated by build.rs from design/drop_tables.csv and design/table_ids.csv. Edit those instead.
+
+use crate::{drop_table::*, item::*, pack::pack_id::*, tile::*};
+use std::sync::LazyLock;
+
+/// Pack tables are saved with this index plus the pack index
+pub const PACK_TABLE_SAVE_INDEX: i32 = 6;
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
+pub enum FixedTableID {
+    Grass,
+    Boulder,
+    OakTree,
+    SmallGold,
+    Cave,
+    Shrub,
+    MudPit,
+    TallGrass,
+    Frog,
+    Clam,
+    Dirt,
+    MudHenge,
+    MudPig,
+    Newt,
+    Pack(PackID),
+
+    // testing
+    #[cfg(test)]
+    TestTable,
+    #[cfg(test)]
+    TestGold,
+    #[cfg(test)]
+    TestCycleA,
+    #[cfg(test)]
+    TestCycleB,
+}
+
+/// Every fixed table the game uses, including pack tables. Excludes test tables.
+pub const ALL_FIXED_TABLES: LazyLock<Vec<FixedTableID>> = LazyLock::new(|| {
+    let mut ret = vec![
+        FixedTableID::Grass,
+        FixedTableID::Boulder,
+        FixedTableID::OakTree,
+        FixedTableID::SmallGold,
+        FixedTableID::Cave,
+        FixedTableID::Shrub,
+        FixedTableID::MudPit,
+        FixedTableID::TallGrass,
+        FixedTableID::Frog,
+        FixedTableID::Clam,
+        FixedTableID::Dirt,
+        FixedTableID::MudHenge,
+        FixedTableID::MudPig,
+        FixedTableID::Newt,
+    ];
+    ret.extend(ALL_PACKS.iter().map(|pack_id| FixedTableID::Pack(*pack_id)));
+    ret
+});
+
+impl FixedTableID {
+    pub fn save_index(&self) -> i32 {
+        match self {
+            Self::Grass => 0,
+            Self::Boulder => 1,
+            Self::OakTree => 2,
+            Self::SmallGold => 3,
+            Self::Cave => 4,
+            Self::Shrub => 5,
+            Self::MudPit => 7,
+            Self::TallGrass => 8,
+            Self::Frog => 9,
+            Self::Clam => 10,
+            Self::Dirt => 11,
+            Self::MudHenge => 12,
+            Self::MudPig => 13,
+            Self::Newt => 14,
+            Self::Pack(_) => PACK_TABLE_SAVE_INDEX,
+            #[cfg(test)]
+            _ => panic!("Should never try to serialize a test table."),
+        }
+    }
+
+    /// Pack tables aren't included
+    pub fn from_save_index(index: i32) -> Option<Self> {
+        match index {
+            0 => Some(Self::Grass),
+            1 => Some(Self::Boulder),
+            2 => Some(Self::OakTree),
+            3 => Some(Self::SmallGold),
+            4 => Some(Self::Cave),
+            5 => Some(Self::Shrub),
+            7 => Some(Self::MudPit),
+            8 => Some(Self::TallGrass),
+            9 => Some(Self::Frog),
+            10 => Some(Self::Clam),
+            11 => Some(Self::Dirt),
+            12 => Some(Self::MudHenge),
+            13 => Some(Self::MudPig),
+            14 => Some(Self::Newt),
+            _ => None,
+        }
+    }
+}
+
+pub static GRASS: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::DirtClod, 1), 12.0),
+        (EntryOutput::new_item(ItemType::Stick, 1), 4.0),
+        (EntryOutput::new_item(ItemType::Dew, 1), 4.0),
+    ])
+});
+
+pub static BOULDER: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::Rock, 1), 10.0),
+        (EntryOutput::new_table(FixedTableID::SmallGold, 1), 3.0),
+    ])
+});
+
+pub static OAK_TREE: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::Stick, 1), 3.0),
+        (EntryOutput::new_item(ItemType::OakLog, 1), 3.0),
+        (EntryOutput::new_item(ItemType::Acorn, 1), 2.0),
+    ])
+});
+
+pub static SMALL_GOLD: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_gold(1), 10.0),
+        (EntryOutput::new_gold(7), 4.0),
+        (EntryOutput::new_gold(25), 1.0),
+        (EntryOutput::new_gold(200), 0.1),
+    ])
+});
+
+pub static CAVE: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_table(FixedTableID::SmallGold, 3), 25.0),
+        (EntryOutput::new_item(ItemType::DragonEgg, 1), 0.1),
+        (EntryOutput::new_item(ItemType::Baby, 1), 15.0),
+        (EntryOutput::new_item(ItemType::MudHeart, 1), 15.0),
+    ])
+});
+
+pub static SHRUB: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::Stick, 1), 1.0),
+        (EntryOutput::new_item(ItemType::Berry, 1), 2.0),
+        (EntryOutput::new_tile(TileType::BirdNest, 1), 0.1),
+    ])
+});
+
+pub static MUD_PIT: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_table(FixedTableID::Dirt, 1), 16.0),
+        (EntryOutput::new_item(ItemType::MudHeart, 1), 0.5),
+    ])
+});
+
+pub static TALL_GRASS: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_table(FixedTableID::SmallGold, 3), 25.0),
+    ])
+});
+
+pub static FROG: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::FrogLeg, 1), 1.0),
+        (EntryOutput::new_item(ItemType::PotionHaste, 1), 0.02),
+    ])
+});
+
+pub static CLAM: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_table(FixedTableID::SmallGold, 1), 1.0),
+        (EntryOutput::new_item(ItemType::OldHat, 1), 5.0),
+        (EntryOutput::new_item(ItemType::Seaweed, 1), 5.0),
+        (EntryOutput::new_item(ItemType::TrashBag, 1), 5.0),
+        (EntryOutput::new_item(ItemType::OldBoot, 1), 5.0),
+        (EntryOutput::new_item(ItemType::PotionBounty, 1), 0.2),
+    ])
+});
+
+pub static DIRT: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::Dirt, 1), 10.0),
+        (EntryOutput::new_tile(TileType::Dirt, 4), 5.0),
+        (EntryOutput::new_tile(TileType::Dirt, 10), 1.0),
+        (EntryOutput::new_tile(TileType::Dirt, 25), 0.1),
+    ])
+});
+
+pub static MUD_HENGE: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::MudHeart, 1), 10.0),
+        (EntryOutput::new_item(ItemType::MudHeart, 2), 5.0),
+        (EntryOutput::new_item(ItemType::MudHeart, 3), 1.0),
+    ])
+});
+
+pub static MUD_PIG: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::EyeOfNewt, 1), 1.0),
+        (EntryOutput::new_item(ItemType::FrogLeg, 1), 1.0),
+        (EntryOutput::new_item(ItemType::Root, 1), 1.0),
+    ])
+});
+
+pub static NEWT: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_item(ItemType::EyeOfNewt, 1), 1.0),
+        (EntryOutput::new_item(ItemType::PotionRipen, 1), 0.02),
+    ])
+});
+
+pub static PACK_STARTER: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::Grass, 1), 16.0),
+        (EntryOutput::new_tile(TileType::Dirt, 1), 25.0),
+        (EntryOutput::new_tile(TileType::Shrub, 1), 5.0),
+        (EntryOutput::new_tile(TileType::Boulder, 1), 5.0),
+        (EntryOutput::new_tile(TileType::MudPit, 1), 5.0),
+        (EntryOutput::new_tile(TileType::Frog, 1), 0.5),
+        (EntryOutput::new_gold(20), 0.5),
+    ])
+});
+
+pub static PACK_STICK: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::OakTree, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Boulder, 1), 1.0),
+        (EntryOutput::new_tile(TileType::BirdNest, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Cave, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Shrub, 1), 1.0),
+    ])
+});
+
+pub static PACK_MUD: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::Dirt, 3), 10.0),
+        (EntryOutput::new_tile(TileType::Water, 2), 10.0),
+        (EntryOutput::new_tile(TileType::MudHenge, 1), 3.0),
+        (EntryOutput::new_tile(TileType::Newt, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Reed, 1), 5.0),
+    ])
+});
+
+pub static PACK_WATER: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::Water, 2), 3.0),
+        (EntryOutput::new_tile(TileType::Clam, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Cave, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Spring, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Kelp, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Crab, 1), 1.0),
+        (EntryOutput::new_tile(TileType::MudHenge, 1), 1.0),
+    ])
+});
+
+pub static PACK_MUD_HEART: LazyLock<DropTable> = LazyLock::new(|| {
+    DropTable::new(vec![
+        (EntryOutput::new_tile(TileType::MudFish, 1), 1.0),
+        (EntryOutput::new_tile(TileType::MudChicken, 1), 1.0),
+        (EntryOutput::new_tile(TileType::Goblin, 1), 1.0),
+        (EntryOutput::new_tile(TileType::MudPig, 1), 1.0),
+    ])
+});
+
+pub fn generated_table<'a>(id: FixedTableID) -> &'a DropTable {
+    match id {
+        FixedTableID::Grass => &GRASS,
+        FixedTableID::Boulder => &BOULDER,
+        FixedTableID::OakTree => &OAK_TREE,
+        FixedTableID::SmallGold => &SMALL_GOLD,
+        FixedTableID::Cave => &CAVE,
+        FixedTableID::Shrub => &SHRUB,
+        FixedTableID::MudPit => &MUD_PIT,
+        FixedTableID::TallGrass => &TALL_GRASS,
+        FixedTableID::Frog => &FROG,
+        FixedTableID::Clam => &CLAM,
+        FixedTableID::Dirt => &DIRT,
+        FixedTableID::MudHenge => &MUD_HENGE,
+        FixedTableID::MudPig => &MUD_PIG,
+        FixedTableID::Newt => &NEWT,
+        FixedTableID::Pack(PackID::Starter) => &PACK_STARTER,
+        FixedTableID::Pack(PackID::Stick) => &PACK_STICK,
+        FixedTableID::Pack(PackID::Mud) => &PACK_MUD,
+        FixedTableID::Pack(PackID::Water) => &PACK_WATER,
+        FixedTableID::Pack(PackID::MudHeart) => &PACK_MUD_HEART,
+        #[cfg(test)]
+        _ => panic!("Test tables aren't generated"),
+    }
+}
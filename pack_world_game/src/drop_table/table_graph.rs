@@ -0,0 +1,469 @@
+use crate::{drop_table::*, grid::*, item::*, pack::pack_id::*, tile::*};
+
+/// Something wrong with the drop tables, found without pulling anything
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableProblem {
+    /// Tables which lead back to themselves. The first table is repeated at the end.
+    Cycle(Vec<FixedTableID>),
+
+    /// No pack, tile or other table ever pulls from this table
+    Unreachable(FixedTableID),
+
+    /// Pulling ignores the entry, or breaks the chance values of the entries after it
+    BadWeight {
+        table: FixedTableID,
+        entry: usize,
+        weight: f64,
+    },
+
+    /// Pulling from this panics
+    Empty(FixedTableID),
+
+    /// No pack or tile can drop this item
+    Unobtainable(ItemType),
+}
+
+impl TableProblem {
+    pub fn display(&self) -> String {
+        match self {
+            Self::Cycle(tables) => {
+                let path: Vec<String> = tables.iter().map(|id| format!("{:?}", id)).collect();
+                format!("cycle {}", path.join(" -> "))
+            }
+            Self::Unreachable(table) => format!("{:?} is never pulled from", table),
+            Self::BadWeight {
+                table,
+                entry,
+                weight,
+            } => format!("{:?} entry {} has weight {}", table, entry, weight),
+            Self::Empty(table) => format!("{:?} has no entries", table),
+            Self::Unobtainable(item_type) => format!("{:?} can't be obtained", item_type),
+        }
+    }
+}
+
+/// A tile's harvest table. Only pulled from once the tile itself can be obtained.
+pub struct TileHarvest {
+    pub tile_type: TileType,
+    pub table: FixedTableID,
+
+    /// Entries given outside of the table, like drop conditions
+    pub extra_entries: Vec<EntryOutput>,
+}
+
+/// Where drops come from, for checking which tables and items can be reached
+pub struct TableGraph<'a> {
+    pub tables: Vec<(FixedTableID, &'a DropTable)>,
+
+    /// Tables that can always be pulled from. The packs.
+    pub roots: Vec<FixedTableID>,
+
+    pub harvests: Vec<TileHarvest>,
+}
+
+impl<'a> TableGraph<'a> {
+    /// Every fixed table, rooted at the packs. Tile harvests are followed once the tile is reachable.
+    pub fn from_fixed_tables() -> Self {
+        let tables = ALL_FIXED_TABLES
+            .iter()
+            .map(|id| (*id, get_fixed_table(*id)))
+            .collect();
+
+        let roots: Vec<FixedTableID> = ALL_PACKS
+            .iter()
+            .map(|pack_id| FixedTableID::Pack(*pack_id))
+            .collect();
+
+        let mut harvests: Vec<TileHarvest> = vec![];
+        for tile_type in ALL_TILE_TYPES.iter() {
+            let inst = tile_type.create_instance(GridPos::new(0, 0));
+            if let Some(harvest) = &inst.comp_harvest {
+                harvests.push(TileHarvest {
+                    tile_type: *tile_type,
+                    table: harvest.table,
+                    extra_entries: harvest.condition_entries(),
+                });
+            }
+        }
+
+        Self {
+            tables,
+            roots,
+            harvests,
+        }
+    }
+
+    /// All problems in table order. Deterministic, nothing is pulled.
+    pub fn validate(&self, items: &[ItemType]) -> Vec<TableProblem> {
+        let mut ret: Vec<TableProblem> = vec![];
+
+        for (id, table) in &self.tables {
+            let entries = table.list_entries();
+            if entries.is_empty() {
+                ret.push(TableProblem::Empty(*id));
+            }
+
+            for (i, (_, weight)) in entries.iter().enumerate() {
+                if weight.is_nan() || *weight <= 0.0 {
+                    ret.push(TableProblem::BadWeight {
+                        table: *id,
+                        entry: i,
+                        weight: *weight,
+                    });
+                }
+            }
+        }
+
+        ret.extend(self.cycles().into_iter().map(TableProblem::Cycle));
+
+        let (reached, obtainable) = self.reachable();
+        for (id, _) in &self.tables {
+            if !reached.contains(id) {
+                ret.push(TableProblem::Unreachable(*id));
+            }
+        }
+
+        for item_type in items {
+            if !obtainable.contains(item_type) {
+                ret.push(TableProblem::Unobtainable(*item_type));
+            }
+        }
+
+        ret
+    }
+
+    fn table(&self, id: FixedTableID) -> Option<&'a DropTable> {
+        self.tables
+            .iter()
+            .find(|(table_id, _)| *table_id == id)
+            .map(|(_, table)| *table)
+    }
+
+    fn children(&self, id: FixedTableID) -> Vec<FixedTableID> {
+        let Some(table) = self.table(id) else {
+            return vec![];
+        };
+
+        table
+            .list_entries()
+            .iter()
+            .filter_map(|(output, _)| match output.ty {
+                EntryOutputType::Table(child) => Some(child),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Each cycle is reported once, from the first table of it that's visited
+    fn cycles(&self) -> Vec<Vec<FixedTableID>> {
+        let mut ret: Vec<Vec<FixedTableID>> = vec![];
+        let mut done: Vec<FixedTableID> = vec![];
+
+        for (id, _) in &self.tables {
+            let mut path: Vec<FixedTableID> = vec![];
+            self.find_cycles(*id, &mut path, &mut done, &mut ret);
+        }
+
+        ret
+    }
+
+    fn find_cycles(
+        &self,
+        id: FixedTableID,
+        path: &mut Vec<FixedTableID>,
+        done: &mut Vec<FixedTableID>,
+        cycles: &mut Vec<Vec<FixedTableID>>,
+    ) {
+        if let Some(start) = path.iter().position(|visited| *visited == id) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(id);
+            cycles.push(cycle);
+            return;
+        }
+        if done.contains(&id) {
+            return;
+        }
+
+        path.push(id);
+        for child in self.children(id) {
+            self.find_cycles(child, path, done, cycles);
+        }
+        path.pop();
+
+        done.push(id);
+    }
+
+    /// Tables that can be pulled from and the items they give. A tile's harvest only counts once
+    /// something reachable gives the tile, so this repeats until nothing new is found.
+    fn reachable(&self) -> (Vec<FixedTableID>, Vec<ItemType>) {
+        let mut tables: Vec<FixedTableID> = vec![];
+        let mut items: Vec<ItemType> = vec![];
+        let mut harvested: Vec<TileType> = vec![];
+        let mut next: Vec<FixedTableID> = self.roots.clone();
+
+        loop {
+            while let Some(id) = next.pop() {
+                if tables.contains(&id) {
+                    continue;
+                }
+                tables.push(id);
+
+                if let Some(table) = self.table(id) {
+                    for (output, _) in table.list_entries() {
+                        follow(&output, &mut next, &mut items);
+                    }
+                }
+            }
+
+            let mut found = false;
+            for harvest in &self.harvests {
+                if harvested.contains(&harvest.tile_type)
+                    || !items.contains(&ItemType::Tile(harvest.tile_type))
+                {
+                    continue;
+                }
+                harvested.push(harvest.tile_type);
+                found = true;
+
+                next.push(harvest.table);
+                for output in &harvest.extra_entries {
+                    follow(output, &mut next, &mut items);
+                }
+            }
+
+            if !found {
+                return (tables, items);
+            }
+        }
+    }
+}
+
+fn follow(output: &EntryOutput, next: &mut Vec<FixedTableID>, items: &mut Vec<ItemType>) {
+    match output.ty {
+        EntryOutputType::Table(id) => next.push(id),
+        EntryOutputType::Item(item_type) => {
+            if !items.contains(&item_type) {
+                items.push(item_type);
+            }
+        }
+        EntryOutputType::Gold => {}
+    }
+}
+
+/// Every item and tile a player should be able to get
+pub fn obtainable_item_types() -> Vec<ItemType> {
+    let mut ret: Vec<ItemType> = ALL_ITEM_TYPES.clone();
+    ret.extend(ALL_TILE_TYPES.iter().map(|tile| ItemType::Tile(*tile)));
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn graph<'a>(
+        tables: Vec<(FixedTableID, &'a DropTable)>,
+        roots: Vec<FixedTableID>,
+    ) -> TableGraph<'a> {
+        TableGraph {
+            tables,
+            roots,
+            harvests: vec![],
+        }
+    }
+
+    #[test]
+    fn cycle() {
+        let a = DropTable::new(vec![(
+            EntryOutput::new_table(FixedTableID::TestCycleB, 1),
+            1.0,
+        )]);
+        let b = DropTable::new(vec![
+            (EntryOutput::new_gold(1), 1.0),
+            (EntryOutput::new_table(FixedTableID::TestCycleA, 1), 1.0),
+        ]);
+        let g = graph(
+            vec![
+                (FixedTableID::TestCycleA, &a),
+                (FixedTableID::TestCycleB, &b),
+            ],
+            vec![FixedTableID::TestCycleA],
+        );
+
+        assert_eq!(
+            g.validate(&[]),
+            vec![TableProblem::Cycle(vec![
+                FixedTableID::TestCycleA,
+                FixedTableID::TestCycleB,
+                FixedTableID::TestCycleA
+            ])]
+        );
+    }
+
+    #[test]
+    fn unreachable() {
+        let gold = DropTable::new(vec![(EntryOutput::new_gold(1), 1.0)]);
+        let nested = DropTable::new(vec![(
+            EntryOutput::new_table(FixedTableID::TestGold, 1),
+            1.0,
+        )]);
+        let g = graph(
+            vec![
+                (FixedTableID::TestTable, &nested),
+                (FixedTableID::TestGold, &gold),
+                (FixedTableID::TestCycleA, &gold),
+            ],
+            vec![FixedTableID::TestTable],
+        );
+
+        assert_eq!(
+            g.validate(&[]),
+            vec![TableProblem::Unreachable(FixedTableID::TestCycleA)]
+        );
+    }
+
+    #[test]
+    fn weights_and_empty() {
+        let bad = DropTable::new(vec![
+            (EntryOutput::new_gold(1), 1.0),
+            (EntryOutput::new_gold(2), 0.0),
+            (EntryOutput::new_gold(3), -2.0),
+        ]);
+        let empty = DropTable::new(vec![]);
+        let g = graph(
+            vec![
+                (FixedTableID::TestTable, &bad),
+                (FixedTableID::TestGold, &empty),
+            ],
+            vec![FixedTableID::TestTable, FixedTableID::TestGold],
+        );
+
+        assert_eq!(
+            g.validate(&[]),
+            vec![
+                TableProblem::BadWeight {
+                    table: FixedTableID::TestTable,
+                    entry: 1,
+                    weight: 0.0
+                },
+                TableProblem::BadWeight {
+                    table: FixedTableID::TestTable,
+                    entry: 2,
+                    weight: -2.0
+                },
+                TableProblem::Empty(FixedTableID::TestGold),
+            ]
+        );
+    }
+
+    #[test]
+    fn unobtainable() {
+        let table = DropTable::new(vec![(EntryOutput::new_item(ItemType::Stick, 1), 1.0)]);
+        let orphan = DropTable::new(vec![(EntryOutput::new_item(ItemType::Rock, 1), 1.0)]);
+        let mut g = graph(
+            vec![
+                (FixedTableID::TestTable, &table),
+                (FixedTableID::TestGold, &orphan),
+            ],
+            vec![FixedTableID::TestTable],
+        );
+        g.harvests.push(TileHarvest {
+            tile_type: TileType::Grass,
+            table: FixedTableID::TestTable,
+            extra_entries: vec![EntryOutput::new_item(ItemType::Acorn, 1)],
+        });
+
+        // grass is never given, so its harvest and extra entries don't count
+        let problems = g.validate(&[ItemType::Stick, ItemType::Rock, ItemType::Acorn]);
+        assert_eq!(
+            problems,
+            vec![
+                TableProblem::Unreachable(FixedTableID::TestGold),
+                TableProblem::Unobtainable(ItemType::Rock),
+                TableProblem::Unobtainable(ItemType::Acorn),
+            ]
+        );
+    }
+
+    #[test]
+    fn harvest_needs_tile() {
+        let pack = DropTable::new(vec![(EntryOutput::new_tile(TileType::Grass, 1), 1.0)]);
+        let grass = DropTable::new(vec![(EntryOutput::new_tile(TileType::Shrub, 1), 1.0)]);
+        let shrub = DropTable::new(vec![(EntryOutput::new_item(ItemType::Berry, 1), 1.0)]);
+        let orphan = DropTable::new(vec![(EntryOutput::new_item(ItemType::Pearl, 1), 1.0)]);
+        let mut g = graph(
+            vec![
+                (FixedTableID::TestTable, &pack),
+                (FixedTableID::TestGold, &grass),
+                (FixedTableID::TestCycleA, &shrub),
+                (FixedTableID::TestCycleB, &orphan),
+            ],
+            vec![FixedTableID::TestTable],
+        );
+
+        // shrub is only reached through the grass harvest, and nothing gives tall grass
+        g.harvests = vec![
+            TileHarvest {
+                tile_type: TileType::Shrub,
+                table: FixedTableID::TestCycleA,
+                extra_entries: vec![],
+            },
+            TileHarvest {
+                tile_type: TileType::Grass,
+                table: FixedTableID::TestGold,
+                extra_entries: vec![],
+            },
+            TileHarvest {
+                tile_type: TileType::TallGrass,
+                table: FixedTableID::TestCycleB,
+                extra_entries: vec![],
+            },
+        ];
+
+        assert_eq!(
+            g.validate(&[ItemType::Berry, ItemType::Pearl]),
+            vec![
+                TableProblem::Unreachable(FixedTableID::TestCycleB),
+                TableProblem::Unobtainable(ItemType::Pearl),
+            ]
+        );
+    }
+
+    /// Items known to have no source yet. table_check still lists them.
+    const KNOWN_UNOBTAINABLE: [ItemType; 2] =
+        [ItemType::Pearl, ItemType::Tile(TileType::TallGrass)];
+
+    #[test]
+    fn fixed_tables() {
+        let graph = TableGraph::from_fixed_tables();
+        let problems = graph.validate(&obtainable_item_types());
+
+        for item_type in KNOWN_UNOBTAINABLE {
+            assert!(
+                problems.contains(&TableProblem::Unobtainable(item_type)),
+                "{:?} can be obtained now, take it off the known list",
+                item_type
+            );
+        }
+
+        // the harvest of a tile that can't be obtained is never pulled from either
+        let known_harvests: Vec<FixedTableID> = graph
+            .harvests
+            .iter()
+            .filter(|harvest| KNOWN_UNOBTAINABLE.contains(&ItemType::Tile(harvest.tile_type)))
+            .map(|harvest| harvest.table)
+            .collect();
+
+        let problems: Vec<TableProblem> = problems
+            .into_iter()
+            .filter(|problem| match problem {
+                TableProblem::Unobtainable(item_type) => !KNOWN_UNOBTAINABLE.contains(item_type),
+                TableProblem::Unreachable(table) => !known_harvests.contains(table),
+                _ => true,
+            })
+            .collect();
+
+        let report: Vec<String> = problems.iter().map(|p| p.display()).collect();
+        assert!(problems.is_empty(), "{}", report.join("\n"));
+    }
+}
@@ -48,6 +48,14 @@ impl DropTableInstance {
         }
     }
 
+    /// Exact odds, including any entries added on top of the fixed table
+    pub fn drop_odds(&self) -> Vec<DropOdds> {
+        match self {
+            DropTableInstance::Fixed(table_id) => get_fixed_table(*table_id).drop_odds(),
+            DropTableInstance::Custom(table) => table.drop_odds(),
+        }
+    }
+
     pub fn entries_count(&self) -> usize {
         match self {
             DropTableInstance::Fixed(table_id) => get_fixed_table(*table_id).entries.len(),
@@ -75,7 +83,11 @@ impl DropTableInstance {
             DropTableInstance::Custom(table) => {
                 let id: i32 = 2;
 
+                let custom_key = format!("{}.t.c", key_parent);
+
                 save_file.save_i32(&type_key, id);
+
+                table.save_file_write(custom_key, save_file)?;
             }
         }
 
@@ -94,20 +106,22 @@ impl DropTableInstance {
                 let table_id = FixedTableID::save_file_load(fixed_id_key, save_file)?;
                 return Ok(DropTableInstance::Fixed(table_id));
             }
-            // 2 => Ok(TileGrass::save_file_load(state_key, save_file)?),
+            2 => {
+                let custom_key = format!("{}.t.c", key_parent);
+                let table = DropTable::save_file_load(custom_key, save_file)?;
+                return Ok(DropTableInstance::Custom(table));
+            }
             _ => {
                 return Err(Error::UnknownDropTableInstanceID(id));
             }
         }
-
-        todo!();
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{save_file::*, testing_infra::*};
+    use crate::{item::*, save_file::*, testing_infra::*, tile::*};
 
     #[test]
     pub fn table_conversion() {
@@ -122,6 +136,13 @@ mod test {
 
         table = table.add_entry((EntryOutput::new_gold(1), 1.0));
         assert_eq!(table.entries_count(), 2);
+
+        table = table.add_entry((EntryOutput::new_item(ItemType::Stick, 1), 2.0));
+        let odds = table.drop_odds();
+        assert_eq!(odds.len(), 2);
+        assert_eq!(odds[0].drop, Drop::new_gold(1));
+        assert_eq!(odds[0].chance, 0.5);
+        assert_eq!(odds[1].chance, 0.5);
     }
 
     #[test]
@@ -146,4 +167,79 @@ mod test {
             _ => panic!("Incorrect"),
         }
     }
+
+    #[test]
+    fn save_load_custom() {
+        let mut file = SaveFile::new();
+
+        // Boulder has a nested SmallGold table entry
+        let table = DropTableInstance::new_fixed(FixedTableID::Boulder)
+            .add_entry((EntryOutput::new_tile(TileType::Grass, 2), 0.5))
+            .add_entry((EntryOutput::new_gold(7), 1.5));
+        table.save_file_write("b".into(), &mut file).unwrap();
+
+        DropTableInstance::Fixed(FixedTableID::Cave)
+            .save_file_write("c".into(), &mut file)
+            .unwrap();
+
+        let loaded = DropTableInstance::save_file_load("b".into(), &file).unwrap();
+        let DropTableInstance::Custom(loaded_table) = &loaded else {
+            panic!("Incorrect");
+        };
+        let DropTableInstance::Custom(orig_table) = &table else {
+            panic!("Incorrect");
+        };
+
+        let orig = orig_table.list_entries();
+        let entries = loaded_table.list_entries();
+        assert_eq!(entries.len(), 4);
+        for (i, (output, weight)) in entries.iter().enumerate() {
+            assert_eq!(output.ty, orig[i].0.ty);
+            assert_eq!(output.amount, orig[i].0.amount);
+            assert_eq!(*weight, orig[i].1);
+        }
+
+        assert_eq!(
+            entries[1].0.ty,
+            EntryOutputType::Table(FixedTableID::SmallGold)
+        );
+        assert_eq!(
+            entries[2].0.ty,
+            EntryOutputType::Item(ItemType::Tile(TileType::Grass))
+        );
+        assert_eq!(entries[3].0.ty, EntryOutputType::Gold);
+        assert_eq!(entries[3].0.amount, 7);
+
+        // chance values are rebuilt on load
+        assert_eq!(loaded.drop_odds(), table.drop_odds());
+
+        match DropTableInstance::save_file_load("c".into(), &file).unwrap() {
+            DropTableInstance::Fixed(table_id) => assert_eq!(table_id, FixedTableID::Cave),
+            _ => panic!("Incorrect"),
+        }
+    }
+
+    #[test]
+    fn save_load_custom_empty() {
+        let mut file = SaveFile::new();
+
+        DropTableInstance::Custom(DropTable::new(vec![]))
+            .save_file_write("e".into(), &mut file)
+            .unwrap();
+
+        let loaded = DropTableInstance::save_file_load("e".into(), &file).unwrap();
+        assert!(matches!(loaded, DropTableInstance::Custom(_)));
+        assert_eq!(loaded.entries_count(), 0);
+    }
+
+    #[test]
+    fn save_load_unknown() {
+        let mut file = SaveFile::new();
+        file.save_i32("u.t", 9);
+
+        assert!(matches!(
+            DropTableInstance::save_file_load("u".into(), &file),
+            Err(Error::UnknownDropTableInstanceID(9))
+        ));
+    }
 }
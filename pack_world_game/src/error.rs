@@ -6,18 +6,51 @@ pub enum Error {
     // saveing / loading
     UnknownTileMethodID(i32),
     UnknownDropTableInstanceID(i32),
+    UnknownEntryOutputID(i32),
     UnknownPackID(i32),
+    UnknownItemID(i32),
     UnknownFixedTableID(i32),
-    KeyTooLong(String),
+    UnknownWorldBuffID(i32),
+    UnknownQuestID(i32),
+    UnknownAchievementID(i32),
+    WrongSaveValueType(String),
+    UnknownSaveValueTag(u8),
+    UnknownSaveFormat(u8),
+    SaveFileTruncated,
+    SaveFileTrailingData,
+    SaveFileChecksum,
+    SaveFileTooDeep,
+    NoValidSave,
+    InvalidSaveCode,
+    SaveCodeDamaged,
+    SaveCodeTooLarge,
+    SaveSlotLimit,
+    DeleteActiveSaveSlot,
+    UnknownSaveSlot(usize),
+    NotASnapshot,
+    DeltaBaseMismatch,
     StringUTF8Error(std::string::FromUtf8Error),
 
     NegativeItemCount,
     InvalidTilePosition,
     HitBankLimit,
+    NoPotionTarget,
+    ItemProtected,
+    NotEnoughItems,
+
+    // Trading
+    InvalidTradeCode,
+    TradeCodeDamaged,
+    TradeOwnOffer,
+    TradeAlreadyUsed,
+    UnknownTrade,
+    TradeCodeForged,
 
     // Loading
     InvalidTileTypeIndex(i32),
     MissingKey(String),
+    InvalidSaveVersion(i32),
+    SaveVersionTooNew(i32),
 }
 
 impl From<std::io::Error> for Error {
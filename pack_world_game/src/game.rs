@@ -43,6 +43,7 @@ use std::{
 };
 
 pub mod account_system;
+pub mod achievement;
 pub mod constants;
 pub mod drop_table;
 pub mod error;
@@ -53,9 +54,12 @@ pub mod item;
 pub mod pack;
 pub mod pack_shop_display;
 pub mod pack_shop_signals;
+pub mod quest;
 pub mod save_file;
 pub mod state;
 pub mod tile;
+pub mod toast;
+pub mod trade;
 pub mod ui_panels;
 pub mod update_signal;
 pub mod user_account;
@@ -76,9 +80,12 @@ use item::*;
 use pack::*;
 use pack_shop_display::*;
 use pack_shop_signals::*;
+use quest::*;
 use save_file::*;
-use state::inventory::*;
+use state::{inventory::*, market::*, player_state::*};
 use tile::*;
+use toast::*;
+use trade::*;
 use ui_panels::{debug_panel::*, nav_tabs_panel::*, tile_library_panel::*, *};
 use update_signal::*;
 use user_account::*;
@@ -97,7 +104,8 @@ const BUTTON_BG: Color = Color {
     a: 0.2,
 };
 
-fn setup_initial(world: &mut World, inventory: &mut Inventory) {
+/// Starting world and inventory for a new game
+pub fn setup_initial(world: &mut World, inventory: &mut Inventory) {
     // setup map
     {
         let init_dirt: Vec<GridPos> = vec![
@@ -269,6 +277,9 @@ pub fn game_init(
 
     // setup first map
     setup_initial(&mut gs.world, &mut gs.inventory);
+    gs.player_state.market.seed = Market::new_seed(platform_api);
+    gs.player_state.trades.save_id = TradeLog::new_save_id(platform_api);
+    gs.player_state.trades.secret = TradeLog::new_secret(platform_api);
 
     // make debug panel. Needs to happen here so that the memory is in dll space.
     {
@@ -286,14 +297,68 @@ pub fn game_init(
     }
 }
 
-fn sim_world(gs: &mut State, es: &mut EngineState, ms: f64, platform_api: &PlatformApi) {
-    let world_snapshot = gs.world.get_world_snapshot();
+/// Load the active save slot and simulate the time since it was saved.
+/// A slot without a save, or one that can't be loaded, starts a new game.
+pub fn load_active_slot(gs: &mut State, es: &mut EngineState, platform_api: &PlatformApi) {
+    gs.save_history = SaveHistory::new();
+    let slot_data = gs.save_slots.active().saves();
+
+    let loaded = load_game(
+        &mut gs.world,
+        &mut gs.inventory,
+        &mut gs.player_state,
+        &slot_data,
+        &gs.account_system,
+        &mut gs.save_history,
+        platform_api,
+    );
 
-    let mut update_signals: Vec<UpdateSignal> = vec![];
+    match loaded {
+        Ok(mut ms_to_sim) => {
+            if let Some(report) = &gs.save_history.last_load {
+                if !report.is_clean() {
+                    es.logger.println(&format!(
+                        "Save file partly recovered.\n{}",
+                        report.display()
+                    ));
+                    gs.toasts
+                        .push(Toast::new("Part of your save couldn't be loaded.".into()));
+                    gs.ui_panel_stack
+                        .push(CreatePanelData::LoadReport.create_panel());
+                } else if !report.repaired.is_empty() {
+                    es.logger
+                        .println(&format!("Save file repaired.\n{}", report.display()));
+                }
+            }
+
+            gs.player_state.stats.offline_simulated_ms += ms_to_sim;
+            while ms_to_sim > 0.0 {
+                // println!("Forward Simulating {}ms remaining", ms_to_sim);
+                let ms_step = ms_to_sim.clamp(0.0, MAX_SIM_MS);
+                sim_world(gs, es, ms_step / 1000.0, platform_api);
+                ms_to_sim -= ms_step;
+            }
+        }
+        Err(error) => {
+            if !slot_data.is_empty() {
+                es.logger.println(&format!(
+                    "Error loading save file. Clearing save data. {:?}",
+                    error
+                ));
+            }
+            gs.player_state.clear();
+            gs.player_state.market.seed = Market::new_seed(platform_api);
+            gs.player_state.trades.save_id = TradeLog::new_save_id(platform_api);
+            gs.player_state.trades.secret = TradeLog::new_secret(platform_api);
+            setup_initial(&mut gs.world, &mut gs.inventory);
+        }
+    };
+}
+
+fn sim_world(gs: &mut State, es: &mut EngineState, ms: f64, platform_api: &PlatformApi) {
+    let update_signals: Vec<UpdateSignal> = gs.world.sim_update(ms, platform_api);
+    gs.player_state.market.update(ms * 1000.0);
 
-    for (eid, entity) in &mut gs.world.entities {
-        update_signals.append(&mut entity.sim_update(ms, &world_snapshot, platform_api));
-    }
     handle_signals(update_signals, gs, es, platform_api);
 }
 
@@ -380,8 +445,21 @@ pub fn game_loop(
 
                 if gs.save_queued {
                     gs.save_queued = false;
-                    save_game(&gs.world, &gs.inventory, platform_api).expect("Error saving game.");
-                    println!("Saving game");
+                    match save_game(
+                        &mut gs.world,
+                        &gs.inventory,
+                        &gs.player_state,
+                        &mut gs.save_history,
+                        &mut gs.save_slots,
+                        platform_api,
+                    ) {
+                        Ok(()) => println!("Saving game"),
+                        Err(error) => {
+                            es.logger.println(&format!("Error saving game {:?}", error));
+                            gs.toasts
+                                .push(Toast::new("Your game couldn't be saved.".into()));
+                        }
+                    }
                 }
             }
         }
@@ -389,7 +467,15 @@ pub fn game_loop(
         // manual save for testing
         if build_type_development() {
             if input.keyboard.get_key(KeyCode::Q).on_press {
-                save_game(&gs.world, &gs.inventory, platform_api).expect("Error saving game.");
+                save_game(
+                    &mut gs.world,
+                    &gs.inventory,
+                    &gs.player_state,
+                    &mut gs.save_history,
+                    &mut gs.save_slots,
+                    platform_api,
+                )
+                .expect("Error saving game.");
                 println!("Game manually saved");
             }
 
@@ -402,30 +488,10 @@ pub fn game_loop(
         // check for data to load
         {
             if !es.game_to_load.is_empty() && gs.account_system.user_fetches_finished() {
-                match load_game(
-                    &mut gs.world,
-                    &mut gs.inventory,
-                    &es.game_to_load,
-                    &gs.account_system,
-                    platform_api,
-                ) {
-                    Ok(mut ms_to_sim) => {
-                        while ms_to_sim > 0.0 {
-                            // println!("Forward Simulating {}ms remaining", ms_to_sim);
-                            let ms_step = ms_to_sim.clamp(0.0, MAX_SIM_MS);
-                            sim_world(gs, es, ms_step / 1000.0, platform_api);
-                            ms_to_sim -= ms_step;
-                        }
-                    }
-                    Err(error) => {
-                        es.logger.println(&format!(
-                            "Error loading save file. Clearing save data. {:?}",
-                            error
-                        ));
-                        setup_initial(&mut gs.world, &mut gs.inventory);
-                    }
-                };
+                gs.save_slots = SaveSlots::read(&es.game_to_load);
+                gs.save_slots.fetch_names(platform_api);
                 es.game_to_load.clear();
+                load_active_slot(gs, es, platform_api);
             }
         }
     }
@@ -441,6 +507,9 @@ pub fn game_loop(
                 &mut es.networking_system,
                 &mut ui_frame_state,
                 &gs.inventory,
+                &gs.player_state,
+                &gs.save_slots,
+                &gs.save_history,
                 &mut gs.assets,
                 &mut gs.ui_context.as_mut().unwrap(),
                 platform_api,
@@ -454,6 +523,9 @@ pub fn game_loop(
                 &mut es.networking_system,
                 &mut ui_frame_state,
                 &gs.inventory,
+                &gs.player_state,
+                &gs.save_slots,
+                &gs.save_history,
                 &mut gs.assets,
                 &mut gs.ui_context.as_mut().unwrap(),
                 platform_api,
@@ -467,6 +539,9 @@ pub fn game_loop(
                 &mut es.networking_system,
                 &mut ui_frame_state,
                 &gs.inventory,
+                &gs.player_state,
+                &gs.save_slots,
+                &gs.save_history,
                 &mut gs.assets,
                 &mut gs.ui_context.as_mut().unwrap(),
                 platform_api,
@@ -561,6 +636,9 @@ pub fn game_loop(
                         &mut es.networking_system,
                         &mut ui_frame_state,
                         &gs.inventory,
+                        &gs.player_state,
+                        &gs.save_slots,
+                        &gs.save_history,
                         &mut gs.assets,
                         &mut gs.ui_context.as_mut().unwrap(),
                         platform_api,
@@ -611,6 +689,7 @@ pub fn game_loop(
         }
 
         sim_world(gs, es, frame_delta, platform_api);
+        gs.player_state.stats.time_played += prev_delta_time;
     }
 
     // run tile updates
@@ -654,6 +733,7 @@ pub fn game_loop(
                 render_api,
                 platform_api,
                 &mut gs.inventory,
+                &gs.player_state,
                 &mut gs.assets,
                 gs.ui_context.as_mut().unwrap(),
             ),
@@ -665,6 +745,7 @@ pub fn game_loop(
                 render_api,
                 platform_api,
                 &mut gs.inventory,
+                &gs.player_state,
                 &mut gs.assets,
                 gs.ui_context.as_mut().unwrap(),
                 &mut gs.account_system,
@@ -686,6 +767,11 @@ pub fn game_loop(
         handle_signals(update_sigs, gs, es, platform_api);
     }
 
+    // update quests
+    gs.player_state
+        .quest_log
+        .update_queries(&gs.world, gs.inventory.gold);
+
     // update harvest drops
     {
         let mut sigs: Vec<UpdateSignal> = vec![];
@@ -699,7 +785,10 @@ pub fn game_loop(
             );
 
             if h.is_finished() {
-                sigs.push(UpdateSignal::GiveDrop(h.drop));
+                sigs.push(UpdateSignal::GiveDrop {
+                    drop: h.drop,
+                    source: h.source,
+                });
             }
         }
 
@@ -709,6 +798,20 @@ pub fn game_loop(
         handle_signals(sigs, gs, es, platform_api);
     }
 
+    // update toasts
+    {
+        for (i, toast) in gs.toasts.iter_mut().enumerate() {
+            toast.update_and_draw(
+                prev_delta_time,
+                i,
+                &mut ui_frame_state,
+                gs.ui_context.as_mut().unwrap(),
+            );
+        }
+
+        gs.toasts.retain(|t| !t.is_finished());
+    }
+
     es.render_system
         .render_packs
         .get_mut(&RenderPackID::UI)
@@ -1,3 +1,4 @@
+use crate::state::{market::*, player_state::*};
 pub use crate::{
     assets::*, constants::*, grid::*, inventory::*, item::*, pack::*, pack_shop_display::*,
     pack_shop_signals::*, save_file::*, state::*, theme::*, tile::*, update_signal::*, world::*,
@@ -19,12 +20,16 @@ pub use elara_engine::{
 #[derive(Debug)]
 pub struct GameModeInventory {
     item_selected: Option<ItemType>,
+
+    /// Count of the selected item waiting on sell confirmation
+    sell_confirm: Option<i64>,
 }
 
 impl GameModeInventory {
     pub fn new() -> Self {
         Self {
             item_selected: None,
+            sell_confirm: None,
         }
     }
 
@@ -37,6 +42,7 @@ impl GameModeInventory {
         render_api: &mut impl RenderApi,
         platform_api: &PlatformApi,
         inventory: &mut Inventory,
+        player_state: &PlayerState,
         assets: &mut Assets,
         ui_context: &mut UIContext,
     ) -> Vec<UpdateSignal> {
@@ -213,6 +219,14 @@ impl GameModeInventory {
                         ui_frame_state,
                         ui_context,
                     );
+
+                    ret.append(&mut self.render_merchant(
+                        item_selected,
+                        ui_frame_state,
+                        inventory,
+                        &player_state.market,
+                        ui_context,
+                    ));
                 }
             }
             end_panel(&mut ui_frame_state, ui_context);
@@ -222,6 +236,177 @@ impl GameModeInventory {
         ret
     }
 
+    fn render_merchant(
+        &mut self,
+        item_type: ItemType,
+        ui_state: &mut UIFrameState,
+        inventory: &Inventory,
+        market: &Market,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut ret: Vec<UpdateSignal> = vec![];
+
+        let count = *inventory.items.get(&item_type).unwrap_or(&0);
+        let protected = inventory.is_protected(item_type);
+
+        draw_text(
+            &format!(
+                "Sells for {} gold each (base {})",
+                market.price(item_type),
+                item_type.sell_value()
+            ),
+            VecTwo::new(20.0, 450.0),
+            *THEME_TEXT,
+            &ui_context.font_body.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // price history chart
+        {
+            let history = market.history(item_type);
+            let chart_height: f64 = 80.0;
+            let bar_width: f64 = 15.0;
+            let origin = VecTwo::new(20.0, 620.0);
+
+            let max = history.iter().copied().max().unwrap_or(1).max(1);
+            for (i, price) in history.iter().enumerate() {
+                let height = (*price as f64 / max as f64) * chart_height;
+                let bar = Rect::new_top_size(
+                    origin + VecTwo::new(i as f64 * (bar_width + 3.0), chart_height - height),
+                    bar_width,
+                    height,
+                );
+                draw_rect(bar, *THEME_TEXT_MUT, ui_state, ui_context);
+            }
+
+            draw_text(
+                &format!("Last {} hours, high {}", history.len(), max),
+                origin + VecTwo::new(0.0, chart_height + 25.0),
+                *THEME_TEXT_MUT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        if let Some(event) = market.todays_event() {
+            draw_text(
+                &event.display(),
+                VecTwo::new(20.0, 760.0),
+                *THEME_TEXT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        // protect toggle
+        {
+            let label = if protected { "Unprotect" } else { "Protect" };
+            if draw_button(
+                label,
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(VecTwo::new(20.0, 470.0), 130.0, 35.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                self.sell_confirm = None;
+                ret.push(UpdateSignal::SetItemProtected {
+                    item_type,
+                    protected: !protected,
+                });
+            }
+        }
+
+        if protected {
+            draw_text(
+                "Protected items can't be sold",
+                VecTwo::new(20.0, 545.0),
+                *THEME_TEXT_MUT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+            return ret;
+        }
+
+        match self.sell_confirm {
+            None => {
+                let options: Vec<(&str, i64)> =
+                    vec![("Sell 1", 1), ("Sell 10", 10), ("Sell All", count)];
+
+                for (i, (label, amount)) in options.iter().enumerate() {
+                    if *amount <= 0 || !inventory.has_atleast(item_type, *amount) {
+                        continue;
+                    }
+
+                    let button_rect = Rect::new_top_size(
+                        VecTwo::new(20.0 + (i as f64 * 140.0), 520.0),
+                        130.0,
+                        35.0,
+                    );
+                    if draw_button_id(
+                        i as i32,
+                        label,
+                        ButtonStyleData::new_outline(None),
+                        &button_rect,
+                        ui_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        self.sell_confirm = Some(*amount);
+                    }
+                }
+            }
+
+            Some(amount) => {
+                draw_text(
+                    &format!(
+                        "Sell {} {} for {} gold?",
+                        amount,
+                        item_type.user_title(),
+                        market.quote(item_type, amount)
+                    ),
+                    VecTwo::new(20.0, 545.0),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+
+                if draw_button(
+                    "Confirm",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(VecTwo::new(20.0, 565.0), 130.0, 35.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    self.sell_confirm = None;
+                    ret.push(UpdateSignal::SellItem {
+                        item_type,
+                        count: amount,
+                    });
+                }
+
+                if draw_button(
+                    "Cancel",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(VecTwo::new(160.0, 565.0), 130.0, 35.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    self.sell_confirm = None;
+                }
+            }
+        }
+
+        ret
+    }
+
     fn render_item(
         &mut self,
         i: i32,
@@ -251,6 +436,7 @@ impl GameModeInventory {
                         ui_context,
                     ) {
                         self.item_selected = Some(*item_type);
+                        self.sell_confirm = None;
                     }
                 }
             }
@@ -272,6 +458,9 @@ impl GameModeInventory {
             | ItemType::DragonEgg
             | ItemType::Baby
             | ItemType::EyeOfNewt
+            | ItemType::PotionBounty
+            | ItemType::PotionHaste
+            | ItemType::PotionRipen
             | ItemType::Acorn => {
                 if draw_button_id(
                     i,
@@ -283,6 +472,7 @@ impl GameModeInventory {
                     ui_context,
                 ) {
                     self.item_selected = Some(*item_type);
+                    self.sell_confirm = None;
                 }
             }
         };
@@ -1,3 +1,4 @@
+use crate::state::player_state::*;
 pub use crate::{
     account_system::*, assets::*, grid::*, inventory::*, item::*, pack::*, pack_shop_display::*,
     pack_shop_signals::*, save_file::*, state::*, tile::*, update_signal::*, world::*,
@@ -113,6 +114,7 @@ impl GameModeShop {
         render_api: &mut impl RenderApi,
         platform_api: &PlatformApi,
         inventory: &mut Inventory,
+        player_state: &PlayerState,
         assets: &mut Assets,
         ui_context: &mut UIContext,
         account_system: &mut AccountSystem,
@@ -356,6 +358,7 @@ impl GameModeShop {
                         &input.mouse.button_left,
                         mouse_world,
                         inventory,
+                        &player_state.pity,
                         assets,
                         &mut es.render_system,
                         &mut ui_frame_state,
@@ -19,6 +19,7 @@ pub use elara_engine::{
 pub struct GameModeWorld {
     rotate_time: f64,
     tile_placing: Option<TileType>,
+    potion_using: Option<ItemType>,
 
     // For paint mode
     prev_pos_placed: Option<GridPos>,
@@ -29,6 +30,7 @@ impl GameModeWorld {
         Self {
             rotate_time: 0.0,
             tile_placing: None,
+            potion_using: None,
             prev_pos_placed: None,
         }
     }
@@ -199,6 +201,7 @@ impl GameModeWorld {
 
                         let mut us: Vec<UpdateSignal> = update_sigs;
                         ret.append(&mut us);
+                        ret.push(UpdateSignal::TilePlaced { tile_type: tile });
                     }
                 }
 
@@ -206,11 +209,30 @@ impl GameModeWorld {
                     self.prev_pos_placed = None;
                 }
             }
+
+            // using potions
+            if let Some(potion) = self.potion_using {
+                if input.keyboard.get_key(KeyCode::Escape).on_press {
+                    self.potion_using = None;
+                }
+
+                if input.mouse.button_left.on_press {
+                    ret.push(UpdateSignal::UsePotion {
+                        item_type: potion,
+                        pos: mouse_grid,
+                    });
+
+                    // that was the last one
+                    if !inventory.has_atleast(potion, 2) {
+                        self.potion_using = None;
+                    }
+                }
+            }
         }
 
         // tile hovering
         {
-            if self.tile_placing.is_none() {
+            if self.tile_placing.is_none() && self.potion_using.is_none() {
                 let world_cell: WorldCell = world.get_entities(mouse_grid);
                 let world_snapshot = world.get_world_snapshot();
 
@@ -219,7 +241,7 @@ impl GameModeWorld {
 
                     // Harvesting
                     if input.mouse.button_left.pressing && tile.can_harvest() {
-                        tile.harvest(&world_snapshot, platform_api);
+                        ret.append(&mut tile.harvest(&world_snapshot, platform_api));
                     }
 
                     // render hover rect
@@ -312,6 +334,7 @@ impl GameModeWorld {
                                     ui_context,
                                 ) {
                                     self.tile_placing = Some(*tile_type);
+                                    self.potion_using = None;
                                 }
                             }
                         }
@@ -320,6 +343,63 @@ impl GameModeWorld {
 
                     i += 1;
                 }
+
+                // potions go after the tiles
+                let potion_inv = inventory.get_all_potions();
+                for (item_type, count) in potion_inv {
+                    let disp = format!("{count}");
+                    let icon = assets.get_item_icon(item_type);
+
+                    let mut r = Rect::new_size(icon_size, icon_size);
+                    r.translate(VecTwo::new(70.0, icon_size * 0.5));
+                    r.translate(VecTwo::new(
+                        (i as f64 % 2.0) * gutter,
+                        (i / 2) as f64 * gutter,
+                    ));
+
+                    if draw_button_id(
+                        i,
+                        &disp,
+                        ButtonStyleData::new_shrink(None, Some(icon), 4.0),
+                        &r,
+                        ui_frame_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        self.potion_using = Some(*item_type);
+                        self.tile_placing = None;
+                    }
+
+                    i += 1;
+                }
+            }
+            end_panel(&mut ui_frame_state, ui_context);
+        }
+
+        // active buffs
+        if world.buffs.len() > 0 {
+            let x = ui_frame_state.resolution.x - 300.0;
+
+            begin_panel(
+                Rect::new(
+                    VecTwo::new(x, 57.0),
+                    VecTwo::new(x + 300.0, 57.0 + 20.0 + (world.buffs.len() as f64 * 30.0)),
+                ),
+                Color::new(0.0, 0.0, 0.0, 0.70),
+                ui_frame_state,
+                ui_context,
+            );
+            {
+                for (i, buff) in world.buffs.iter().enumerate() {
+                    draw_text(
+                        &format!("{} {}", buff.kind.title(), buff.remaining.display()),
+                        VecTwo::new(10.0, 30.0 + (i as f64 * 30.0)),
+                        COLOR_WHITE,
+                        &ui_context.font_body.clone(),
+                        ui_frame_state,
+                        ui_context,
+                    );
+                }
             }
             end_panel(&mut ui_frame_state, ui_context);
         }
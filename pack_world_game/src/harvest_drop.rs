@@ -23,6 +23,7 @@ pub static SIN_HEIGHT: f64 = 50.0;
 
 pub struct HarvestDrop {
     pub drop: Drop,
+    pub source: DropSource,
 
     time: f64,
     origin: VecTwo,
@@ -34,13 +35,14 @@ pub struct HarvestDrop {
 }
 
 impl HarvestDrop {
-    pub fn new(drop: Drop, origin: VecTwo, platform_api: &PlatformApi) -> Self {
+    pub fn new(drop: Drop, source: DropSource, origin: VecTwo, platform_api: &PlatformApi) -> Self {
         // not a uniform randomness here
         let x: f64 = ((platform_api.rand)() * DROP_RADIUS * 2.0) - DROP_RADIUS;
         let y: f64 = ((platform_api.rand)() * DROP_RADIUS * 2.0) - DROP_RADIUS;
 
         Self {
             drop,
+            source,
             origin,
             time: 0.0,
 
@@ -1,7 +1,10 @@
-use crate::{error::*, save_file::*, tile::*};
+use crate::{constants::*, error::*, save_file::*, tile::*, world::*};
 use std::sync::LazyLock;
 
 mod item_data;
+pub mod potion;
+
+pub use potion::*;
 
 #[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
 pub enum ItemType {
@@ -23,6 +26,9 @@ pub enum ItemType {
     EyeOfNewt,
     FrogLeg,
     Root,
+    PotionBounty,
+    PotionHaste,
+    PotionRipen,
 
     Tile(TileType),
 }
@@ -47,6 +53,9 @@ pub const ALL_ITEM_TYPES: LazyLock<Vec<ItemType>> = LazyLock::new(|| {
         ItemType::EyeOfNewt,
         ItemType::FrogLeg,
         ItemType::Root,
+        ItemType::PotionBounty,
+        ItemType::PotionHaste,
+        ItemType::PotionRipen,
     ]
 });
 
@@ -71,6 +80,9 @@ impl ItemType {
             ItemType::EyeOfNewt => "Eye of Newt",
             ItemType::FrogLeg => "Frog Leg",
             ItemType::Root => "Root",
+            ItemType::PotionBounty => "Bounty Potion",
+            ItemType::PotionHaste => "Haste Potion",
+            ItemType::PotionRipen => "Ripening Potion",
 
             ItemType::Tile(tile_type) => tile_type.get_definition().title,
         }
@@ -98,97 +110,95 @@ impl ItemType {
             ItemType::EyeOfNewt => Some("Gross!"),
             ItemType::FrogLeg => Some("Please don't kick."),
             ItemType::Root => Some("Very chewwy."),
+            ItemType::PotionBounty => {
+                Some("Doubles drops around where it's poured. Lasts 30 minutes.")
+            }
+            ItemType::PotionHaste => {
+                Some("Tiles around where it's poured harvest twice as fast. Lasts an hour.")
+            }
+            ItemType::PotionRipen => Some("Instantly finishes one tile's harvest."),
         };
 
         ret
     }
 
-    pub fn is_tile(&self) -> bool {
+    /// Potions are consumed when used on the world
+    pub fn potion_effect(&self) -> Option<PotionEffect> {
         match self {
-            ItemType::Tile(_) => true,
-            _ => false,
+            ItemType::PotionBounty => Some(PotionEffect::Buff(WorldBuffKind::DoubleDrops)),
+            ItemType::PotionHaste => Some(PotionEffect::Buff(WorldBuffKind::HarvestSpeed)),
+            ItemType::PotionRipen => Some(PotionEffect::FinishHarvest),
+            _ => None,
         }
     }
 
-    pub fn save_file_write(
-        &self,
-        key_parent: String,
-        save_file: &mut SaveFile,
-    ) -> Result<(), Error> {
-        let id_key = format!("{}.t", key_parent);
-
+    /// Gold the merchant pays for one of these
+    pub fn sell_value(&self) -> i64 {
         match self {
-            Self::DirtClod => {
-                save_file.save_i32(&id_key, 0);
-            }
-            Self::Acorn => {
-                save_file.save_i32(&id_key, 1);
-            }
-            Self::Stick => {
-                save_file.save_i32(&id_key, 2);
-            }
-            Self::Rock => {
-                save_file.save_i32(&id_key, 3);
-            }
-            Self::OakLog => {
-                save_file.save_i32(&id_key, 4);
-            }
-            Self::DragonEgg => {
-                save_file.save_i32(&id_key, 5);
-            }
-            Self::Baby => {
-                save_file.save_i32(&id_key, 6);
-            }
-            Self::Berry => {
-                save_file.save_i32(&id_key, 7);
-            }
-            Self::Tile(tile_type) => {
-                save_file.save_i32(&id_key, 8);
-
-                let tile_type_key = format!("{}.t", id_key);
-                save_file.save_i32(&tile_type_key, tile_type.to_index());
-            }
+            ItemType::DirtClod => 1,
+            ItemType::Stick => 1,
+            ItemType::Rock => 1,
+            ItemType::Dew => 1,
+            ItemType::OakLog => 2,
+            ItemType::Berry => 2,
+            ItemType::Root => 2,
+            ItemType::Seaweed => 2,
+            ItemType::Acorn => 3,
+            ItemType::OldBoot => 3,
+            ItemType::TrashBag => 3,
+            ItemType::OldHat => 3,
+            ItemType::FrogLeg => 6,
+            ItemType::EyeOfNewt => 8,
+            ItemType::MudHeart => 25,
+            ItemType::PotionBounty => 30,
+            ItemType::PotionHaste => 30,
+            ItemType::PotionRipen => 30,
+            ItemType::Pearl => 40,
+            ItemType::Baby => 100,
+            ItemType::DragonEgg => 500,
 
-            Self::MudHeart => {
-                save_file.save_i32(&id_key, 9);
-            }
-            Self::Pearl => {
-                save_file.save_i32(&id_key, 10);
-            }
-            Self::OldBoot => {
-                save_file.save_i32(&id_key, 11);
-            }
-            Self::Seaweed => {
-                save_file.save_i32(&id_key, 12);
-            }
-            Self::TrashBag => {
-                save_file.save_i32(&id_key, 13);
-            }
-            Self::OldHat => {
-                save_file.save_i32(&id_key, 14);
-            }
-            Self::Dew => {
-                save_file.save_i32(&id_key, 15);
-            }
-            Self::EyeOfNewt => {
-                save_file.save_i32(&id_key, 16);
-            }
-            Self::FrogLeg => {
-                save_file.save_i32(&id_key, 17);
-            }
-            Self::Root => {
-                save_file.save_i32(&id_key, 18);
-            }
+            ItemType::Tile(_) => TILE_SELL_VALUE,
         }
+    }
 
-        Ok(())
+    pub fn is_tile(&self) -> bool {
+        match self {
+            ItemType::Tile(_) => true,
+            _ => false,
+        }
     }
 
-    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
-        let id_key = format!("{}.t", key_parent);
-        let id = save_file.load_i32(&id_key).unwrap();
+    /// Save id. Tiles all share one id and save their tile type separately.
+    pub fn to_index(&self) -> i32 {
+        match self {
+            Self::DirtClod => 0,
+            Self::Acorn => 1,
+            Self::Stick => 2,
+            Self::Rock => 3,
+            Self::OakLog => 4,
+            Self::DragonEgg => 5,
+            Self::Baby => 6,
+            Self::Berry => 7,
+            Self::Tile(_) => 8,
+            Self::MudHeart => 9,
+            Self::Pearl => 10,
+            Self::OldBoot => 11,
+            Self::Seaweed => 12,
+            Self::TrashBag => 13,
+            Self::OldHat => 14,
+            Self::Dew => 15,
+            Self::EyeOfNewt => 16,
+            Self::FrogLeg => 17,
+            Self::Root => 18,
+            Self::PotionBounty => 19,
+            Self::PotionHaste => 20,
+            Self::PotionRipen => 21,
+        }
+    }
 
-        match id {
+    /// Inverse of to_index for everything except tiles
+    pub fn from_index(index: i32) -> Result<Self, Error> {
+        match index {
             0 => Ok(Self::DirtClod),
             1 => Ok(Self::Acorn),
             2 => Ok(Self::Stick),
@@ -197,12 +207,6 @@ impl ItemType {
             5 => Ok(Self::DragonEgg),
             6 => Ok(Self::Baby),
             7 => Ok(Self::Berry),
-            8 => {
-                let tile_type_key = format!("{}.t", id_key);
-                let tile_id = save_file.load_i32(&tile_type_key).unwrap();
-
-                return Ok(Self::Tile(TileType::from_index(tile_id)?));
-            }
             9 => Ok(Self::MudHeart),
             10 => Ok(Self::Pearl),
             11 => Ok(Self::OldBoot),
@@ -213,9 +217,42 @@ impl ItemType {
             16 => Ok(Self::EyeOfNewt),
             17 => Ok(Self::FrogLeg),
             18 => Ok(Self::Root),
-            _ => panic!("Invalid item id"),
+            19 => Ok(Self::PotionBounty),
+            20 => Ok(Self::PotionHaste),
+            21 => Ok(Self::PotionRipen),
+            _ => Err(Error::UnknownItemID(index)),
         }
     }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        let id_key = format!("{}.t", key_parent);
+        save_file.save_i32(&id_key, self.to_index());
+
+        if let Self::Tile(tile_type) = self {
+            let tile_type_key = format!("{}.t", id_key);
+            save_file.save_i32(&tile_type_key, tile_type.to_index());
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let id_key = format!("{}.t", key_parent);
+        let id = save_file.load_i32(&id_key)?;
+
+        if id == Self::Tile(TileType::Dirt).to_index() {
+            let tile_type_key = format!("{}.t", id_key);
+            let tile_id = save_file.load_i32(&tile_type_key)?;
+
+            return Ok(Self::Tile(TileType::from_index(tile_id)?));
+        }
+
+        Self::from_index(id)
+    }
 }
 
 mod test {
@@ -290,6 +327,14 @@ mod test {
             ItemType::Berry
         );
 
+        ItemType::PotionHaste
+            .save_file_write("haste".into(), &mut save_file)
+            .unwrap();
+        assert_eq!(
+            ItemType::save_file_load("haste".into(), &save_file).unwrap(),
+            ItemType::PotionHaste
+        );
+
         ItemType::Tile(TileType::Boulder)
             .save_file_write("boulder".into(), &mut save_file)
             .unwrap();
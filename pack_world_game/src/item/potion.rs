@@ -0,0 +1,11 @@
+use crate::world::*;
+
+/// What happens when a potion is used on a grid position
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PotionEffect {
+    /// Start a timed buff centered on the position
+    Buff(WorldBuffKind),
+
+    /// Instantly finish the harvest timer of the tile at the position
+    FinishHarvest,
+}
@@ -3,9 +3,11 @@ use elara_engine::{platform_api::*, vectors::*};
 
 pub mod pack_id;
 pub mod packs;
+pub mod pity;
 
 pub use pack_id::PackID;
 pub use packs::*;
+pub use pity::*;
 
 #[derive(Debug)]
 pub struct Pack {
@@ -17,6 +19,9 @@ pub struct Pack {
     pub content_count: i32,
 
     pub shop_position: VecThreeFloat,
+
+    /// Bad luck protection. Counted per pack in the player state.
+    pub pity: Option<PityRule>,
 }
 
 impl Pack {
@@ -26,6 +31,7 @@ impl Pack {
         content_count: i32,
         table_id: FixedTableID,
         shop_position: VecThreeFloat,
+        pity: Option<PityRule>,
     ) -> Pack {
         Pack {
             display_name,
@@ -33,6 +39,7 @@ impl Pack {
             table_id,
             content_count,
             shop_position,
+            pity,
         }
     }
 
@@ -57,8 +64,17 @@ impl Pack {
         }
     }
 
+    /// Odds of each drop on one pull, before pity
+    pub fn drop_odds(&self) -> Vec<DropOdds> {
+        get_fixed_table(self.table_id).drop_odds()
+    }
+
     // Assumes you can afford the pack
-    pub fn pull(&self, platform_api: &PlatformApi) -> Drop {
-        get_drop(self.table_id, platform_api)
+    // misses is the pity counter for this pack
+    pub fn pull(&self, misses: i64, platform_api: &PlatformApi) -> Drop {
+        match &self.pity {
+            Some(rule) => rule.pull(get_fixed_table(self.table_id), misses, platform_api),
+            None => get_drop(self.table_id, platform_api),
+        }
     }
 }
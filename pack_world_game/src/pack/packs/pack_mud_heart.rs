@@ -13,5 +13,6 @@ pub static MUD_HEART: LazyLock<Pack> = LazyLock::new(|| {
         4,
         FixedTableID::Pack(PackID::MudHeart),
         VecThreeFloat::new(0.0, 0.0, 12.0),
+        None,
     )
 });
@@ -1,7 +1,8 @@
 use crate::{
-    drop_table::FixedTableID,
+    drop_table::{DropType, FixedTableID},
     item::ItemType,
-    pack::{pack_id::PackID, Pack},
+    pack::{pack_id::PackID, Pack, PityKind, PityRule},
+    tile::TileType,
 };
 use elara_engine::vectors::*;
 use std::sync::LazyLock;
@@ -13,5 +14,11 @@ pub static STARTER: LazyLock<Pack> = LazyLock::new(|| {
         4,
         FixedTableID::Pack(PackID::Starter),
         VecThreeFloat::new(0.0, 0.0, 0.0),
+        Some(PityRule::new(
+            vec![DropType::Item {
+                item_type: ItemType::Tile(TileType::Frog),
+            }],
+            PityKind::Guarantee(40),
+        )),
     )
 });
@@ -13,5 +13,6 @@ pub static STICK: LazyLock<Pack> = LazyLock::new(|| {
         4,
         FixedTableID::Pack(PackID::Stick),
         VecThreeFloat::new(-6.0, 0.0, 6.0),
+        None,
     )
 });
@@ -13,5 +13,6 @@ pub static WATER: LazyLock<Pack> = LazyLock::new(|| {
         4,
         FixedTableID::Pack(PackID::Water),
         VecThreeFloat::new(6.0, 0.0, 6.0),
+        None,
     )
 });
@@ -0,0 +1,266 @@
+use crate::{
+    drop_table::*,
+    error::*,
+    pack::{pack_id::*, *},
+    save_file::*,
+};
+use elara_engine::platform_api::*;
+use std::collections::HashMap;
+
+/// Rerolls allowed when pulling a drop that isn't a pity target
+const MAX_REROLLS: i32 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PityKind {
+    /// A target drop is guaranteed once this many pulls in a row have missed
+    Guarantee(i64),
+
+    /// Each miss raises the chance of a target drop by this fraction of its base chance
+    Ramp(f64),
+}
+
+/// Bad luck protection for the rare drops in a pack
+#[derive(Debug, Clone)]
+pub struct PityRule {
+    pub targets: Vec<DropType>,
+    pub kind: PityKind,
+}
+
+impl PityRule {
+    pub fn new(targets: Vec<DropType>, kind: PityKind) -> Self {
+        Self { targets, kind }
+    }
+
+    pub fn is_target(&self, drop_type: DropType) -> bool {
+        self.targets.contains(&drop_type)
+    }
+
+    /// Base chance of pulling any target from the table
+    pub fn base_chance(&self, table: &DropTable) -> f64 {
+        self.targets
+            .iter()
+            .map(|drop_type| table.drop_type_chance(*drop_type))
+            .sum()
+    }
+
+    /// Chance of pulling any target after this many misses
+    pub fn chance(&self, table: &DropTable, misses: i64) -> f64 {
+        let base = self.base_chance(table);
+
+        match self.kind {
+            PityKind::Guarantee(pulls) => {
+                if misses + 1 >= pulls {
+                    1.0
+                } else {
+                    base
+                }
+            }
+            PityKind::Ramp(step) => (base * (1.0 + step * misses as f64)).min(1.0),
+        }
+    }
+
+    pub fn pull(&self, table: &DropTable, misses: i64, platform_api: &PlatformApi) -> Drop {
+        let chance = self.chance(table, misses);
+
+        if (platform_api.rand)() < chance {
+            return self.pull_target(table, platform_api);
+        }
+
+        // Missed the boosted roll. Reroll until it's not a target so the total chance stays correct.
+        let mut tables_visited: Vec<FixedTableID> = vec![];
+        let mut drop = table.pull(&mut tables_visited, platform_api);
+        for _ in 0..MAX_REROLLS {
+            if !self.is_target(drop.drop_type) {
+                break;
+            }
+            tables_visited.clear();
+            drop = table.pull(&mut tables_visited, platform_api);
+        }
+        drop
+    }
+
+    /// One of the targets, weighted by their chances in the table
+    fn pull_target(&self, table: &DropTable, platform_api: &PlatformApi) -> Drop {
+        let drops = table.list_drops();
+        let weights: Vec<f64> = self
+            .targets
+            .iter()
+            .map(|drop_type| table.drop_type_chance(*drop_type))
+            .collect();
+
+        let total: f64 = weights.iter().sum();
+        let mut roll = (platform_api.rand)() * total;
+
+        let mut picked = self.targets[0];
+        for (drop_type, weight) in self.targets.iter().zip(weights.iter()) {
+            if roll < *weight {
+                picked = *drop_type;
+                break;
+            }
+            roll -= weight;
+        }
+
+        drops
+            .into_iter()
+            .find(|drop| drop.drop_type == picked)
+            .unwrap_or(Drop::new(picked, 1))
+    }
+
+    pub fn display(&self, table: &DropTable, misses: i64) -> String {
+        let names: Vec<String> = self
+            .targets
+            .iter()
+            .map(|drop_type| match drop_type {
+                DropType::Gold => "Gold".to_string(),
+                DropType::Item { item_type } => item_type.user_title().to_string(),
+            })
+            .collect();
+        let names = names.join(" or ");
+
+        match self.kind {
+            PityKind::Guarantee(pulls) => format!(
+                "{} guaranteed within {} pulls",
+                names,
+                (pulls - misses).max(1)
+            ),
+            PityKind::Ramp(_) => format!(
+                "{} chance {:.1}% after {} misses",
+                names,
+                self.chance(table, misses) * 100.0,
+                misses
+            ),
+        }
+    }
+}
+
+/// Pulls since the last pity target, per pack
+#[derive(Debug)]
+pub struct PityCounters {
+    misses: HashMap<PackID, i64>,
+}
+
+impl PityCounters {
+    pub fn new() -> Self {
+        Self {
+            misses: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.misses.clear();
+    }
+
+    pub fn misses(&self, pack_id: PackID) -> i64 {
+        *self.misses.get(&pack_id).unwrap_or(&0)
+    }
+
+    pub fn record_pull(&mut self, pack_id: PackID, drop: &Drop) {
+        let Some(rule) = &pack_id.get_pack_info().pity else {
+            return;
+        };
+
+        if rule.is_target(drop.drop_type) {
+            self.misses.remove(&pack_id);
+        } else {
+            *self.misses.entry(pack_id).or_insert(0) += 1;
+        }
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        for (pack_id, misses) in &self.misses {
+            save_file.save_i64(
+                &format!("{}pity.{}", key_parent, pack_id.to_index()),
+                *misses,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        for pack_id in ALL_PACKS.iter() {
+            let key = format!("{}pity.{}", key_parent, pack_id.to_index());
+            if let Ok(misses) = save_file.load_i64(&key) {
+                ret.misses.insert(*pack_id, misses);
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{item::*, testing_infra::*, tile::*};
+
+    fn frog() -> DropType {
+        DropType::Item {
+            item_type: ItemType::Tile(TileType::Frog),
+        }
+    }
+
+    #[test]
+    fn guarantee() {
+        let plat_api = windows_plaform_api();
+        let table = get_fixed_table(FixedTableID::Pack(PackID::Starter));
+        let rule = PityRule::new(vec![frog()], PityKind::Guarantee(10));
+
+        assert!(rule.chance(table, 0) < 0.1);
+        assert_eq!(rule.chance(table, 9), 1.0);
+
+        for _ in 0..20 {
+            assert_eq!(rule.pull(table, 9, &plat_api).drop_type, frog());
+        }
+    }
+
+    #[test]
+    fn ramp() {
+        let table = get_fixed_table(FixedTableID::Pack(PackID::Starter));
+        let rule = PityRule::new(vec![frog()], PityKind::Ramp(1.0));
+
+        let base = rule.base_chance(table);
+        assert_eq!(rule.chance(table, 0), base);
+        assert!((rule.chance(table, 3) - base * 4.0).abs() < 0.0001);
+        assert_eq!(rule.chance(table, 100_000), 1.0);
+    }
+
+    #[test]
+    fn counters() {
+        let mut counters = PityCounters::new();
+        let rule = PackID::Starter.get_pack_info().pity.clone().unwrap();
+        let target = Drop::new(rule.targets[0], 1);
+
+        counters.record_pull(PackID::Starter, &Drop::new_gold(20));
+        counters.record_pull(PackID::Starter, &Drop::new_tile(TileType::Dirt, 1));
+        assert_eq!(counters.misses(PackID::Starter), 2);
+
+        counters.record_pull(PackID::Starter, &target);
+        assert_eq!(counters.misses(PackID::Starter), 0);
+
+        // packs without a rule don't count
+        counters.record_pull(PackID::Stick, &Drop::new_tile(TileType::Shrub, 1));
+        assert_eq!(counters.misses(PackID::Stick), 0);
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut counters = PityCounters::new();
+        for _ in 0..7 {
+            counters.record_pull(PackID::Starter, &Drop::new_gold(20));
+        }
+        counters.save_file_write("".into(), &mut save_file).unwrap();
+
+        let loaded = PityCounters::save_file_load("".into(), &save_file).unwrap();
+        assert_eq!(loaded.misses(PackID::Starter), 7);
+        assert_eq!(loaded.misses(PackID::Mud), 0);
+    }
+}
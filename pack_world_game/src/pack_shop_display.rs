@@ -1,5 +1,5 @@
 use crate::{
-    drop_table::*, inventory::*, item::*, pack::*, pack::*, pack_shop_signals::*, state::assets::*,
+    drop_table::*, inventory::*, item::*, pack::*, pack_shop_signals::*, state::assets::*,
     ui_panels::*, update_signal::*,
 };
 use elara_engine::{
@@ -87,6 +87,7 @@ impl PackShopDisplay {
         mouse_left: &elara_engine::input::ButtonState,
         mouse_world: VecThreeFloat,
         inventory: &Inventory,
+        pity: &PityCounters,
         assets: &mut Assets,
         render_system: &mut RenderSystem,
         mut ui_frame_state: &mut UIFrameState,
@@ -126,9 +127,12 @@ impl PackShopDisplay {
             PackShopDisplayState::Opening => {
                 if hovering && mouse_left.on_press && self.items_remaining > 0 {
                     // pull item from pack and give
-                    let pull = pack_info.pull(platform_api);
+                    let pull = pack_info.pull(pity.misses(pack_id), platform_api);
                     ret.push(PackShopSignals::StandardUpateSignal {
-                        sigs: vec![UpdateSignal::GiveDrop(pull)],
+                        sigs: vec![UpdateSignal::GiveDrop {
+                            drop: pull,
+                            source: DropSource::Pack(pack_id),
+                        }],
                     });
 
                     self.items_remaining -= 1;
@@ -0,0 +1,125 @@
+use crate::{drop_table::*, item::*, pack::*, tile::*, world::*};
+
+pub mod quest_id;
+pub mod quest_log;
+pub mod quests;
+
+pub use quest_id::*;
+pub use quest_log::*;
+pub use quests::*;
+
+#[derive(Debug)]
+pub struct Quest {
+    pub title: String,
+    pub description: String,
+    pub objective: QuestObjective,
+    pub rewards: Vec<Drop>,
+
+    /// Quest that must be claimed before this one becomes active. This is how chains are built.
+    pub requires: Option<QuestID>,
+}
+
+impl Quest {
+    pub fn new(
+        title: String,
+        description: String,
+        objective: QuestObjective,
+        rewards: Vec<Drop>,
+        requires: Option<QuestID>,
+    ) -> Self {
+        Self {
+            title,
+            description,
+            objective,
+            rewards,
+            requires,
+        }
+    }
+}
+
+/// Something that happened in the game which might progress a quest
+#[derive(Debug, Copy, Clone)]
+pub enum QuestEvent {
+    HarvestedTile(TileType),
+    PlacedTile(TileType),
+    OpenedPack(PackID),
+    GotDrop(Drop),
+}
+
+#[derive(Debug)]
+pub enum QuestObjective {
+    // Counted from events. Only counts while the quest is active.
+    HarvestTile { tile_type: TileType, count: i64 },
+    PlaceTile { tile_type: TileType, count: i64 },
+    OpenPack { pack_id: PackID, count: i64 },
+    CollectItem { item_type: ItemType, count: i64 },
+
+    // Queried from the current state
+    OwnTiles { tile: TileSnapshot, count: i64 },
+    HaveGold { amount: i64 },
+}
+
+impl QuestObjective {
+    pub fn target(&self) -> i64 {
+        match self {
+            Self::HarvestTile { count, .. }
+            | Self::PlaceTile { count, .. }
+            | Self::OpenPack { count, .. }
+            | Self::CollectItem { count, .. }
+            | Self::OwnTiles { count, .. } => *count,
+            Self::HaveGold { amount } => *amount,
+        }
+    }
+
+    /// Query objectives are recalculated from the world instead of counted from events
+    pub fn is_query(&self) -> bool {
+        match self {
+            Self::OwnTiles { .. } | Self::HaveGold { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// How much this event progresses the objective
+    pub fn event_progress(&self, event: &QuestEvent) -> i64 {
+        match (self, event) {
+            (Self::HarvestTile { tile_type, .. }, QuestEvent::HarvestedTile(ty))
+            | (Self::PlaceTile { tile_type, .. }, QuestEvent::PlacedTile(ty)) => {
+                if tile_type == ty {
+                    return 1;
+                }
+            }
+            (Self::OpenPack { pack_id, .. }, QuestEvent::OpenedPack(id)) => {
+                if pack_id == id {
+                    return 1;
+                }
+            }
+            (Self::CollectItem { item_type, .. }, QuestEvent::GotDrop(drop)) => {
+                if drop.drop_type
+                    == (DropType::Item {
+                        item_type: *item_type,
+                    })
+                {
+                    return drop.amount;
+                }
+            }
+            _ => {}
+        }
+
+        0
+    }
+
+    /// Current progress for query objectives. None for counted objectives.
+    pub fn query_progress(&self, world: &World, gold: i64) -> Option<i64> {
+        match self {
+            Self::OwnTiles { tile, .. } => Some(
+                world
+                    .entities
+                    .values()
+                    .filter(|inst| inst.into_snapshot() == *tile)
+                    .count() as i64,
+            ),
+            Self::HaveGold { .. } => Some(gold),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{error::*, quest::*};
+use std::sync::LazyLock;
+
+#[derive(Debug, Hash, Eq, PartialEq, Clone, Copy)]
+pub enum QuestID {
+    FirstSteps,
+    GrassHarvest,
+    StickPacks,
+    MudPacks,
+    Nesting,
+    BerryPicking,
+    Hoarder,
+}
+
+pub const ALL_QUESTS: LazyLock<Vec<QuestID>> = LazyLock::new(|| {
+    vec![
+        QuestID::FirstSteps,
+        QuestID::GrassHarvest,
+        QuestID::StickPacks,
+        QuestID::MudPacks,
+        QuestID::Nesting,
+        QuestID::BerryPicking,
+        QuestID::Hoarder,
+    ]
+});
+
+impl QuestID {
+    pub fn to_index(&self) -> i32 {
+        match self {
+            Self::FirstSteps => 0,
+            Self::GrassHarvest => 1,
+            Self::StickPacks => 2,
+            Self::MudPacks => 3,
+            Self::Nesting => 4,
+            Self::BerryPicking => 5,
+            Self::Hoarder => 6,
+        }
+    }
+
+    pub fn from_index(index: i32) -> Result<Self, Error> {
+        match index {
+            0 => Ok(Self::FirstSteps),
+            1 => Ok(Self::GrassHarvest),
+            2 => Ok(Self::StickPacks),
+            3 => Ok(Self::MudPacks),
+            4 => Ok(Self::Nesting),
+            5 => Ok(Self::BerryPicking),
+            6 => Ok(Self::Hoarder),
+            _ => Err(Error::UnknownQuestID(index)),
+        }
+    }
+
+    pub fn get_quest_info(&self) -> &'static Quest {
+        match self {
+            Self::FirstSteps => &FIRST_STEPS,
+            Self::GrassHarvest => &GRASS_HARVEST,
+            Self::StickPacks => &STICK_PACKS,
+            Self::MudPacks => &MUD_PACKS,
+            Self::Nesting => &NESTING,
+            Self::BerryPicking => &BERRY_PICKING,
+            Self::Hoarder => &HOARDER,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_round_trip() {
+        for id in ALL_QUESTS.iter() {
+            assert_eq!(QuestID::from_index(id.to_index()).unwrap(), *id);
+        }
+    }
+
+    #[test]
+    fn chains_are_defined() {
+        // Every required quest must come earlier in the list, otherwise the chain can never start.
+        for (i, id) in ALL_QUESTS.iter().enumerate() {
+            if let Some(req) = id.get_quest_info().requires {
+                let req_i = ALL_QUESTS.iter().position(|q| *q == req).unwrap();
+                assert!(req_i < i);
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{drop_table::*, error::*, quest::*, save_file::*, world::*};
+use std::collections::HashMap;
+
+/// Tracks progress on all the quests
+#[derive(Debug)]
+pub struct QuestLog {
+    progress: HashMap<QuestID, i64>,
+    claimed: HashMap<QuestID, bool>,
+}
+
+impl QuestLog {
+    pub fn new() -> Self {
+        Self {
+            progress: HashMap::new(),
+            claimed: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.progress.clear();
+        self.claimed.clear();
+    }
+
+    pub fn is_claimed(&self, id: QuestID) -> bool {
+        *self.claimed.get(&id).unwrap_or(&false)
+    }
+
+    /// Active quests are unclaimed and have their required quest claimed
+    pub fn is_active(&self, id: QuestID) -> bool {
+        if self.is_claimed(id) {
+            return false;
+        }
+
+        match id.get_quest_info().requires {
+            Some(req) => self.is_claimed(req),
+            None => true,
+        }
+    }
+
+    pub fn active_quests(&self) -> Vec<QuestID> {
+        ALL_QUESTS
+            .iter()
+            .filter(|id| self.is_active(**id))
+            .copied()
+            .collect()
+    }
+
+    pub fn progress(&self, id: QuestID) -> i64 {
+        *self.progress.get(&id).unwrap_or(&0)
+    }
+
+    pub fn is_complete(&self, id: QuestID) -> bool {
+        self.progress(id) >= id.get_quest_info().objective.target()
+    }
+
+    /// Count an event towards all active quests
+    pub fn handle_event(&mut self, event: QuestEvent) {
+        for id in self.active_quests() {
+            let amount = id.get_quest_info().objective.event_progress(&event);
+            if amount > 0 {
+                *self.progress.entry(id).or_insert(0) += amount;
+            }
+        }
+    }
+
+    /// Recalculate progress of the objectives that query the world
+    pub fn update_queries(&mut self, world: &World, gold: i64) {
+        for id in self.active_quests() {
+            if let Some(prog) = id.get_quest_info().objective.query_progress(world, gold) {
+                self.progress.insert(id, prog);
+            }
+        }
+    }
+
+    /// Returns the reward drops if the quest could be claimed
+    pub fn claim(&mut self, id: QuestID) -> Option<Vec<Drop>> {
+        if !self.is_active(id) || !self.is_complete(id) {
+            return None;
+        }
+
+        self.claimed.insert(id, true);
+        self.progress.remove(&id);
+
+        Some(id.get_quest_info().rewards.clone())
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        for id in ALL_QUESTS.iter() {
+            let i = id.to_index();
+
+            if self.is_claimed(*id) {
+                save_file.save_bool(&format!("{}.q.{}.c", key_parent, i), true);
+            }
+
+            let prog = self.progress(*id);
+            if prog > 0 {
+                save_file.save_i64(&format!("{}.q.{}.p", key_parent, i), prog);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        // Missing keys are fine. Quests with no progress aren't written.
+        for id in ALL_QUESTS.iter() {
+            let i = id.to_index();
+
+            if let Ok(claimed) = save_file.load_bool(&format!("{}.q.{}.c", key_parent, i)) {
+                ret.claimed.insert(*id, claimed);
+            }
+
+            if let Ok(prog) = save_file.load_i64(&format!("{}.q.{}.p", key_parent, i)) {
+                ret.progress.insert(*id, prog);
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{grid::*, item::*, pack::*, tile::*};
+
+    #[test]
+    fn chain_unlocks() {
+        let mut log = QuestLog::new();
+
+        assert!(log.is_active(QuestID::FirstSteps));
+        assert!(!log.is_active(QuestID::GrassHarvest));
+
+        // not finished yet
+        assert!(log.claim(QuestID::FirstSteps).is_none());
+
+        for _ in 0..4 {
+            log.handle_event(QuestEvent::PlacedTile(TileType::Grass));
+        }
+        log.handle_event(QuestEvent::PlacedTile(TileType::Dirt));
+        assert_eq!(log.progress(QuestID::FirstSteps), 4);
+
+        let rewards = log.claim(QuestID::FirstSteps).unwrap();
+        assert_eq!(rewards, FIRST_STEPS.rewards);
+
+        // can't claim twice
+        assert!(log.claim(QuestID::FirstSteps).is_none());
+
+        assert!(!log.is_active(QuestID::FirstSteps));
+        assert!(log.is_active(QuestID::GrassHarvest));
+        assert!(log.is_active(QuestID::BerryPicking));
+    }
+
+    #[test]
+    fn inactive_quests_dont_count() {
+        let mut log = QuestLog::new();
+
+        log.handle_event(QuestEvent::OpenedPack(PackID::Stick));
+        assert_eq!(log.progress(QuestID::StickPacks), 0);
+    }
+
+    #[test]
+    fn collect_counts_amount() {
+        let mut log = QuestLog::new();
+        log.claimed.insert(QuestID::FirstSteps, true);
+
+        log.handle_event(QuestEvent::GotDrop(Drop::new_item(ItemType::Berry, 3)));
+        log.handle_event(QuestEvent::GotDrop(Drop::new_item(ItemType::Stick, 3)));
+        log.handle_event(QuestEvent::GotDrop(Drop::new_gold(3)));
+        assert_eq!(log.progress(QuestID::BerryPicking), 3);
+    }
+
+    #[test]
+    fn own_tiles_query() {
+        let mut world = World::new();
+        let mut log = QuestLog::new();
+        for id in [
+            QuestID::FirstSteps,
+            QuestID::GrassHarvest,
+            QuestID::StickPacks,
+            QuestID::MudPacks,
+        ] {
+            log.claimed.insert(id, true);
+        }
+
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Dirt);
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::OakTree);
+        let _ = world.insert_tile_type(GridPos::new(5, 0), TileType::Dirt);
+        let _ = world.insert_tile_type(GridPos::new(5, 0), TileType::OakTree);
+
+        log.update_queries(&world, 0);
+        assert_eq!(log.progress(QuestID::Nesting), 0);
+
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::BirdNest);
+        log.update_queries(&world, 0);
+        assert_eq!(log.progress(QuestID::Nesting), 1);
+
+        log.update_queries(&world, 20_000);
+        assert!(log.is_complete(QuestID::Hoarder));
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut log = QuestLog::new();
+        log.claimed.insert(QuestID::FirstSteps, true);
+        log.progress.insert(QuestID::GrassHarvest, 12);
+
+        log.save_file_write("".into(), &mut save_file).unwrap();
+        let loaded = QuestLog::save_file_load("".into(), &save_file).unwrap();
+
+        assert!(loaded.is_claimed(QuestID::FirstSteps));
+        assert!(!loaded.is_claimed(QuestID::GrassHarvest));
+        assert_eq!(loaded.progress(QuestID::GrassHarvest), 12);
+        assert_eq!(loaded.progress(QuestID::StickPacks), 0);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{drop_table::*, item::*, pack::*, quest::*, tile::*, world::*};
+use std::sync::LazyLock;
+
+// Main chain
+
+pub static FIRST_STEPS: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "First Steps".into(),
+        "Place some grass. It grows on dirt.".into(),
+        QuestObjective::PlaceTile {
+            tile_type: TileType::Grass,
+            count: 4,
+        },
+        vec![Drop::new_gold(25), Drop::new_tile(TileType::Dirt, 2)],
+        None,
+    )
+});
+
+pub static GRASS_HARVEST: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Mowing".into(),
+        "Harvest grass 50 times.".into(),
+        QuestObjective::HarvestTile {
+            tile_type: TileType::Grass,
+            count: 50,
+        },
+        vec![Drop::new_item(ItemType::Stick, 20)],
+        Some(QuestID::FirstSteps),
+    )
+});
+
+pub static STICK_PACKS: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Stick Collector".into(),
+        "Open 3 Stick packs.".into(),
+        QuestObjective::OpenPack {
+            pack_id: PackID::Stick,
+            count: 3,
+        },
+        vec![Drop::new_gold(100), Drop::new_tile(TileType::Shrub, 1)],
+        Some(QuestID::GrassHarvest),
+    )
+});
+
+pub static MUD_PACKS: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Mud Lover".into(),
+        "Open 5 Mud packs.".into(),
+        QuestObjective::OpenPack {
+            pack_id: PackID::Mud,
+            count: 5,
+        },
+        vec![Drop::new_item(ItemType::MudHeart, 1)],
+        Some(QuestID::StickPacks),
+    )
+});
+
+pub static NESTING: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Nesting Season".into(),
+        "Own 3 Oak Trees with bird nests.".into(),
+        QuestObjective::OwnTiles {
+            tile: TileSnapshot::OakTree { has_nest: true },
+            count: 3,
+        },
+        vec![Drop::new_gold(1000)],
+        Some(QuestID::MudPacks),
+    )
+});
+
+// Side quests
+
+pub static BERRY_PICKING: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Berry Picking".into(),
+        "Collect 10 berries.".into(),
+        QuestObjective::CollectItem {
+            item_type: ItemType::Berry,
+            count: 10,
+        },
+        vec![Drop::new_gold(50)],
+        Some(QuestID::FirstSteps),
+    )
+});
+
+pub static HOARDER: LazyLock<Quest> = LazyLock::new(|| {
+    Quest::new(
+        "Hoarder".into(),
+        "Have 10,000 gold.".into(),
+        QuestObjective::HaveGold { amount: 10_000 },
+        vec![Drop::new_item(ItemType::PotionBounty, 1)],
+        Some(QuestID::StickPacks),
+    )
+});
@@ -1,18 +1,41 @@
 use crate::account_system::*;
 use crate::{
-    error::Error, grid::*, state::inventory::*, tile::tile_instance::TileInstance, world::*,
+    error::Error,
+    grid::*,
+    state::{inventory::*, market::*, player_state::*},
+    tile::tile_instance::TileInstance,
+    trade::*,
+    world::*,
 };
 use elara_engine::platform_api::*;
 use std::io::{Cursor, Read, Seek, Write};
 
+pub mod delta_save;
 pub mod kvp_file;
 pub mod load;
+pub mod migration;
+pub mod save_code;
+pub mod save_slots;
+pub mod snapshot;
 
-pub use kvp_file::SaveFile;
+pub use delta_save::*;
+pub use kvp_file::{split_saves, SaveFile};
+pub use migration::*;
+pub use save_code::*;
+pub use save_slots::*;
+pub use snapshot::*;
 
 pub const TILE_INSTANCE_ID_CHAR: char = 'E';
 pub const VALID_ADJ_ID_CHAR: char = 'V';
 pub const DROP_MOD_CHAR: char = 'G';
+pub const HARVEST_SPEED_MOD_CHAR: char = 'S';
+pub const BUFF_ID_CHAR: char = 'B';
+
+/// Older saves written after the newest one, to fall back on if it's broken
+pub const SAVE_BACKUPS: usize = 2;
+
+/// Section holding entries that failed to load
+pub const QUARANTINE_KEY: &str = "quarantine";
 
 pub const SIM_LIMIT_H_FREE: i32 = 6;
 pub const SIM_LIMIT_H_PREMIUM: i32 = 24;
@@ -20,130 +43,667 @@ pub const SIM_LIMIT_H_PREMIUM: i32 = 24;
 pub fn build_save_file(
     world: &World,
     inventory: &Inventory,
+    player_state: &PlayerState,
     platform_api: &PlatformApi,
 ) -> Result<SaveFile, Error> {
     let mut save_file = SaveFile::new();
 
-    // world stuff. This should probably move into world struct
-    {
-        // write tile instances
-        for t in &world.entities {
-            let key = format!("{}.{}", TILE_INSTANCE_ID_CHAR, t.0.id);
-            save_file.save_u64(&key, t.0.id);
+    // write tile instances
+    for (eid, tile) in &world.entities {
+        write_entity(*eid, tile, &mut save_file)?;
+    }
+
+    write_world_globals(world, &mut save_file)?;
+    write_player(inventory, player_state, platform_api, &mut save_file)?;
+
+    Ok(save_file)
+}
+
+/// Everything in KeyGroup::Entity
+fn write_entity(eid: EntityID, tile: &TileInstance, save_file: &mut SaveFile) -> Result<(), Error> {
+    let key = format!("{}.{}", TILE_INSTANCE_ID_CHAR, eid.id);
+    save_file.save_u64(&key, eid.id);
+
+    tile.save_file_write(format!("{}", eid.id), save_file)
+}
+
+/// Everything in KeyGroup::World. This should probably move into world struct
+fn write_world_globals(world: &World, save_file: &mut SaveFile) -> Result<(), Error> {
+    // write valid adjacent positions
+    for (i, (key, value)) in world.valids.iter().enumerate() {
+        let id_key = format!("{}.{}", VALID_ADJ_ID_CHAR, i);
+        save_file.save_i32(&id_key, i as i32);
+
+        save_file.save_i32(&format!("valid_x.{}", i as i32), key.x);
+        save_file.save_i32(&format!("valid_y.{}", i as i32), key.y);
+    }
+
+    // write global drop mods
+    for (i, (key, value)) in world.drop_count_mod.iter().enumerate() {
+        let id_key = format!("{}.{}", DROP_MOD_CHAR, i);
+        save_file.save_i32(&id_key, i as i32);
+
+        save_file.save_i32(&format!("drop_mod_x.{}", i as i32), key.x);
+        save_file.save_i32(&format!("drop_mod_y.{}", i as i32), key.y);
+        save_file.save_f64(&format!("drop_mod_v.{}", i as i32), *value);
+    }
+
+    // write global harvest speed mods
+    for (i, (key, value)) in world.harvest_speed_mod.iter().enumerate() {
+        let id_key = format!("{}.{}", HARVEST_SPEED_MOD_CHAR, i);
+        save_file.save_i32(&id_key, i as i32);
+
+        save_file.save_i32(&format!("speed_mod_x.{}", i as i32), key.x);
+        save_file.save_i32(&format!("speed_mod_y.{}", i as i32), key.y);
+        save_file.save_f64(&format!("speed_mod_v.{}", i as i32), *value);
+    }
+
+    // write active buffs
+    for (i, buff) in world.buffs.iter().enumerate() {
+        let id_key = format!("{}.{}", BUFF_ID_CHAR, i);
+        save_file.save_i32(&id_key, i as i32);
+
+        buff.save_file_write(format!("buff.{}", i), save_file)?;
+    }
+
+    save_file.save_u64("next_entity_id", world.next_entity_id);
+    Ok(())
+}
+
+/// Everything in KeyGroup::Other, apart from the slot and quarantine
+fn write_player(
+    inventory: &Inventory,
+    player_state: &PlayerState,
+    platform_api: &PlatformApi,
+    save_file: &mut SaveFile,
+) -> Result<(), Error> {
+    save_file.save_i32(SAVE_VERSION_KEY, SAVE_VERSION);
+
+    inventory.save_file_write("".into(), save_file)?;
+    player_state.save_file_write("".into(), save_file)?;
+
+    save_file.save_f64("unix_time_saved", (platform_api.epoch_time_ms)());
+    Ok(())
+}
+
+/// Summary of a save, shown before loading it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavePreview {
+    pub gold: i64,
+    pub tile_count: usize,
+    pub unix_time_saved: f64,
+}
+
+impl SavePreview {
+    /// The save file should already be migrated
+    pub fn from_save_file(save_file: &SaveFile) -> Result<Self, Error> {
+        let tile_prefix = format!("{}.", TILE_INSTANCE_ID_CHAR);
+
+        Ok(Self {
+            gold: save_file.load_i64(".g")?,
+            tile_count: save_file
+                .entries
+                .keys()
+                .filter(|key| key.starts_with(&tile_prefix))
+                .count(),
+            unix_time_saved: save_file.load_f64("unix_time_saved")?,
+        })
+    }
 
-            t.1.save_file_write(format!("{}", t.0.id), &mut save_file)?;
+    pub fn saved_ago(&self, time_now: f64) -> String {
+        let minutes = ((time_now - self.unix_time_saved) / (60.0 * 1000.0)).max(0.0) as i64;
+
+        if minutes < 60 {
+            format!("{} minutes ago", minutes)
+        } else if minutes < 48 * 60 {
+            format!("{} hours ago", minutes / 60)
+        } else {
+            format!("{} days ago", minutes / (24 * 60))
         }
+    }
+}
+
+/// What a load couldn't use
+#[derive(Debug, Clone)]
+pub struct LoadReport {
+    /// 0 is the newest save. Anything higher means the newer saves were broken.
+    pub save_used: usize,
 
-        // write valid adjacent positions
-        for (i, (key, value)) in world.valids.iter().enumerate() {
-            let id_key = format!("{}.{}", VALID_ADJ_ID_CHAR, i);
-            save_file.save_i32(&id_key, i as i32);
+    /// Saves that couldn't be loaded at all, newest first
+    pub broken_saves: Vec<String>,
 
-            save_file.save_i32(&format!("valid_x.{}", i as i32), key.x);
-            save_file.save_i32(&format!("valid_y.{}", i as i32), key.y);
+    /// Parts of the loaded save that failed. They're kept in the quarantine.
+    pub lost: Vec<String>,
+
+    /// Saved world state that didn't match the state rebuilt from the tiles. The rebuilt state is used.
+    pub repaired: Vec<String>,
+}
+
+impl LoadReport {
+    pub fn new() -> Self {
+        Self {
+            save_used: 0,
+            broken_saves: vec![],
+            lost: vec![],
+            repaired: vec![],
         }
+    }
 
-        // write global drop mods
-        for (i, (key, value)) in world.drop_count_mod.iter().enumerate() {
-            let id_key = format!("{}.{}", DROP_MOD_CHAR, i);
-            save_file.save_i32(&id_key, i as i32);
+    pub fn is_clean(&self) -> bool {
+        self.broken_saves.is_empty() && self.lost.is_empty()
+    }
+
+    pub fn display(&self) -> String {
+        let mut ret = String::new();
 
-            save_file.save_i32(&format!("drop_mod_x.{}", i as i32), key.x);
-            save_file.save_i32(&format!("drop_mod_y.{}", i as i32), key.y);
-            save_file.save_f64(&format!("drop_mod_v.{}", i as i32), *value);
+        if self.save_used > 0 {
+            ret.push_str(&format!("Loaded backup save {}\n", self.save_used));
+        }
+        for broken in &self.broken_saves {
+            ret.push_str(&format!("Broken save {}\n", broken));
+        }
+        for lost in &self.lost {
+            ret.push_str(&format!("Lost {}\n", lost));
+        }
+        for repaired in &self.repaired {
+            ret.push_str(&format!("Repaired {}\n", repaired));
         }
 
-        save_file.save_u64("next_entity_id", world.next_entity_id);
+        ret
     }
+}
 
-    inventory.save_file_write("".into(), &mut save_file)?;
+/// The last good saves, snapshots, and anything that failed to load.
+/// Backups and the quarantine are written out with every full save. Snapshots go in the slot, apart from the saves.
+#[derive(Debug)]
+pub struct SaveHistory {
+    /// Written save files, newest first
+    pub backups: Vec<Vec<u8>>,
 
-    save_file.save_f64("unix_time_saved", (platform_api.epoch_time_ms)());
+    /// Older copies the player can restore, newest first
+    pub snapshots: Vec<Snapshot>,
 
-    Ok(save_file)
+    /// Entries that failed to load. Kept so they can be recovered by hand.
+    pub quarantine: SaveFile,
+
+    pub last_load: Option<LoadReport>,
+
+    /// Changes since the newest backup. None until the first full save.
+    pub delta: Option<DeltaSave>,
 }
 
-pub fn save_game(
-    world: &World,
+impl SaveHistory {
+    pub fn new() -> Self {
+        Self {
+            backups: vec![],
+            snapshots: vec![],
+            quarantine: SaveFile::new(),
+            last_load: None,
+            delta: None,
+        }
+    }
+
+    /// Data to write out for a full save. The new save with the quarantine, followed by the backups.
+    /// Then the new save becomes the newest backup and the base for deltas, and a snapshot if one is due.
+    pub fn write(&mut self, mut save_file: SaveFile) -> Result<Vec<u8>, Error> {
+        if !self.quarantine.entries.is_empty() {
+            save_file.save_section(QUARANTINE_KEY, self.quarantine.clone());
+        }
+
+        let time_saved = save_file.load_f64("unix_time_saved")?;
+        if snapshot_due(&self.snapshots, time_saved) {
+            self.snapshots.insert(0, Snapshot::take(&save_file)?);
+            prune_snapshots(&mut self.snapshots, time_saved);
+        }
+
+        let mut save: Vec<u8> = vec![];
+        save_file.write_file(&mut save)?;
+
+        let mut ret = save.clone();
+        ret.append(&mut self.older_saves());
+
+        self.backups.insert(0, save);
+        self.backups.truncate(SAVE_BACKUPS);
+        self.delta = Some(DeltaSave::new(save_file)?);
+
+        Ok(ret)
+    }
+
+    /// The delta save to write out, with the changes since the newest backup. None when a full save is due instead.
+    fn write_changes(
+        &mut self,
+        world: &World,
+        inventory: &Inventory,
+        player_state: &PlayerState,
+        slot_name: &str,
+        platform_api: &PlatformApi,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let time_now = (platform_api.epoch_time_ms)();
+        if snapshot_due(&self.snapshots, time_now) {
+            return Ok(None);
+        }
+        let Some(delta) = &mut self.delta else {
+            return Ok(None);
+        };
+        if delta.compact_due(time_now) {
+            return Ok(None);
+        }
+
+        // Bounded by the item types, so always written. Only what changed goes in the delta.
+        let mut other = SaveFile::new();
+        write_player(inventory, player_state, platform_api, &mut other)?;
+        other.save_str(SAVE_SLOT_KEY, slot_name);
+        if !self.quarantine.entries.is_empty() {
+            other.save_section(QUARANTINE_KEY, self.quarantine.clone());
+        }
+        let time_saved = other.load_f64("unix_time_saved")?;
+
+        for eid in &world.changed_entities {
+            let mut entries = SaveFile::new();
+            if let Some(tile) = world.entities.get(eid) {
+                write_entity(*eid, tile, &mut entries)?;
+            }
+            delta.replace_group(KeyGroup::Entity(eid.id), entries, time_saved);
+        }
+
+        if world.globals_changed {
+            let mut entries = SaveFile::new();
+            write_world_globals(world, &mut entries)?;
+            delta.replace_group(KeyGroup::World, entries, time_saved);
+        }
+
+        delta.replace_group(KeyGroup::Other, other, time_saved);
+
+        Ok(Some(delta.write(slot_name)?))
+    }
+
+    /// Backups, to write after a new save
+    pub fn older_saves(&self) -> Vec<u8> {
+        let mut ret: Vec<u8> = vec![];
+        for backup in &self.backups {
+            ret.extend_from_slice(backup);
+        }
+        ret
+    }
+
+    /// Every snapshot, one after another
+    pub fn snapshot_data(&self) -> Vec<u8> {
+        let mut ret: Vec<u8> = vec![];
+        for snapshot in &self.snapshots {
+            ret.extend_from_slice(&snapshot.data);
+        }
+        ret
+    }
+}
+
+/// Save the game into the active slot without writing it out.
+/// Only the world changes since the last full save are written, until a full save is due.
+pub fn store_game(
+    world: &mut World,
     inventory: &Inventory,
+    player_state: &PlayerState,
+    history: &mut SaveHistory,
+    slots: &mut SaveSlots,
     platform_api: &PlatformApi,
 ) -> Result<(), Error> {
-    let save_file = build_save_file(world, inventory, platform_api)?;
+    let slot_name = slots.active().name.clone();
+
+    let changes =
+        history.write_changes(world, inventory, player_state, &slot_name, platform_api)?;
+    match changes {
+        Some(delta) => slots.set_active_delta(delta),
+        None => {
+            let mut save_file = build_save_file(world, inventory, player_state, platform_api)?;
+            save_file.save_str(SAVE_SLOT_KEY, &slot_name);
+            slots.set_active_data(history.write(save_file)?);
+            slots.set_snapshots(slots.active, history.snapshot_data());
+        }
+    }
+    world.clear_changes();
 
-    let mut write_data: Vec<u8> = vec![];
-    let mut write_cursor = Cursor::new(write_data);
-    save_file.write_file(&mut write_cursor).unwrap();
+    Ok(())
+}
+
+/// Save the game into the active slot and write out every slot through the platform's save data.
+/// The header of the data names the active slot. Only the slot names go in local storage.
+pub fn save_game(
+    world: &mut World,
+    inventory: &Inventory,
+    player_state: &PlayerState,
+    history: &mut SaveHistory,
+    slots: &mut SaveSlots,
+    platform_api: &PlatformApi,
+) -> Result<(), Error> {
+    store_game(world, inventory, player_state, history, slots, platform_api)?;
 
-    (platform_api.write_save_game_data)(write_cursor.get_ref().to_vec())
+    (platform_api.write_save_game_data)(slots.write()?)
         .map_err(|e| Error::EngineError(format!("{:?}", e)))?;
+    slots.store_names(platform_api);
     Ok(())
 }
 
-/// returns the ms that is needed to forward simulate
+/// Loads the newest save that can be read, with its delta applied, falling back to the backups.
+/// Snapshots in the data go into the history.
+/// Returns the ms that is needed to forward simulate. What was lost is in history.last_load.
 pub fn load_game(
     world: &mut World,
     inventory: &mut Inventory,
+    player_state: &mut PlayerState,
     data: &Vec<u8>,
     account_system: &AccountSystem,
+    history: &mut SaveHistory,
     platform_api: &PlatformApi,
 ) -> Result<f64, Error> {
+    // Snapshots are kept for restoring, not loaded
+    let mut saves: Vec<&[u8]> = vec![];
+    history.snapshots.clear();
+    for save in split_saves(data) {
+        match Snapshot::read(save) {
+            Ok(snapshot) => history.snapshots.push(snapshot),
+            Err(_) => saves.push(save),
+        }
+    }
+
+    let mut report = LoadReport::new();
+
+    for i in 0..saves.len() {
+        let loaded = read_save(&saves, i).and_then(|save_file| {
+            load_save(
+                world,
+                inventory,
+                player_state,
+                save_file,
+                account_system,
+                &mut report,
+                platform_api,
+            )
+        });
+
+        match loaded {
+            Ok((ms_to_sim, quarantine)) => {
+                report.save_used = i;
+
+                history.backups = saves[i..]
+                    .iter()
+                    .filter(|save| SaveFile::read_bytes(save).is_ok_and(|save| !is_delta(&save)))
+                    .take(SAVE_BACKUPS)
+                    .map(|save| save.to_vec())
+                    .collect();
+                history.quarantine = quarantine;
+                history.last_load = Some(report);
+
+                // Loading can drop or fix entries, so deltas start from a new full save
+                history.delta = None;
+
+                return Ok(ms_to_sim);
+            }
+            Err(error) => {
+                report.broken_saves.push(format!("{}. {:?}", i, error));
+                report.lost.clear();
+            }
+        }
+    }
+
     world.clear();
     inventory.clear();
+    player_state.clear();
+
+    Err(Error::NoValidSave)
+}
 
-    let mut cursor = Cursor::new(data);
-    let save_file = SaveFile::read_file(&mut cursor).unwrap();
+/// Copy matching entries into the quarantine
+fn quarantine_where(save_file: &SaveFile, quarantine: &mut SaveFile, keep: impl Fn(&str) -> bool) {
+    for (key, value) in &save_file.entries {
+        if key != QUARANTINE_KEY && keep(key) {
+            quarantine.entries.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// Load one save. Only errors if the save can't be read at all.
+/// Parts that fail are added to the report and returned in the quarantine.
+fn load_save(
+    world: &mut World,
+    inventory: &mut Inventory,
+    player_state: &mut PlayerState,
+    mut save_file: SaveFile,
+    account_system: &AccountSystem,
+    report: &mut LoadReport,
+    platform_api: &PlatformApi,
+) -> Result<(f64, SaveFile), Error> {
+    world.clear();
+    inventory.clear();
+    player_state.clear();
+
+    migrate(&mut save_file)?;
+
+    let mut quarantine = save_file
+        .load_section(QUARANTINE_KEY)
+        .cloned()
+        .unwrap_or(SaveFile::new());
 
     // world stuff
     {
-        world.next_entity_id = save_file.load_u64("next_entity_id")?;
-
-        for (key, value) in &save_file.entries {
+        for key in save_file.entries.keys() {
             // check if is tile
             let parts: Vec<&str> = key.split('.').collect();
+            let index = parts.get(1).copied().unwrap_or("");
 
             // tile instance
             if parts[0].starts_with(TILE_INSTANCE_ID_CHAR) {
-                let eid = EntityID {
-                    id: save_file.load_u64(key)?,
-                };
+                let loaded = save_file.load_u64(key).and_then(|id| {
+                    let tile_instance =
+                        TileInstance::save_file_load(format!("{}", id), &save_file)?;
+                    Ok((EntityID { id }, tile_instance))
+                });
 
-                let tile_instance =
-                    TileInstance::save_file_load(format!("{}", eid.id), &save_file)?;
+                match loaded {
+                    Ok((eid, tile_instance)) => world.raw_insert_entity(eid, tile_instance),
+                    Err(error) => {
+                        report.lost.push(format!("tile {}. {:?}", index, error));
 
-                world.raw_insert_entity(eid, tile_instance);
+                        let tile_prefix = format!("{}.", index);
+                        quarantine_where(&save_file, &mut quarantine, |k| {
+                            k == key || k.starts_with(&tile_prefix)
+                        });
+                    }
+                }
             } else if parts[0].starts_with(VALID_ADJ_ID_CHAR) {
-                let i = save_file.load_i32(key)?;
+                let loaded = save_file.load_i32(key).and_then(|i| {
+                    let key_x = &format!("valid_x.{}", i as i32);
+                    let key_y = &format!("valid_y.{}", i as i32);
 
-                let key_x = &format!("valid_x.{}", i as i32);
-                let key_y = &format!("valid_y.{}", i as i32);
+                    Ok(GridPos::new(
+                        save_file.load_i32(&key_x)?,
+                        save_file.load_i32(&key_y)?,
+                    ))
+                });
 
-                let grid_pos =
-                    GridPos::new(save_file.load_i32(&key_x)?, save_file.load_i32(&key_y)?);
+                match loaded {
+                    Ok(grid_pos) => {
+                        world.valids.insert(grid_pos, true);
+                    }
+                    Err(error) => {
+                        report
+                            .lost
+                            .push(format!("valid position {}. {:?}", index, error));
 
-                world.valids.insert(grid_pos, true);
+                        let keys = [
+                            key.clone(),
+                            format!("valid_x.{}", index),
+                            format!("valid_y.{}", index),
+                        ];
+                        quarantine_where(&save_file, &mut quarantine, |k| {
+                            keys.iter().any(|q| q == k)
+                        });
+                    }
+                }
             } else if parts[0].starts_with(DROP_MOD_CHAR) {
-                let i = save_file.load_i32(key)?;
+                let loaded = save_file.load_i32(key).and_then(|i| {
+                    let key_x = &format!("drop_mod_x.{}", i as i32);
+                    let key_y = &format!("drop_mod_y.{}", i as i32);
+                    let key_v = &format!("drop_mod_v.{}", i as i32);
+
+                    let gp = GridPos::new(save_file.load_i32(&key_x)?, save_file.load_i32(&key_y)?);
+                    let val: f64 = save_file.load_f64(key_v)?;
+                    Ok((gp, val))
+                });
+
+                match loaded {
+                    Ok((gp, val)) => {
+                        world.drop_count_mod.insert(gp, val);
+                    }
+                    Err(error) => {
+                        report.lost.push(format!("drop mod {}. {:?}", index, error));
+
+                        let keys = [
+                            key.clone(),
+                            format!("drop_mod_x.{}", index),
+                            format!("drop_mod_y.{}", index),
+                            format!("drop_mod_v.{}", index),
+                        ];
+                        quarantine_where(&save_file, &mut quarantine, |k| {
+                            keys.iter().any(|q| q == k)
+                        });
+                    }
+                }
+            } else if parts[0].starts_with(HARVEST_SPEED_MOD_CHAR) {
+                let loaded = save_file.load_i32(key).and_then(|i| {
+                    let key_x = &format!("speed_mod_x.{}", i as i32);
+                    let key_y = &format!("speed_mod_y.{}", i as i32);
+                    let key_v = &format!("speed_mod_v.{}", i as i32);
+
+                    let gp = GridPos::new(save_file.load_i32(&key_x)?, save_file.load_i32(&key_y)?);
+                    let val: f64 = save_file.load_f64(key_v)?;
+                    Ok((gp, val))
+                });
 
-                let key_x = &format!("drop_mod_x.{}", i as i32);
-                let key_y = &format!("drop_mod_y.{}", i as i32);
-                let key_v = &format!("drop_mod_v.{}", i as i32);
+                match loaded {
+                    Ok((gp, val)) => {
+                        world.harvest_speed_mod.insert(gp, val);
+                    }
+                    Err(error) => {
+                        report
+                            .lost
+                            .push(format!("speed mod {}. {:?}", index, error));
 
-                let gp = GridPos::new(save_file.load_i32(&key_x)?, save_file.load_i32(&key_y)?);
-                let val: f64 = save_file.load_f64(key_v)?;
+                        let keys = [
+                            key.clone(),
+                            format!("speed_mod_x.{}", index),
+                            format!("speed_mod_y.{}", index),
+                            format!("speed_mod_v.{}", index),
+                        ];
+                        quarantine_where(&save_file, &mut quarantine, |k| {
+                            keys.iter().any(|q| q == k)
+                        });
+                    }
+                }
+            } else if parts[0].starts_with(BUFF_ID_CHAR) {
+                // The buff mods are rebuilt with the rest once every tile is in
+                let loaded = save_file
+                    .load_i32(key)
+                    .and_then(|i| WorldBuff::save_file_load(format!("buff.{}", i), &save_file));
+
+                match loaded {
+                    Ok(buff) => world.buffs.push(buff),
+                    Err(error) => {
+                        report.lost.push(format!("buff {}. {:?}", index, error));
+
+                        let buff_prefix = format!("buff.{}.", index);
+                        quarantine_where(&save_file, &mut quarantine, |k| {
+                            k == key || k.starts_with(&buff_prefix)
+                        });
+                    }
+                }
+            }
+        }
+
+        // Footprints, valids, global mods and world conditions all come from the tiles.
+        // The saved valids and mods are only checked against them.
+        report.repaired = world.rebuild_derived();
+
+        // A delta only rewrites timers when more than time changed, so older ones catch up to the save
+        world.update_buffs(stale_seconds(&save_file, KeyGroup::World));
+        let world_snapshot = world.get_world_snapshot();
+        for (eid, tile) in &mut world.entities {
+            let behind = stale_seconds(&save_file, KeyGroup::Entity(eid.id));
+            if behind > 0.0 {
+                tile.catch_up(behind, &world_snapshot);
+            }
+        }
 
-                world.drop_count_mod.insert(gp, val);
+        // Never hand out an id that a loaded tile already has
+        let next_free_id = world
+            .entities
+            .keys()
+            .map(|eid| eid.id + 1)
+            .max()
+            .unwrap_or(0);
+        world.next_entity_id = match save_file.load_u64("next_entity_id") {
+            Ok(id) => id.max(next_free_id),
+            Err(error) => {
+                report.lost.push(format!("next entity id. {:?}", error));
+                next_free_id
             }
+        };
+    }
+
+    match Inventory::save_file_load("".into(), &save_file) {
+        Ok(inv) => {
+            inventory.items_seen = inv.items_seen;
+            inventory.items = inv.items;
+            inventory.gold = inv.gold;
+            inventory.limit = inv.limit;
+            inventory.protected = inv.protected;
+        }
+        Err(error) => {
+            report.lost.push(format!("inventory. {:?}", error));
+
+            quarantine_where(&save_file, &mut quarantine, |k| {
+                k == ".g"
+                    || k == ".l"
+                    || k.starts_with("item")
+                    || k.ends_with(".item_count")
+                    || k.starts_with("prot")
+            });
+        }
+    }
+
+    match PlayerState::save_file_load("".into(), &save_file) {
+        Ok(loaded) => *player_state = loaded,
+        Err(error) => {
+            report.lost.push(format!("player progress. {:?}", error));
+
+            let prefixes = [".q.", ".a.", "col", "st.", "mk", "tr", "pity."];
+            quarantine_where(&save_file, &mut quarantine, |k| {
+                prefixes.iter().any(|prefix| k.starts_with(prefix))
+            });
         }
     }
+    if player_state.trades.save_id == 0 {
+        player_state.trades.save_id = TradeLog::new_save_id(platform_api);
+    }
+    if player_state.trades.secret == 0 {
+        player_state.trades.secret = TradeLog::new_secret(platform_api);
+    }
+    if player_state.market.seed == 0 {
+        player_state.market.seed = Market::new_seed(platform_api);
+    }
 
-    let inv = Inventory::save_file_load("".into(), &save_file)?;
-    inventory.items_seen = inv.items_seen;
-    inventory.items = inv.items;
-    inventory.gold = inv.gold;
-    inventory.limit = inv.limit;
+    // Anything ever collected has been seen, even if it isn't in the inventory anymore
+    for item_type in player_state.collection.entries.keys() {
+        inventory.items_seen.insert(*item_type, true);
+    }
 
     let time_now = (platform_api.epoch_time_ms)();
-    let time_saved = save_file.load_f64("unix_time_saved")?;
+    let time_saved = match save_file.load_f64("unix_time_saved") {
+        Ok(time_saved) => time_saved,
+        Err(error) => {
+            // Without the time there's nothing to simulate
+            report.lost.push(format!("offline time. {:?}", error));
+            time_now
+        }
+    };
 
     let sim_limit_hour = if account_system.user_purchased_base() {
         SIM_LIMIT_H_PREMIUM
@@ -152,5 +712,474 @@ pub fn load_game(
     };
     let sim_limit_ms = sim_limit_hour as f64 * 60.0 * 60.0 * 1000.0;
 
-    return Ok((time_now - time_saved).clamp(0.0, sim_limit_ms));
+    return Ok(((time_now - time_saved).clamp(0.0, sim_limit_ms), quarantine));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{item::*, setup_initial, testing_infra::*, tile::*};
+
+    const TEST_SAVES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/test_saves");
+
+    fn load(data: &Vec<u8>) -> Result<(World, Inventory, PlayerState), Error> {
+        let (world, inventory, player_state, _) = load_with_history(data)?;
+        Ok((world, inventory, player_state))
+    }
+
+    fn load_with_history(
+        data: &Vec<u8>,
+    ) -> Result<(World, Inventory, PlayerState, SaveHistory), Error> {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        let mut player_state = PlayerState::new();
+        let mut history = SaveHistory::new();
+
+        load_game(
+            &mut world,
+            &mut inventory,
+            &mut player_state,
+            data,
+            &AccountSystem::new(),
+            &mut history,
+            &windows_plaform_api(),
+        )?;
+
+        Ok((world, inventory, player_state, history))
+    }
+
+    fn build(world: &World) -> SaveFile {
+        build_save_file(
+            world,
+            &Inventory::new(),
+            &PlayerState::new(),
+            &windows_plaform_api(),
+        )
+        .unwrap()
+    }
+
+    fn write(world: &World, inventory: &Inventory, player_state: &PlayerState) -> Vec<u8> {
+        let save_file =
+            build_save_file(world, inventory, player_state, &windows_plaform_api()).unwrap();
+
+        let mut cursor = Cursor::new(vec![]);
+        save_file.write_file(&mut cursor).unwrap();
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+
+        let data = write(&world, &inventory, &PlayerState::new());
+        let (loaded_world, loaded_inventory, _) = load(&data).unwrap();
+
+        assert_eq!(loaded_world.entities.len(), world.entities.len());
+        assert!(loaded_inventory.has_atleast(ItemType::Tile(TileType::Grass), 4));
+    }
+
+    /// Every save in the corpus must keep loading after format changes. Never delete them.
+    #[test]
+    fn old_saves_load() {
+        let mut count = 0;
+
+        for entry in std::fs::read_dir(TEST_SAVES_DIR).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("save") {
+                continue;
+            }
+
+            let data = std::fs::read(&path).unwrap();
+            let (world, _, _, history) = load_with_history(&data)
+                .unwrap_or_else(|error| panic!("{} failed to load. {:?}", path.display(), error));
+            assert!(
+                history.last_load.as_ref().unwrap().is_clean(),
+                "{} lost data. {}",
+                path.display(),
+                history.last_load.as_ref().unwrap().display()
+            );
+            assert!(
+                !world.entities.is_empty(),
+                "{} has no tiles",
+                path.display()
+            );
+
+            // saving the loaded game writes the current version
+            let mut cursor = Cursor::new(write(&world, &Inventory::new(), &PlayerState::new()));
+            let resaved = SaveFile::read_file(&mut cursor).unwrap();
+            assert_eq!(save_version(&resaved), SAVE_VERSION);
+
+            count += 1;
+        }
+
+        assert!(count > 0);
+    }
+
+    #[test]
+    fn old_save_progress() {
+        let data = std::fs::read(format!("{}/v0_progress.save", TEST_SAVES_DIR)).unwrap();
+        let (world, inventory, player_state) = load(&data).unwrap();
+
+        assert_eq!(world.entities.len(), 7);
+        assert_eq!(world.next_entity_id, 7);
+        assert_eq!(inventory.gold, 350);
+        assert!(inventory.has_atleast(ItemType::Stick, 12));
+        assert!(inventory.has_atleast(ItemType::Tile(TileType::Grass), 2));
+
+        // Progress tracking started after this save, so it starts from nothing
+        assert_eq!(player_state.stats.gold_earned, 0);
+        assert!(player_state.trades.pending.is_empty());
+
+        // Saves from before the market get their own event calendar
+        assert_ne!(player_state.market.seed, 0);
+    }
+
+    #[test]
+    fn broken_tile_quarantined() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+        assert_eq!(world.entities.len(), 2);
+
+        // Grass is entity 1. Give it a tile type that doesn't exist.
+        let mut save_file = build(&world);
+        save_file.entries.remove("1.type");
+        save_file.save_i32("1.type", 999);
+
+        let mut history = SaveHistory::new();
+        let data = history.write(save_file).unwrap();
+
+        let (loaded_world, _, _, mut history) = load_with_history(&data).unwrap();
+        let report = history.last_load.clone().unwrap();
+        assert_eq!(loaded_world.entities.len(), 1);
+        assert_eq!(loaded_world.next_entity_id, 2);
+        assert_eq!(report.save_used, 0);
+        assert_eq!(report.lost.len(), 1);
+        assert_eq!(history.quarantine.load_i32("1.type").unwrap(), 999);
+        assert!(history.quarantine.entries.contains_key("E.1"));
+
+        // The quarantine is written back out with the next save, and loads again
+        let data = history.write(build(&loaded_world)).unwrap();
+        let (_, _, _, history) = load_with_history(&data).unwrap();
+        assert_eq!(history.quarantine.load_i32("1.type").unwrap(), 999);
+    }
+
+    fn derived_world() -> World {
+        let mut world = World::new();
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0), (2, 1)] {
+            let _ = world.insert_tile_type(GridPos::new(x, y), TileType::Dirt);
+        }
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::OakTree);
+        let _ = world.insert_tile_type(GridPos::new(3, 0), TileType::Water);
+        let _ = world.insert_tile_type(GridPos::new(2, 0), TileType::Grass);
+        let _ = world.insert_tile_type(GridPos::new(2, 1), TileType::Spring);
+        world
+    }
+
+    fn harvest_length(world: &World, pos: GridPos) -> f64 {
+        let eid = world.get_entities(pos).layers[&WorldLayer::Floor];
+        let harvest = world.get_entity(&eid).comp_harvest.as_ref().unwrap();
+        harvest.length()
+    }
+
+    #[test]
+    fn derived_state_rebuilt() {
+        let world = derived_world();
+
+        let data = write(&world, &Inventory::new(), &PlayerState::new());
+        let (loaded_world, _, _, history) = load_with_history(&data).unwrap();
+        assert!(history.last_load.unwrap().repaired.is_empty());
+
+        // Every cell of the tree points at it, not just the origin
+        let tree = world.get_entities(GridPos::new(0, 0)).layers[&WorldLayer::Floor];
+        for pos in GridPos::new(0, 0).to_rect_iter(2, 2) {
+            assert_eq!(
+                loaded_world.get_entities(pos).layers[&WorldLayer::Floor],
+                tree
+            );
+        }
+
+        // The grass is next to water
+        let grass_pos = GridPos::new(2, 0);
+        let dry_grass = TileType::Grass.create_instance(grass_pos);
+        assert!(
+            harvest_length(&loaded_world, grass_pos)
+                < dry_grass.comp_harvest.as_ref().unwrap().length()
+        );
+        assert_eq!(
+            harvest_length(&loaded_world, grass_pos),
+            harvest_length(&world, grass_pos)
+        );
+
+        assert_eq!(loaded_world.valids, world.valids);
+        assert_eq!(loaded_world.drop_count_mod, world.drop_count_mod);
+    }
+
+    #[test]
+    fn derived_state_mismatch_reported() {
+        let world = derived_world();
+
+        let mut save_file = build(&world);
+        save_file.entries.remove("drop_mod_v.0");
+        save_file.save_f64("drop_mod_v.0", 5.0);
+
+        let mut history = SaveHistory::new();
+        let data = history.write(save_file).unwrap();
+
+        let (loaded_world, _, _, history) = load_with_history(&data).unwrap();
+        let report = history.last_load.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.repaired, vec!["1 drop mods wrong".to_string()]);
+        assert_eq!(loaded_world.drop_count_mod, world.drop_count_mod);
+    }
+
+    #[test]
+    fn falls_back_to_backup() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+
+        let mut history = SaveHistory::new();
+        history.write(build(&world)).unwrap();
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+        history.write(build(&world)).unwrap();
+        let _ = world.insert_tile_type(GridPos::new(1, 0), TileType::Dirt);
+        let mut data = history.write(build(&world)).unwrap();
+
+        // Only the last two are kept, and the first save was snapshotted apart from them
+        assert_eq!(history.backups.len(), SAVE_BACKUPS);
+        assert_eq!(history.snapshots.len(), 1);
+        assert_eq!(split_saves(&data).len(), SAVE_BACKUPS + 1);
+
+        let (loaded_world, _, _, history) = load_with_history(&data).unwrap();
+        assert_eq!(loaded_world.entities.len(), 3);
+        assert!(history.last_load.unwrap().is_clean());
+
+        // Corrupt the newest save
+        data[20] ^= 0xFF;
+        let (loaded_world, _, _, history) = load_with_history(&data).unwrap();
+        let report = history.last_load.unwrap();
+        assert_eq!(loaded_world.entities.len(), 2);
+        assert_eq!(report.save_used, 1);
+        assert_eq!(report.broken_saves.len(), 1);
+        assert_eq!(history.backups.len(), SAVE_BACKUPS);
+
+        // Nothing valid
+        assert!(matches!(
+            load(&vec![0, b'P', b'W', b'S', 3, 1]),
+            Err(Error::NoValidSave)
+        ));
+    }
+
+    #[test]
+    fn delta_saves() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+        let eid = world.get_entities(GridPos::new(0, 0)).layers[&WorldLayer::Floor];
+        let start_gold = inventory.gold;
+
+        let player_state = PlayerState::new();
+        let platform_api = windows_plaform_api();
+        let mut history = SaveHistory::new();
+        let mut slots = SaveSlots::new();
+        let mut store = |world: &mut World, inventory: &Inventory| {
+            store_game(
+                world,
+                inventory,
+                &player_state,
+                &mut history,
+                &mut slots,
+                &platform_api,
+            )
+            .unwrap();
+            slots.active().clone()
+        };
+
+        let full = store(&mut world, &inventory);
+        assert!(full.delta.is_empty());
+        assert_eq!(split_saves(&full.snapshots).len(), 1);
+        assert!(world.changed_entities.is_empty());
+        assert!(!world.globals_changed);
+
+        // A timer counting on isn't a change, but one coming due is
+        let _ = world.sim_update(1.0, &platform_api);
+        assert!(world.changed_entities.is_empty());
+        let _ = world.sim_update(100.0, &platform_api);
+        assert!(world.changed_entities.contains(&eid));
+
+        // Only the harvest timer and gold change, and the full saves stay as they were
+        inventory.gold = 99;
+        let slot = store(&mut world, &inventory);
+        assert_eq!(slot.data, full.data);
+        assert!(is_delta(&SaveFile::read_bytes(&slot.delta).unwrap()));
+        assert!(slot.delta.len() < slot.data.len() / 2);
+
+        let data = slot.saves();
+        let (loaded_world, loaded_inventory, _, history) = load_with_history(&data).unwrap();
+        assert_eq!(loaded_inventory.gold, 99);
+        assert!(loaded_world.get_entity(&eid).can_harvest());
+        assert_eq!(history.last_load.unwrap().save_used, 0);
+        assert_eq!(history.backups.len(), 1);
+        assert!(history.delta.is_none());
+
+        // A broken delta falls back to the full save
+        let mut broken = data.clone();
+        broken[20] ^= 0xFF;
+        let (_, loaded_inventory, _, history) = load_with_history(&broken).unwrap();
+        assert_eq!(loaded_inventory.gold, start_gold);
+        assert_eq!(history.last_load.unwrap().save_used, 1);
+    }
+
+    #[test]
+    fn save_game_writes_every_slot() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+
+        let platform_api = windows_plaform_api();
+        let mut history = SaveHistory::new();
+        let mut slots = SaveSlots::new();
+        let sandbox = slots.create("Sandbox").unwrap();
+        slots.active = sandbox;
+        save_game(
+            &mut world,
+            &inventory,
+            &PlayerState::new(),
+            &mut history,
+            &mut slots,
+            &platform_api,
+        )
+        .unwrap();
+
+        // The platform gets the active slot's name in the data, and local storage only the names
+        let saved = SaveSlots::read(&SAVE_GAME_DATA.with(|data| data.borrow().clone()));
+        assert_eq!(saved.active().name, "Sandbox");
+        assert_eq!(saved.active().saves(), slots.active().saves());
+        assert_eq!(
+            (platform_api.local_persist_get)(SAVE_SLOTS_KEY).unwrap(),
+            "Sandbox\nMain"
+        );
+
+        let (loaded_world, _, _, history) = load_with_history(&saved.active().saves()).unwrap();
+        assert_eq!(loaded_world.entities.len(), 1);
+        assert_eq!(history.snapshots.len(), 1);
+    }
+
+    #[test]
+    fn delta_timers_catch_up() {
+        let mut world = World::new();
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Dirt);
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+        let eid = world.get_entities(GridPos::new(0, 0)).layers[&WorldLayer::Floor];
+
+        // The base is saved 5 seconds before the delta, which doesn't rewrite the grass
+        let mut base = build(&world);
+        let time_saved = base.load_f64("unix_time_saved").unwrap();
+        base.entries.remove("unix_time_saved");
+        base.save_f64("unix_time_saved", time_saved - 5000.0);
+
+        let mut other = SaveFile::new();
+        for (key, value) in &build(&world).entries {
+            if KeyGroup::of_key(key) == KeyGroup::Other {
+                other.entries.insert(key.clone(), value.clone());
+            }
+        }
+        let mut delta = DeltaSave::new(base.clone()).unwrap();
+        delta.replace_group(KeyGroup::Other, other, time_saved);
+
+        let mut data = delta.write("Main").unwrap();
+        base.write_file(&mut data).unwrap();
+
+        let (loaded_world, _, _, _) = load_with_history(&data).unwrap();
+        let time = loaded_world
+            .get_entity(&eid)
+            .comp_harvest
+            .as_ref()
+            .unwrap()
+            .time;
+        assert!((5.0..6.0).contains(&time));
+
+        // A full save is never behind
+        let mut full: Vec<u8> = vec![];
+        base.write_file(&mut full).unwrap();
+        let (loaded_world, _, _, _) = load_with_history(&full).unwrap();
+        assert_eq!(
+            loaded_world
+                .get_entity(&eid)
+                .comp_harvest
+                .as_ref()
+                .unwrap()
+                .time,
+            0.0
+        );
+    }
+
+    #[test]
+    fn snapshots_kept_and_restored() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+
+        let mut history = SaveHistory::new();
+        history.write(build(&world)).unwrap();
+
+        // Snapshots aren't in the saves, the slot keeps them after
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+        let mut data = history.write(build(&world)).unwrap();
+        assert!(split_saves(&data)
+            .iter()
+            .all(|save| Snapshot::read(save).is_err()));
+        data.append(&mut history.snapshot_data());
+
+        // The snapshot is never loaded as a save, only carried along
+        let (loaded_world, _, _, history) = load_with_history(&data).unwrap();
+        assert_eq!(loaded_world.entities.len(), 2);
+        assert_eq!(history.snapshots.len(), 1);
+        assert_eq!(history.snapshots[0].preview.tile_count, 1);
+        assert!(history
+            .backups
+            .iter()
+            .all(|save| Snapshot::read(save).is_err()));
+
+        // Restoring gives a normal save, with no offline time to simulate
+        let time_now = (windows_plaform_api().epoch_time_ms)();
+        let restored = history.snapshots[0].restore(time_now).unwrap();
+        let (restored_world, _, _, _) = load_with_history(&restored).unwrap();
+        assert_eq!(restored_world.entities.len(), 1);
+
+        let loaded = SaveFile::read_bytes(&restored).unwrap();
+        assert_eq!(loaded.load_f64("unix_time_saved").unwrap(), time_now);
+    }
+
+    #[test]
+    fn saved_ago() {
+        let preview = SavePreview {
+            gold: 0,
+            tile_count: 0,
+            unix_time_saved: 0.0,
+        };
+        let minute = 60.0 * 1000.0;
+
+        assert_eq!(preview.saved_ago(5.0 * minute), "5 minutes ago");
+        assert_eq!(preview.saved_ago(3.0 * 60.0 * minute), "3 hours ago");
+        assert_eq!(preview.saved_ago(72.0 * 60.0 * minute), "3 days ago");
+    }
+
+    /// Adds the current format to the corpus. Run this before bumping SAVE_VERSION.
+    /// cargo test -p pack_world_game write_corpus_save -- --ignored
+    #[test]
+    #[ignore]
+    fn write_corpus_save() {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+
+        let path = format!("{}/v{}_starter.save", TEST_SAVES_DIR, SAVE_VERSION);
+        std::fs::write(path, write(&world, &inventory, &PlayerState::new())).unwrap();
+    }
 }
@@ -0,0 +1,369 @@
+use crate::{error::*, save_file::*};
+use std::collections::{HashMap, HashSet};
+
+/// Section of a delta save holding every entry changed since its base
+pub const DELTA_KEY: &str = "delta";
+
+/// Section of a delta save listing entries removed since its base
+pub const DELTA_REMOVED_KEY: &str = "delta_removed";
+
+/// Save time of the full save a delta applies to. Only delta saves have it.
+pub const DELTA_BASE_KEY: &str = "delta_base";
+
+/// Section of a delta save with when each tile and the world were last written. Others were written with the base.
+/// Timers that only counted on since then aren't rewritten, so loading catches them up.
+pub const DELTA_TIMES_KEY: &str = "delta_times";
+
+/// Longest time between full saves
+pub const COMPACT_INTERVAL_MS: f64 = 10.0 * 60.0 * 1000.0;
+
+/// A full save is written once the delta holds more than 1 / COMPACT_FRACTION of the base entries
+const COMPACT_FRACTION: usize = 4;
+
+/// Entries that are always written together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyGroup {
+    /// One tile. Its id entry and every key under its id.
+    Entity(u64),
+
+    /// Valid positions, global mods, buffs and the next entity id. Their keys are positional.
+    World,
+
+    /// Inventory, player progress and everything else
+    Other,
+}
+
+impl KeyGroup {
+    /// Key in the delta times section
+    fn time_key(&self) -> String {
+        match self {
+            KeyGroup::Entity(id) => format!("{}.{}", TILE_INSTANCE_ID_CHAR, id),
+            KeyGroup::World => "world".into(),
+            KeyGroup::Other => "other".into(),
+        }
+    }
+
+    pub fn of_key(key: &str) -> Self {
+        let first = key.split('.').next().unwrap_or("");
+
+        // Inventory counts are the only other keys that start with a number
+        if key.ends_with(".item_count") {
+            return KeyGroup::Other;
+        }
+        if let Ok(id) = first.parse::<u64>() {
+            return KeyGroup::Entity(id);
+        }
+        if first.starts_with(TILE_INSTANCE_ID_CHAR) {
+            if let Some(Ok(id)) = key.split('.').nth(1).map(|id| id.parse::<u64>()) {
+                return KeyGroup::Entity(id);
+            }
+        }
+
+        let world_chars = [
+            VALID_ADJ_ID_CHAR,
+            DROP_MOD_CHAR,
+            HARVEST_SPEED_MOD_CHAR,
+            BUFF_ID_CHAR,
+        ];
+        let world_prefixes = [
+            "valid_",
+            "drop_mod_",
+            "speed_mod_",
+            "buff",
+            "next_entity_id",
+        ];
+        if world_chars.iter().any(|c| first.starts_with(*c))
+            || world_prefixes.iter().any(|prefix| key.starts_with(prefix))
+        {
+            return KeyGroup::World;
+        }
+
+        KeyGroup::Other
+    }
+}
+
+/// The last full save, and every change made since it
+#[derive(Debug)]
+pub struct DeltaSave {
+    base_time: f64,
+    base_len: usize,
+
+    /// The base with every change applied. What a full save would write.
+    current: SaveFile,
+
+    /// Keys written since the base
+    changed: HashSet<String>,
+
+    /// Keys dropped since the base
+    removed: HashSet<String>,
+
+    /// Keys of current, by group
+    groups: HashMap<KeyGroup, Vec<String>>,
+
+    /// When each group was last replaced
+    written: HashMap<KeyGroup, f64>,
+}
+
+impl DeltaSave {
+    /// base is the full save as written
+    pub fn new(base: SaveFile) -> Result<Self, Error> {
+        let mut groups: HashMap<KeyGroup, Vec<String>> = HashMap::new();
+        for key in base.entries.keys() {
+            groups
+                .entry(KeyGroup::of_key(key))
+                .or_default()
+                .push(key.clone());
+        }
+
+        Ok(Self {
+            base_time: base.load_f64("unix_time_saved")?,
+            base_len: base.entries.len(),
+            current: base,
+            changed: HashSet::new(),
+            removed: HashSet::new(),
+            groups,
+            written: HashMap::new(),
+        })
+    }
+
+    /// A full save is due when the delta gets too big or too old
+    pub fn compact_due(&self, time_now: f64) -> bool {
+        let delta_len = self.changed.len() + self.removed.len();
+
+        !(0.0..COMPACT_INTERVAL_MS).contains(&(time_now - self.base_time))
+            || delta_len * COMPACT_FRACTION > self.base_len
+    }
+
+    /// Replace every entry of a group, as it was at time. Only entries that differ are added to the delta.
+    pub fn replace_group(&mut self, group: KeyGroup, entries: SaveFile, time: f64) {
+        self.written.insert(group, time);
+
+        let old_keys = self.groups.remove(&group).unwrap_or_default();
+        for key in old_keys {
+            if !entries.entries.contains_key(&key) {
+                self.current.entries.remove(&key);
+                self.changed.remove(&key);
+                self.removed.insert(key);
+            }
+        }
+
+        let new_keys: Vec<String> = entries.entries.keys().cloned().collect();
+        for (key, value) in entries.entries {
+            if self.current.entries.get(&key) != Some(&value) {
+                self.removed.remove(&key);
+                self.changed.insert(key.clone());
+                self.current.entries.insert(key, value);
+            }
+        }
+
+        if !new_keys.is_empty() {
+            self.groups.insert(group, new_keys);
+        }
+    }
+
+    /// The delta as a save to write in front of the base
+    pub fn write(&self, slot_name: &str) -> Result<Vec<u8>, Error> {
+        let mut delta = SaveFile::new();
+        for key in &self.changed {
+            delta
+                .entries
+                .insert(key.clone(), self.current.entries[key].clone());
+        }
+
+        let mut removed = SaveFile::new();
+        for key in &self.removed {
+            removed.save_bool(key, true);
+        }
+
+        let mut times = SaveFile::new();
+        for (group, time) in &self.written {
+            times.save_f64(&group.time_key(), *time);
+        }
+
+        let mut save_file = SaveFile::new();
+        save_file.save_f64(DELTA_BASE_KEY, self.base_time);
+        save_file.save_str(SAVE_SLOT_KEY, slot_name);
+        save_file.save_section(DELTA_KEY, delta);
+        save_file.save_section(DELTA_REMOVED_KEY, removed);
+        save_file.save_section(DELTA_TIMES_KEY, times);
+
+        let mut ret: Vec<u8> = vec![];
+        save_file.write_file(&mut ret)?;
+        Ok(ret)
+    }
+}
+
+pub fn is_delta(save_file: &SaveFile) -> bool {
+    save_file.entries.contains_key(DELTA_BASE_KEY)
+}
+
+/// The base with the delta applied. Errors if the delta was written for another base.
+/// Keeps the delta times, with the base's save time for groups the delta didn't replace.
+pub fn apply_delta(delta: &SaveFile, base: &SaveFile) -> Result<SaveFile, Error> {
+    let base_time = base.load_f64("unix_time_saved")?;
+    if is_delta(base) || delta.load_f64(DELTA_BASE_KEY)? != base_time {
+        return Err(Error::DeltaBaseMismatch);
+    }
+
+    let mut ret = base.clone();
+    for key in delta.load_section(DELTA_REMOVED_KEY)?.entries.keys() {
+        ret.entries.remove(key);
+    }
+    for (key, value) in &delta.load_section(DELTA_KEY)?.entries {
+        ret.entries.insert(key.clone(), value.clone());
+    }
+
+    // Deltas from before the times rewrote every running timer
+    if let Ok(times) = delta.load_section(DELTA_TIMES_KEY) {
+        let mut times = times.clone();
+        times.save_f64(DELTA_BASE_KEY, base_time);
+        ret.save_section(DELTA_TIMES_KEY, times);
+    }
+
+    Ok(ret)
+}
+
+/// Seconds a group's timers are behind the save. Only tiles and buffs from a delta save can be behind.
+pub fn stale_seconds(save_file: &SaveFile, group: KeyGroup) -> f64 {
+    let Ok(times) = save_file.load_section(DELTA_TIMES_KEY) else {
+        return 0.0;
+    };
+    let Ok(time_saved) = save_file.load_f64("unix_time_saved") else {
+        return 0.0;
+    };
+
+    let written = times
+        .load_f64(&group.time_key())
+        .or_else(|_| times.load_f64(DELTA_BASE_KEY))
+        .unwrap_or(time_saved);
+    ((time_saved - written) / 1000.0).max(0.0)
+}
+
+/// Reads save i. A delta is applied to the save after it.
+pub fn read_save(saves: &[&[u8]], i: usize) -> Result<SaveFile, Error> {
+    let save_file = SaveFile::read_bytes(saves[i])?;
+    if !is_delta(&save_file) {
+        return Ok(save_file);
+    }
+
+    let Some(base) = saves.get(i + 1) else {
+        return Err(Error::DeltaBaseMismatch);
+    };
+    apply_delta(&save_file, &SaveFile::read_bytes(base)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn base() -> SaveFile {
+        let mut save_file = SaveFile::new();
+        save_file.save_i32(SAVE_VERSION_KEY, SAVE_VERSION);
+        save_file.save_f64("unix_time_saved", 1000.0);
+        save_file.save_i64(".g", 10);
+        for id in 0..10 {
+            save_file.save_u64(&format!("E.{}", id), id);
+            save_file.save_i32(&format!("{}.x", id), id as i32);
+        }
+        save_file
+    }
+
+    fn tile(id: u64, x: i32) -> SaveFile {
+        let mut entries = SaveFile::new();
+        entries.save_u64(&format!("E.{}", id), id);
+        entries.save_i32(&format!("{}.x", id), x);
+        entries
+    }
+
+    fn other(gold: i64, time: f64) -> SaveFile {
+        let mut entries = SaveFile::new();
+        entries.save_i32(SAVE_VERSION_KEY, SAVE_VERSION);
+        entries.save_f64("unix_time_saved", time);
+        entries.save_i64(".g", gold);
+        entries
+    }
+
+    #[test]
+    fn key_groups() {
+        assert_eq!(KeyGroup::of_key("E.12"), KeyGroup::Entity(12));
+        assert_eq!(KeyGroup::of_key("12.comp.ht.t"), KeyGroup::Entity(12));
+        assert_eq!(KeyGroup::of_key("3.item_count"), KeyGroup::Other);
+        assert_eq!(KeyGroup::of_key("V.4"), KeyGroup::World);
+        assert_eq!(KeyGroup::of_key("valid_x.4"), KeyGroup::World);
+        assert_eq!(KeyGroup::of_key("buff.0.kind"), KeyGroup::World);
+        assert_eq!(KeyGroup::of_key("next_entity_id"), KeyGroup::World);
+        assert_eq!(KeyGroup::of_key(".g"), KeyGroup::Other);
+        assert_eq!(KeyGroup::of_key("item_index.0"), KeyGroup::Other);
+        assert_eq!(KeyGroup::of_key(SAVE_SLOT_KEY), KeyGroup::Other);
+    }
+
+    #[test]
+    fn delta_round_trip() {
+        let mut delta = DeltaSave::new(base()).unwrap();
+
+        delta.replace_group(KeyGroup::Entity(3), tile(3, 30), 1500.0);
+        delta.replace_group(KeyGroup::Entity(4), SaveFile::new(), 1500.0);
+        delta.replace_group(KeyGroup::Entity(10), tile(10, 100), 2000.0);
+        delta.replace_group(KeyGroup::Other, other(10, 2000.0), 2000.0);
+
+        // Unchanged entries aren't written
+        assert_eq!(delta.changed.len(), 4);
+        assert_eq!(delta.removed.len(), 2);
+
+        let written = delta.write("Main").unwrap();
+        let written = SaveFile::read_bytes(&written).unwrap();
+        assert!(is_delta(&written));
+
+        // Tile 3 was written before the save, and the rest with the base
+        let mut merged = apply_delta(&written, &base()).unwrap();
+        assert_eq!(stale_seconds(&merged, KeyGroup::Entity(3)), 0.5);
+        assert_eq!(stale_seconds(&merged, KeyGroup::Entity(10)), 0.0);
+        assert_eq!(stale_seconds(&merged, KeyGroup::Entity(5)), 1.0);
+        assert_eq!(stale_seconds(&base(), KeyGroup::Entity(5)), 0.0);
+
+        merged.entries.remove(DELTA_TIMES_KEY);
+        assert_eq!(merged, delta.current);
+        assert_eq!(merged.load_i32("3.x").unwrap(), 30);
+        assert_eq!(merged.load_i32("10.x").unwrap(), 100);
+        assert!(!merged.entries.contains_key("E.4"));
+        assert_eq!(merged.load_f64("unix_time_saved").unwrap(), 2000.0);
+
+        // Putting a tile back takes it out of the removed list
+        delta.replace_group(KeyGroup::Entity(4), tile(4, 4), 2000.0);
+        assert_eq!(delta.removed.len(), 0);
+    }
+
+    #[test]
+    fn wrong_base() {
+        let delta = DeltaSave::new(base()).unwrap();
+        let written = SaveFile::read_bytes(&delta.write("Main").unwrap()).unwrap();
+
+        let mut other_base = base();
+        other_base.entries.remove("unix_time_saved");
+        other_base.save_f64("unix_time_saved", 5.0);
+        assert!(matches!(
+            apply_delta(&written, &other_base),
+            Err(Error::DeltaBaseMismatch)
+        ));
+
+        let data = delta.write("Main").unwrap();
+        assert!(matches!(
+            read_save(&[&data], 0),
+            Err(Error::DeltaBaseMismatch)
+        ));
+    }
+
+    #[test]
+    fn compact() {
+        let mut delta = DeltaSave::new(base()).unwrap();
+        assert!(!delta.compact_due(1000.0));
+        assert!(delta.compact_due(1000.0 + COMPACT_INTERVAL_MS));
+        assert!(delta.compact_due(0.0));
+
+        // 23 base entries, so 6 changes
+        for id in 0..6 {
+            delta.replace_group(KeyGroup::Entity(id), tile(id, -1), 1000.0);
+        }
+        assert!(delta.compact_due(1000.0));
+    }
+}
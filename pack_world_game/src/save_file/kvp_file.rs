@@ -4,24 +4,145 @@ use std::{
     io::{Read, Write},
 };
 
-#[repr(C)]
-pub struct WrittenEntry {
-    key: [u8; 32],
-    data: [u8; 8],
+/// Start of every v2 and later file. v1 files start with a key, which is never a 0 byte.
+const MAGIC: [u8; 4] = [0, b'P', b'W', b'S'];
+
+/// v3 adds the body length and checksum to the header. v2 files are the same without them.
+const FORMAT_VERSION: u8 = 3;
+const FORMAT_VERSION_UNCHECKED: u8 = 2;
+
+/// Magic, version, body length, body crc
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4 + 4;
+
+// v1 files are fixed size entries, 32 bytes of space padded key then 8 bytes of value
+const V1_KEY_LEN: usize = 32;
+const V1_ENTRY_LEN: usize = V1_KEY_LEN + 8;
+
+const TAG_RAW: u8 = 0;
+const TAG_I32: u8 = 1;
+const TAG_U32: u8 = 2;
+const TAG_I64: u8 = 3;
+const TAG_U64: u8 = 4;
+const TAG_F32: u8 = 5;
+const TAG_F64: u8 = 6;
+const TAG_BOOL: u8 = 7;
+const TAG_STR: u8 = 8;
+const TAG_BLOB: u8 = 9;
+const TAG_SECTION: u8 = 10;
+
+/// Sections nested deeper than this are refused, so a crafted file can't run the reader out of stack
+pub const MAX_SECTION_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveValue {
+    /// 8 bytes with no type. Everything read from v1 files.
+    Raw([u8; 8]),
+
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Str(String),
+    Blob(Vec<u8>),
+
+    /// Nested file with its own keys
+    Section(SaveFile),
 }
 
-impl WrittenEntry {
-    pub fn new() -> Self {
-        Self {
-            key: [b' '; 32],
-            data: [0; 8],
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveFile {
+    pub entries: HashMap<String, SaveValue>,
+}
+
+/// Reads through the file bytes, erroring instead of running off the end
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() - self.pos < len {
+            return Err(Error::SaveFileTruncated);
         }
+
+        let ret = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(ret)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut ret = [0; N];
+        ret.copy_from_slice(self.take(N)?);
+        Ok(ret)
+    }
+
+    fn take_len(&mut self) -> Result<usize, Error> {
+        Ok(u32::from_le_bytes(self.take_array()?) as usize)
     }
 }
 
-#[derive(Debug)]
-pub struct SaveFile {
-    pub entries: HashMap<String, [u8; 8]>,
+fn write_len<W: Write>(writer: &mut W, len: usize) -> Result<(), Error> {
+    writer.write_all(&(len as u32).to_le_bytes())?;
+    Ok(())
+}
+
+/// CRC-32 (IEEE)
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Split data holding several saves written one after another into each save.
+/// A save with a broken length is skipped by searching for the next magic bytes.
+/// v1 and v2 files can't be split, so they are returned whole.
+pub fn split_saves(data: &[u8]) -> Vec<&[u8]> {
+    if !data.starts_with(&MAGIC) || data.get(MAGIC.len()) != Some(&FORMAT_VERSION) {
+        return vec![data];
+    }
+
+    let mut ret: Vec<&[u8]> = vec![];
+    let mut pos: usize = 0;
+
+    while pos < data.len() {
+        let rest = &data[pos..];
+
+        if rest.starts_with(&MAGIC) && rest.len() >= HEADER_LEN {
+            let mut len = [0; 4];
+            len.copy_from_slice(&rest[MAGIC.len() + 1..MAGIC.len() + 5]);
+            let end = HEADER_LEN + u32::from_le_bytes(len) as usize;
+
+            if end <= rest.len() {
+                ret.push(&rest[..end]);
+                pos += end;
+                continue;
+            }
+        }
+
+        // Broken. Keep whatever is left so the error is reported, then look for the next save.
+        match rest[1..].windows(MAGIC.len()).position(|w| w == MAGIC) {
+            Some(next) => {
+                ret.push(&rest[..next + 1]);
+                pos += next + 1;
+            }
+            None => {
+                ret.push(rest);
+                pos = data.len();
+            }
+        }
+    }
+
+    ret
 }
 
 impl SaveFile {
@@ -31,247 +152,323 @@ impl SaveFile {
         }
     }
 
-    /// Write out the entire file
+    /// Write out the entire file, with a header for checking it on read
     pub fn write_file<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
-        let mut entries: Vec<WrittenEntry> = vec![];
+        let mut body: Vec<u8> = vec![];
+        self.write_entries(&mut body)?;
+
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        write_len(writer, body.len())?;
+        writer.write_all(&crc32(&body).to_le_bytes())?;
+        writer.write_all(&body)?;
+        Ok(())
+    }
 
-        for (key, value) in &self.entries {
-            let mut new_entry = WrittenEntry::new();
+    /// Sorted by key so the same data always writes the same bytes
+    fn write_entries<W: Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut keys: Vec<&String> = self.entries.keys().collect();
+        keys.sort();
 
-            for (i, b) in key.bytes().enumerate() {
-                if i > new_entry.key.len() {
-                    return Err(Error::KeyTooLong(key.into()));
-                }
+        write_len(writer, keys.len())?;
+        for key in keys {
+            write_len(writer, key.len())?;
+            writer.write_all(key.as_bytes())?;
 
-                new_entry.key[i] = b;
+            match &self.entries[key] {
+                SaveValue::Raw(data) => {
+                    writer.write_all(&[TAG_RAW])?;
+                    writer.write_all(data)?;
+                }
+                SaveValue::I32(val) => {
+                    writer.write_all(&[TAG_I32])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::U32(val) => {
+                    writer.write_all(&[TAG_U32])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::I64(val) => {
+                    writer.write_all(&[TAG_I64])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::U64(val) => {
+                    writer.write_all(&[TAG_U64])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::F32(val) => {
+                    writer.write_all(&[TAG_F32])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::F64(val) => {
+                    writer.write_all(&[TAG_F64])?;
+                    writer.write_all(&val.to_le_bytes())?;
+                }
+                SaveValue::Bool(val) => {
+                    writer.write_all(&[TAG_BOOL, *val as u8])?;
+                }
+                SaveValue::Str(val) => {
+                    writer.write_all(&[TAG_STR])?;
+                    write_len(writer, val.len())?;
+                    writer.write_all(val.as_bytes())?;
+                }
+                SaveValue::Blob(val) => {
+                    writer.write_all(&[TAG_BLOB])?;
+                    write_len(writer, val.len())?;
+                    writer.write_all(val)?;
+                }
+                SaveValue::Section(section) => {
+                    writer.write_all(&[TAG_SECTION])?;
+                    section.write_entries(writer)?;
+                }
             }
-
-            new_entry.data = *value;
-
-            entries.push(new_entry);
-        }
-
-        for e in entries {
-            writer.write(e.key.as_slice())?;
-            writer.write(e.data.as_slice())?;
         }
 
         Ok(())
     }
 
-    /// Build save file from reader
-    pub fn read_file<W: Read>(reader: &mut W) -> Result<Self, Error> {
-        let mut ret = Self::new();
+    /// Build save file from reader. Reads every format version.
+    pub fn read_file<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut data: Vec<u8> = vec![];
+        reader.read_to_end(&mut data)?;
+        Self::read_bytes(&data)
+    }
 
-        // loop reading entires until we run out of data
-        loop {
-            let mut new_entry = WrittenEntry::new();
+    pub fn read_bytes(data: &[u8]) -> Result<Self, Error> {
+        if !data.starts_with(&MAGIC) {
+            return Self::read_v1(data);
+        }
 
-            match reader.read(&mut new_entry.key) {
-                Ok(v) => {
-                    if v == 0 {
-                        return Ok(ret);
-                    }
-                }
+        let mut reader = ByteReader {
+            data,
+            pos: MAGIC.len(),
+        };
 
-                // This should really return an error
-                Err(e) => return Ok(ret),
-            };
+        let version = reader.take_array::<1>()?[0];
+        let body = match version {
+            FORMAT_VERSION => {
+                let len = reader.take_len()?;
+                let crc = u32::from_le_bytes(reader.take_array()?);
 
-            match reader.read(&mut new_entry.data) {
-                Ok(v) => {
-                    if v == 0 {
-                        return Ok(ret);
-                    }
+                let body = reader.take(len)?;
+                if reader.pos != data.len() {
+                    return Err(Error::SaveFileTrailingData);
+                }
+                if crc32(body) != crc {
+                    return Err(Error::SaveFileChecksum);
                 }
 
-                // This should really return an error
-                Err(e) => return Ok(ret),
-            };
+                body
+            }
+            FORMAT_VERSION_UNCHECKED => &data[reader.pos..],
+            _ => return Err(Error::UnknownSaveFormat(version)),
+        };
 
-            let key: String = String::from_utf8(new_entry.key.to_vec())?.trim().into();
-            ret.entries.insert(key, new_entry.data);
-        }
-    }
+        let mut reader = ByteReader { data: body, pos: 0 };
 
-    pub fn save_f64(&mut self, key: &str, data: f64) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
+        let ret = Self::read_entries(&mut reader, 0)?;
+        if reader.pos != body.len() {
+            return Err(Error::SaveFileTrailingData);
         }
 
-        self.entries.insert(key.into(), data.to_le_bytes());
+        Ok(ret)
     }
 
-    pub fn save_u64(&mut self, key: &str, data: u64) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
+    fn read_v1(data: &[u8]) -> Result<Self, Error> {
+        if data.len() % V1_ENTRY_LEN != 0 {
+            return Err(Error::SaveFileTruncated);
         }
 
-        self.entries.insert(key.into(), data.to_le_bytes());
-    }
+        let mut ret = Self::new();
+        for chunk in data.chunks(V1_ENTRY_LEN) {
+            let key: String = String::from_utf8(chunk[..V1_KEY_LEN].to_vec())?
+                .trim()
+                .into();
 
-    pub fn save_i64(&mut self, key: &str, data: i64) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
+            let mut value = [0; 8];
+            value.copy_from_slice(&chunk[V1_KEY_LEN..]);
+
+            ret.entries.insert(key, SaveValue::Raw(value));
         }
 
-        self.entries.insert(key.into(), data.to_le_bytes());
+        Ok(ret)
     }
 
-    pub fn save_f32(&mut self, key: &str, data: f32) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
+    fn read_entries(reader: &mut ByteReader, depth: usize) -> Result<Self, Error> {
+        if depth > MAX_SECTION_DEPTH {
+            return Err(Error::SaveFileTooDeep);
         }
 
-        let b = data.to_le_bytes();
+        let mut ret = Self::new();
+
+        let count = reader.take_len()?;
+        for _ in 0..count {
+            let key_len = reader.take_len()?;
+            let key = String::from_utf8(reader.take(key_len)?.to_vec())?;
+
+            let tag = reader.take_array::<1>()?[0];
+            let value = match tag {
+                TAG_RAW => SaveValue::Raw(reader.take_array()?),
+                TAG_I32 => SaveValue::I32(i32::from_le_bytes(reader.take_array()?)),
+                TAG_U32 => SaveValue::U32(u32::from_le_bytes(reader.take_array()?)),
+                TAG_I64 => SaveValue::I64(i64::from_le_bytes(reader.take_array()?)),
+                TAG_U64 => SaveValue::U64(u64::from_le_bytes(reader.take_array()?)),
+                TAG_F32 => SaveValue::F32(f32::from_le_bytes(reader.take_array()?)),
+                TAG_F64 => SaveValue::F64(f64::from_le_bytes(reader.take_array()?)),
+                TAG_BOOL => SaveValue::Bool(reader.take_array::<1>()?[0] == 1),
+                TAG_STR => {
+                    let len = reader.take_len()?;
+                    SaveValue::Str(String::from_utf8(reader.take(len)?.to_vec())?)
+                }
+                TAG_BLOB => {
+                    let len = reader.take_len()?;
+                    SaveValue::Blob(reader.take(len)?.to_vec())
+                }
+                TAG_SECTION => SaveValue::Section(Self::read_entries(reader, depth + 1)?),
+                _ => return Err(Error::UnknownSaveValueTag(tag)),
+            };
 
-        let mut d = [0; 8];
-        d[0] = b[0];
-        d[1] = b[1];
-        d[2] = b[2];
-        d[3] = b[3];
+            ret.entries.insert(key, value);
+        }
 
-        self.entries.insert(key.into(), d);
+        Ok(ret)
     }
 
-    pub fn save_i32(&mut self, key: &str, data: i32) {
+    fn insert(&mut self, key: &str, value: SaveValue) {
         if self.entries.contains_key(key) {
             panic!("Key already exists {}", key);
         }
 
-        let b = data.to_le_bytes();
+        self.entries.insert(key.into(), value);
+    }
 
-        let mut d = [0; 8];
-        d[0] = b[0];
-        d[1] = b[1];
-        d[2] = b[2];
-        d[3] = b[3];
+    fn get(&self, key: &str) -> Result<&SaveValue, Error> {
+        self.entries
+            .get(key)
+            .ok_or_else(|| Error::MissingKey(key.into()))
+    }
 
-        self.entries.insert(key.into(), d);
+    /// First bytes of a raw v1 value
+    fn get_raw<const N: usize>(data: &[u8; 8]) -> [u8; N] {
+        let mut ret = [0; N];
+        ret.copy_from_slice(&data[..N]);
+        ret
     }
 
-    pub fn save_u32(&mut self, key: &str, data: u32) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
-        }
+    pub fn save_f64(&mut self, key: &str, data: f64) {
+        self.insert(key, SaveValue::F64(data));
+    }
+
+    pub fn save_u64(&mut self, key: &str, data: u64) {
+        self.insert(key, SaveValue::U64(data));
+    }
+
+    pub fn save_i64(&mut self, key: &str, data: i64) {
+        self.insert(key, SaveValue::I64(data));
+    }
 
-        let b = data.to_le_bytes();
+    pub fn save_f32(&mut self, key: &str, data: f32) {
+        self.insert(key, SaveValue::F32(data));
+    }
 
-        let mut d = [0; 8];
-        d[0] = b[0];
-        d[1] = b[1];
-        d[2] = b[2];
-        d[3] = b[3];
+    pub fn save_i32(&mut self, key: &str, data: i32) {
+        self.insert(key, SaveValue::I32(data));
+    }
 
-        self.entries.insert(key.into(), d);
+    pub fn save_u32(&mut self, key: &str, data: u32) {
+        self.insert(key, SaveValue::U32(data));
     }
 
     pub fn save_bool(&mut self, key: &str, data: bool) {
-        if self.entries.contains_key(key) {
-            panic!("Key already exists {}", key);
-        }
+        self.insert(key, SaveValue::Bool(data));
+    }
 
-        let mut d = [0; 8];
-        if data {
-            d[0] = 1;
-        } else {
-            d[0] = 0;
-        }
+    pub fn save_str(&mut self, key: &str, data: &str) {
+        self.insert(key, SaveValue::Str(data.into()));
+    }
 
-        self.entries.insert(key.into(), d);
+    pub fn save_blob(&mut self, key: &str, data: Vec<u8>) {
+        self.insert(key, SaveValue::Blob(data));
+    }
+
+    pub fn save_section(&mut self, key: &str, data: SaveFile) {
+        self.insert(key, SaveValue::Section(data));
     }
 
     pub fn load_f64(&self, key: &str) -> Result<f64, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::F64(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(f64::from_le_bytes(*data)),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
-
-        let data = *self.entries.get(key).unwrap();
-        let val = f64::from_le_bytes(data);
-        Ok(val)
     }
 
     pub fn load_i64(&self, key: &str) -> Result<i64, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::I64(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(i64::from_le_bytes(*data)),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
-
-        let data = *self.entries.get(key).unwrap();
-        let val = i64::from_le_bytes(data);
-        Ok(val)
     }
 
     pub fn load_u64(&self, key: &str) -> Result<u64, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::U64(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(u64::from_le_bytes(*data)),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
-
-        let data = *self.entries.get(key).unwrap();
-        let val = u64::from_le_bytes(data);
-        Ok(val)
     }
 
     pub fn load_f32(&self, key: &str) -> Result<f32, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::F32(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(f32::from_le_bytes(Self::get_raw(data))),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
-
-        let data = *self.entries.get(key).unwrap();
-
-        let mut d = [0; 4];
-        d[0] = data[0];
-        d[1] = data[1];
-        d[2] = data[2];
-        d[3] = data[3];
-
-        let val = f32::from_le_bytes(d);
-        Ok(val)
     }
 
     pub fn load_i32(&self, key: &str) -> Result<i32, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::I32(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(i32::from_le_bytes(Self::get_raw(data))),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
-
-        let data = *self.entries.get(key).unwrap();
-
-        let mut d = [0; 4];
-        d[0] = data[0];
-        d[1] = data[1];
-        d[2] = data[2];
-        d[3] = data[3];
-
-        let val = i32::from_le_bytes(d);
-        Ok(val)
     }
 
     pub fn load_u32(&self, key: &str) -> Result<u32, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+        match self.get(key)? {
+            SaveValue::U32(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(u32::from_le_bytes(Self::get_raw(data))),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
+    }
 
-        let data = *self.entries.get(key).unwrap();
-
-        let mut d = [0; 4];
-        d[0] = data[0];
-        d[1] = data[1];
-        d[2] = data[2];
-        d[3] = data[3];
+    pub fn load_bool(&self, key: &str) -> Result<bool, Error> {
+        match self.get(key)? {
+            SaveValue::Bool(val) => Ok(*val),
+            SaveValue::Raw(data) => Ok(data[0] == 1),
+            _ => Err(Error::WrongSaveValueType(key.into())),
+        }
+    }
 
-        let val = u32::from_le_bytes(d);
-        Ok(val)
+    pub fn load_str(&self, key: &str) -> Result<&str, Error> {
+        match self.get(key)? {
+            SaveValue::Str(val) => Ok(val),
+            _ => Err(Error::WrongSaveValueType(key.into())),
+        }
     }
 
-    pub fn load_bool(&self, key: &str) -> Result<bool, Error> {
-        if !self.entries.contains_key(key) {
-            return Err(Error::MissingKey(key.into()));
+    pub fn load_blob(&self, key: &str) -> Result<&[u8], Error> {
+        match self.get(key)? {
+            SaveValue::Blob(val) => Ok(val),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
+    }
 
-        let data = *self.entries.get(key).unwrap();
-        if data[0] == 1 {
-            Ok(true)
-        } else {
-            Ok(false)
+    pub fn load_section(&self, key: &str) -> Result<&SaveFile, Error> {
+        match self.get(key)? {
+            SaveValue::Section(val) => Ok(val),
+            _ => Err(Error::WrongSaveValueType(key.into())),
         }
     }
 }
@@ -324,4 +521,239 @@ mod test {
 
         assert_eq!(read_save_file.load_f32("key").unwrap(), 123.45);
     }
+
+    fn write_read(file: &SaveFile) -> SaveFile {
+        let mut cursor = Cursor::new(vec![]);
+        file.write_file(&mut cursor).unwrap();
+        cursor.set_position(0);
+        SaveFile::read_file(&mut cursor).unwrap()
+    }
+
+    fn v1_entry(key: &str, data: [u8; 8]) -> Vec<u8> {
+        let mut ret = vec![b' '; V1_KEY_LEN];
+        ret[..key.len()].copy_from_slice(key.as_bytes());
+        ret.extend_from_slice(&data);
+        ret
+    }
+
+    #[test]
+    fn read_write_typed() {
+        let mut orig_file = SaveFile::new();
+        orig_file.save_i32("i32", -12);
+        orig_file.save_u32("u32", 12);
+        orig_file.save_i64("i64", -5_000_000_000);
+        orig_file.save_u64("u64", u64::MAX);
+        orig_file.save_f64("f64", 0.125);
+        orig_file.save_bool("bool", true);
+
+        let file = write_read(&orig_file);
+        assert_eq!(file, orig_file);
+        assert_eq!(file.load_i32("i32").unwrap(), -12);
+        assert_eq!(file.load_u32("u32").unwrap(), 12);
+        assert_eq!(file.load_i64("i64").unwrap(), -5_000_000_000);
+        assert_eq!(file.load_u64("u64").unwrap(), u64::MAX);
+        assert_eq!(file.load_f64("f64").unwrap(), 0.125);
+        assert_eq!(file.load_bool("bool").unwrap(), true);
+    }
+
+    #[test]
+    fn read_write_str_blob_section() {
+        let long_key = "a key that is much longer than the old thirty two byte limit";
+
+        let mut section = SaveFile::new();
+        section.save_str("name", "Mud Pit");
+        section.save_i64("count", 3);
+
+        let mut orig_file = SaveFile::new();
+        orig_file.save_str(long_key, "hello ✓");
+        orig_file.save_blob("blob", vec![0, 1, 2, 255]);
+        orig_file.save_section("section", section);
+
+        let file = write_read(&orig_file);
+        assert_eq!(file.load_str(long_key).unwrap(), "hello ✓");
+        assert_eq!(file.load_blob("blob").unwrap(), &[0, 1, 2, 255]);
+
+        let section = file.load_section("section").unwrap();
+        assert_eq!(section.load_str("name").unwrap(), "Mud Pit");
+        assert_eq!(section.load_i64("count").unwrap(), 3);
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut file = SaveFile::new();
+        file.save_i64("key", 10);
+
+        assert!(matches!(
+            file.load_i32("key"),
+            Err(Error::WrongSaveValueType(_))
+        ));
+        assert!(matches!(
+            file.load_str("key"),
+            Err(Error::WrongSaveValueType(_))
+        ));
+        assert!(matches!(
+            file.load_i64("missing"),
+            Err(Error::MissingKey(_))
+        ));
+    }
+
+    #[test]
+    fn read_v1() {
+        let mut data: Vec<u8> = vec![];
+        data.extend(v1_entry("next_entity_id", 7u64.to_le_bytes()));
+        data.extend(v1_entry("0.x", [254, 255, 255, 255, 0, 0, 0, 0]));
+        data.extend(v1_entry("unix_time_saved", 12.5f64.to_le_bytes()));
+        data.extend(v1_entry(
+            "a.key.of.exactly.thirty.two.byte",
+            [1, 0, 0, 0, 0, 0, 0, 0],
+        ));
+
+        let file = SaveFile::read_file(&mut Cursor::new(data)).unwrap();
+        assert_eq!(file.load_u64("next_entity_id").unwrap(), 7);
+        assert_eq!(file.load_i32("0.x").unwrap(), -2);
+        assert_eq!(file.load_f64("unix_time_saved").unwrap(), 12.5);
+        assert_eq!(
+            file.load_bool("a.key.of.exactly.thirty.two.byte").unwrap(),
+            true
+        );
+
+        // Resaving writes v2
+        let resaved = write_read(&file);
+        assert_eq!(resaved.load_i32("0.x").unwrap(), -2);
+    }
+
+    #[test]
+    fn read_errors() {
+        let mut file = SaveFile::new();
+        file.save_str("key", "value");
+
+        let mut data: Vec<u8> = vec![];
+        file.write_file(&mut data).unwrap();
+
+        let truncated = data[..data.len() - 2].to_vec();
+        assert!(matches!(
+            SaveFile::read_file(&mut Cursor::new(truncated)),
+            Err(Error::SaveFileTruncated)
+        ));
+
+        let mut trailing = data.clone();
+        trailing.push(0);
+        assert!(matches!(
+            SaveFile::read_file(&mut Cursor::new(trailing)),
+            Err(Error::SaveFileTrailingData)
+        ));
+
+        let mut flipped = data.clone();
+        let last = flipped.len() - 1;
+        flipped[last] ^= 1;
+        assert!(matches!(
+            SaveFile::read_file(&mut Cursor::new(flipped)),
+            Err(Error::SaveFileChecksum)
+        ));
+
+        // v2 files have no checksum, so a bad byte shows up while reading
+        let mut bad_tag: Vec<u8> = MAGIC.to_vec();
+        bad_tag.push(FORMAT_VERSION_UNCHECKED);
+        file.write_entries(&mut bad_tag).unwrap();
+        let tag_pos = MAGIC.len() + 1 + 4 + 4 + "key".len();
+        bad_tag[tag_pos] = 200;
+        assert!(matches!(
+            SaveFile::read_file(&mut Cursor::new(bad_tag)),
+            Err(Error::UnknownSaveValueTag(200))
+        ));
+
+        let mut v1_partial = v1_entry("key", [0; 8]);
+        v1_partial.pop();
+        assert!(matches!(
+            SaveFile::read_file(&mut Cursor::new(v1_partial)),
+            Err(Error::SaveFileTruncated)
+        ));
+    }
+
+    #[test]
+    fn nested_sections() {
+        let mut deepest = SaveFile::new();
+        deepest.save_i32("key", 4);
+        for _ in 0..MAX_SECTION_DEPTH {
+            let mut outer = SaveFile::new();
+            outer.save_section("s", deepest);
+            deepest = outer;
+        }
+        assert_eq!(write_read(&deepest), deepest);
+
+        // Built by hand, writing it would recurse just as deep. The checksum is valid.
+        let mut body: Vec<u8> = vec![];
+        for _ in 0..100_000 {
+            write_len(&mut body, 1).unwrap();
+            write_len(&mut body, 1).unwrap();
+            body.extend_from_slice(&[b's', TAG_SECTION]);
+        }
+        write_len(&mut body, 0).unwrap();
+
+        let mut data: Vec<u8> = MAGIC.to_vec();
+        data.push(FORMAT_VERSION);
+        write_len(&mut data, body.len()).unwrap();
+        data.extend_from_slice(&crc32(&body).to_le_bytes());
+        data.extend_from_slice(&body);
+        assert!(matches!(
+            SaveFile::read_bytes(&data),
+            Err(Error::SaveFileTooDeep)
+        ));
+    }
+
+    #[test]
+    fn read_v2() {
+        let mut file = SaveFile::new();
+        file.save_i32("key", 4);
+
+        let mut data: Vec<u8> = MAGIC.to_vec();
+        data.push(FORMAT_VERSION_UNCHECKED);
+        file.write_entries(&mut data).unwrap();
+
+        assert_eq!(SaveFile::read_bytes(&data).unwrap(), file);
+    }
+
+    #[test]
+    fn crc() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(&[]), 0);
+    }
+
+    #[test]
+    fn split() {
+        let mut first = SaveFile::new();
+        first.save_i32("a", 1);
+        let mut second = SaveFile::new();
+        second.save_str("b", "two");
+
+        let mut data: Vec<u8> = vec![];
+        first.write_file(&mut data).unwrap();
+        let first_len = data.len();
+        second.write_file(&mut data).unwrap();
+        first.write_file(&mut data).unwrap();
+
+        let saves = split_saves(&data);
+        assert_eq!(saves.len(), 3);
+        assert_eq!(SaveFile::read_bytes(saves[1]).unwrap(), second);
+        assert_eq!(SaveFile::read_bytes(saves[2]).unwrap(), first);
+
+        // Break the length of the first. The others are still found.
+        data[MAGIC.len() + 1] = 255;
+        data[MAGIC.len() + 2] = 255;
+        let saves = split_saves(&data);
+        assert_eq!(saves.len(), 3);
+        assert_eq!(saves[0].len(), first_len);
+        assert!(SaveFile::read_bytes(saves[0]).is_err());
+        assert_eq!(SaveFile::read_bytes(saves[1]).unwrap(), second);
+
+        // Cut off the end. Only the last is lost.
+        data.truncate(data.len() - 3);
+        let saves = split_saves(&data);
+        assert_eq!(SaveFile::read_bytes(saves[1]).unwrap(), second);
+        assert!(SaveFile::read_bytes(saves[2]).is_err());
+
+        // v1 data is one save
+        let v1 = v1_entry("key", [0; 8]);
+        assert_eq!(split_saves(&v1).len(), 1);
+    }
 }
@@ -0,0 +1,132 @@
+use crate::{error::Error, save_file::*};
+
+/// Version written into every new save. Bump this and add a migration whenever saved keys or values change.
+pub const SAVE_VERSION: i32 = 1;
+
+/// Saves from before versioning don't have this key and are version 0
+pub const SAVE_VERSION_KEY: &str = "save_version";
+
+/// Upgrades a save from one version to the next
+pub type Migration = fn(&mut SaveFile) -> Result<(), Error>;
+
+/// Index N upgrades a version N save to version N + 1. Never reorder or remove these.
+pub const MIGRATIONS: [Migration; SAVE_VERSION as usize] = [migrate_v0];
+
+/// Version 1 is the first versioned format. Nothing else changed.
+fn migrate_v0(_save_file: &mut SaveFile) -> Result<(), Error> {
+    Ok(())
+}
+
+pub fn save_version(save_file: &SaveFile) -> i32 {
+    save_file.load_i32(SAVE_VERSION_KEY).unwrap_or(0)
+}
+
+/// Upgrade the save to the current version
+pub fn migrate(save_file: &mut SaveFile) -> Result<(), Error> {
+    migrate_with(save_file, &MIGRATIONS)
+}
+
+fn migrate_with(save_file: &mut SaveFile, migrations: &[Migration]) -> Result<(), Error> {
+    let version = save_version(save_file);
+    let latest = migrations.len() as i32;
+
+    if version < 0 {
+        return Err(Error::InvalidSaveVersion(version));
+    }
+    if version > latest {
+        return Err(Error::SaveVersionTooNew(version));
+    }
+
+    for migration in &migrations[version as usize..] {
+        migration(save_file)?;
+    }
+
+    save_file.entries.remove(SAVE_VERSION_KEY);
+    save_file.save_i32(SAVE_VERSION_KEY, latest);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Pretend grass moved from tile index 1 to 30
+    fn renumber_grass(save_file: &mut SaveFile) -> Result<(), Error> {
+        let keys: Vec<String> = save_file
+            .entries
+            .keys()
+            .filter(|key| key.ends_with(".type"))
+            .cloned()
+            .collect();
+
+        for key in keys {
+            if save_file.load_i32(&key)? == 1 {
+                save_file.entries.remove(&key);
+                save_file.save_i32(&key, 30);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_gold(save_file: &mut SaveFile) -> Result<(), Error> {
+        let gold = save_file.load_i64(".g")?;
+        save_file.entries.remove(".g");
+        save_file.save_i64(".g", gold + 5);
+        Ok(())
+    }
+
+    #[test]
+    fn unversioned() {
+        let mut file = SaveFile::new();
+        assert_eq!(save_version(&file), 0);
+
+        migrate(&mut file).unwrap();
+        assert_eq!(save_version(&file), SAVE_VERSION);
+    }
+
+    #[test]
+    fn runs_in_order() {
+        let migrations: [Migration; 3] = [renumber_grass, add_gold, add_gold];
+
+        let mut file = SaveFile::new();
+        file.save_i32("4.type", 1);
+        file.save_i32("5.type", 0);
+        file.save_i64(".g", 10);
+
+        migrate_with(&mut file, &migrations).unwrap();
+
+        assert_eq!(file.load_i32("4.type").unwrap(), 30);
+        assert_eq!(file.load_i32("5.type").unwrap(), 0);
+        assert_eq!(file.load_i64(".g").unwrap(), 20);
+        assert_eq!(save_version(&file), 3);
+    }
+
+    #[test]
+    fn skips_done_migrations() {
+        let migrations: [Migration; 3] = [renumber_grass, add_gold, add_gold];
+
+        let mut file = SaveFile::new();
+        file.save_i32(SAVE_VERSION_KEY, 2);
+        file.save_i32("4.type", 1);
+        file.save_i64(".g", 10);
+
+        migrate_with(&mut file, &migrations).unwrap();
+
+        assert_eq!(file.load_i32("4.type").unwrap(), 1);
+        assert_eq!(file.load_i64(".g").unwrap(), 15);
+        assert_eq!(save_version(&file), 3);
+    }
+
+    #[test]
+    fn too_new() {
+        let mut file = SaveFile::new();
+        file.save_i32(SAVE_VERSION_KEY, SAVE_VERSION + 1);
+
+        assert!(matches!(
+            migrate(&mut file),
+            Err(Error::SaveVersionTooNew(_))
+        ));
+    }
+}
@@ -0,0 +1,375 @@
+use crate::{
+    error::*,
+    save_file::{kvp_file::crc32, *},
+};
+use std::collections::HashMap;
+
+/// Every save code starts with this. Bump it if the format changes.
+pub const SAVE_CODE_PREFIX: &str = "PWS1-";
+
+/// URL safe, so codes survive being put in links and file names
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Compressed data is runs of literals and back references.
+// Control bytes below 0x80 are followed by control + 1 literal bytes.
+// Control bytes from 0x80 copy (control & 0x7f) + MIN_MATCH bytes from a u16 distance back.
+const MAX_LITERALS: usize = 0x80;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 0x7f + MIN_MATCH;
+const MAX_DISTANCE: usize = u16::MAX as usize;
+
+/// Far past any real save. The length in a code's header is checked against this before anything is allocated.
+pub const MAX_DECODED_LEN: usize = 8 * 1024 * 1024;
+
+/// A whole save file as text, for moving a game between devices
+#[derive(Debug, Clone)]
+pub struct SaveCode {
+    /// One save, as written by SaveFile::write_file
+    pub data: Vec<u8>,
+    pub preview: SavePreview,
+}
+
+impl SaveCode {
+    pub fn encode(data: &[u8]) -> String {
+        format!("{}{}", SAVE_CODE_PREFIX, encode_bytes(data))
+    }
+
+    /// Whitespace is ignored, so codes wrapped over several lines still work
+    pub fn decode(code: &str) -> Result<Self, Error> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let Some(body) = code.strip_prefix(SAVE_CODE_PREFIX) else {
+            return Err(Error::InvalidSaveCode);
+        };
+        let data = decode_bytes(body)?;
+
+        let mut save_file = SaveFile::read_bytes(&data)?;
+        migrate(&mut save_file)?;
+
+        let preview = SavePreview::from_save_file(&save_file)?;
+        Ok(Self { data, preview })
+    }
+}
+
+/// Any bytes as compressed text, with their length and checksum
+pub fn encode_bytes(data: &[u8]) -> String {
+    let mut packed: Vec<u8> = vec![];
+    packed.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    packed.extend_from_slice(&crc32(data).to_le_bytes());
+    packed.append(&mut compress(data));
+
+    base64_encode(&packed)
+}
+
+/// Errors if the text is damaged
+pub fn decode_bytes(text: &str) -> Result<Vec<u8>, Error> {
+    let packed = base64_decode(text)?;
+    if packed.len() < 8 {
+        return Err(Error::InvalidSaveCode);
+    }
+
+    let len = u32::from_le_bytes([packed[0], packed[1], packed[2], packed[3]]) as usize;
+    let crc = u32::from_le_bytes([packed[4], packed[5], packed[6], packed[7]]);
+    if len > MAX_DECODED_LEN {
+        return Err(Error::SaveCodeTooLarge);
+    }
+
+    let data = decompress(&packed[8..], len)?;
+    if data.len() != len || crc32(&data) != crc {
+        return Err(Error::SaveCodeDamaged);
+    }
+    Ok(data)
+}
+
+/// Text for a save code that couldn't be imported
+pub fn save_code_error_message(error: &Error) -> String {
+    match error {
+        Error::InvalidSaveCode => "That isn't a save code.".into(),
+        Error::SaveCodeDamaged => "That save code is damaged. Copy the whole code.".into(),
+        Error::SaveCodeTooLarge | Error::SaveFileTooDeep => {
+            "That save code wasn't made by this game.".into()
+        }
+        Error::SaveVersionTooNew(_) => "That save is from a newer version of the game.".into(),
+        _ => format!("That save can't be loaded {:?}", error),
+    }
+}
+
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut ret: Vec<u8> = vec![];
+    let mut literals: Vec<u8> = vec![];
+
+    // Last position each 3 bytes were seen
+    let mut seen: HashMap<[u8; MIN_MATCH], usize> = HashMap::new();
+    let key_at = |i: usize| -> Option<[u8; MIN_MATCH]> {
+        let bytes = data.get(i..i + MIN_MATCH)?;
+        Some([bytes[0], bytes[1], bytes[2]])
+    };
+
+    let flush = |ret: &mut Vec<u8>, literals: &mut Vec<u8>| {
+        if !literals.is_empty() {
+            ret.push((literals.len() - 1) as u8);
+            ret.append(literals);
+        }
+    };
+
+    let mut i: usize = 0;
+    while i < data.len() {
+        let mut match_len: usize = 0;
+        let mut distance: usize = 0;
+
+        if let Some(key) = key_at(i) {
+            if let Some(prev) = seen.insert(key, i) {
+                if i - prev <= MAX_DISTANCE {
+                    while match_len < MAX_MATCH
+                        && i + match_len < data.len()
+                        && data[prev + match_len] == data[i + match_len]
+                    {
+                        match_len += 1;
+                    }
+                    distance = i - prev;
+                }
+            }
+        }
+
+        if match_len >= MIN_MATCH {
+            flush(&mut ret, &mut literals);
+            ret.push(0x80 | (match_len - MIN_MATCH) as u8);
+            ret.extend_from_slice(&(distance as u16).to_le_bytes());
+
+            for skipped in i + 1..i + match_len {
+                if let Some(key) = key_at(skipped) {
+                    seen.insert(key, skipped);
+                }
+            }
+            i += match_len;
+        } else {
+            literals.push(data[i]);
+            if literals.len() == MAX_LITERALS {
+                flush(&mut ret, &mut literals);
+            }
+            i += 1;
+        }
+    }
+
+    flush(&mut ret, &mut literals);
+    ret
+}
+
+/// Stops past len, so a damaged code can't grow without bound. len must already be checked against MAX_DECODED_LEN.
+fn decompress(data: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+    let mut ret: Vec<u8> = Vec::with_capacity(len);
+    let mut i: usize = 0;
+
+    while i < data.len() {
+        let control = data[i] as usize;
+        i += 1;
+
+        if control < MAX_LITERALS {
+            let run = control + 1;
+            let Some(literals) = data.get(i..i + run) else {
+                return Err(Error::SaveCodeDamaged);
+            };
+            ret.extend_from_slice(literals);
+            i += run;
+        } else {
+            let match_len = (control & 0x7f) + MIN_MATCH;
+            let Some(distance) = data.get(i..i + 2) else {
+                return Err(Error::SaveCodeDamaged);
+            };
+            let distance = u16::from_le_bytes([distance[0], distance[1]]) as usize;
+            i += 2;
+
+            if distance == 0 || distance > ret.len() {
+                return Err(Error::SaveCodeDamaged);
+            }
+
+            // Byte by byte, since the copy can overlap what it's writing
+            let start = ret.len() - distance;
+            for j in 0..match_len {
+                ret.push(ret[start + j]);
+            }
+        }
+
+        if ret.len() > len {
+            return Err(Error::SaveCodeDamaged);
+        }
+    }
+
+    Ok(ret)
+}
+
+/// No padding
+fn base64_encode(data: &[u8]) -> String {
+    let mut ret = String::new();
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let bits = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..chunk.len() + 1 {
+            let index = (bits >> (18 - i * 6)) & 0x3f;
+            ret.push(BASE64_CHARS[index as usize] as char);
+        }
+    }
+
+    ret
+}
+
+fn base64_decode(input: &str) -> Result<Vec<u8>, Error> {
+    let mut ret: Vec<u8> = vec![];
+    let mut bits: u32 = 0;
+    let mut bit_count: u32 = 0;
+
+    for c in input.bytes() {
+        let Some(value) = BASE64_CHARS.iter().position(|b| *b == c) else {
+            return Err(Error::InvalidSaveCode);
+        };
+
+        bits = (bits << 6) | value as u32;
+        bit_count += 6;
+
+        if bit_count >= 8 {
+            bit_count -= 8;
+            ret.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        setup_initial,
+        state::{inventory::*, player_state::*},
+        testing_infra::*,
+        world::*,
+    };
+
+    fn starter_save() -> Vec<u8> {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+        inventory.gold = 250;
+
+        let save_file = build_save_file(
+            &world,
+            &inventory,
+            &PlayerState::new(),
+            &windows_plaform_api(),
+        )
+        .unwrap();
+
+        let mut data: Vec<u8> = vec![];
+        save_file.write_file(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn compress_round_trip() {
+        let inputs: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![7],
+            vec![0; 1000],
+            b"abcabcabcabcabcabc_unique_abcabc".to_vec(),
+            (0..5000).map(|i| ((i * 31) % 251) as u8).collect(),
+            starter_save(),
+        ];
+
+        for input in inputs {
+            let packed = compress(&input);
+            assert_eq!(decompress(&packed, input.len()).unwrap(), input);
+        }
+
+        // Repeated keys should make saves much smaller
+        let save = starter_save();
+        assert!(compress(&save).len() < save.len() / 2);
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        for len in 0..10 {
+            let input: Vec<u8> = (0..len).map(|i| (i * 77) as u8).collect();
+            assert_eq!(base64_decode(&base64_encode(&input)).unwrap(), input);
+        }
+        assert_eq!(base64_encode(b"Man"), "TWFu");
+        assert_eq!(base64_encode(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn encode_decode() {
+        let data = starter_save();
+        let code = SaveCode::encode(&data);
+        assert!(code.starts_with(SAVE_CODE_PREFIX));
+
+        // Pasting can wrap lines
+        let wrapped: String = code
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| if i % 40 == 39 { vec![c, '\n'] } else { vec![c] })
+            .collect();
+
+        let decoded = SaveCode::decode(&format!("  {} \n", wrapped)).unwrap();
+        assert_eq!(decoded.data, data);
+        assert_eq!(decoded.preview.gold, 250);
+        assert_eq!(decoded.preview.tile_count, 1);
+    }
+
+    #[test]
+    fn damaged() {
+        let code = SaveCode::encode(&starter_save());
+
+        assert!(matches!(
+            SaveCode::decode("PWT1-abc"),
+            Err(Error::InvalidSaveCode)
+        ));
+        assert!(matches!(
+            SaveCode::decode(&format!("{}!!", code)),
+            Err(Error::InvalidSaveCode)
+        ));
+
+        // Cut off while copying
+        assert!(SaveCode::decode(&code[..code.len() - 10]).is_err());
+
+        // One changed character
+        let mut changed: Vec<char> = code.chars().collect();
+        let i = changed.len() - 20;
+        changed[i] = if changed[i] == 'A' { 'B' } else { 'A' };
+        let changed: String = changed.into_iter().collect();
+        assert!(SaveCode::decode(&changed).is_err());
+    }
+
+    #[test]
+    fn hostile() {
+        // Claims to be 4GB, refused before decompressing
+        let mut packed: Vec<u8> = vec![];
+        packed.extend_from_slice(&u32::MAX.to_le_bytes());
+        packed.extend_from_slice(&0u32.to_le_bytes());
+        packed.append(&mut compress(&[0; 1000]));
+        assert!(matches!(
+            decode_bytes(&base64_encode(&packed)),
+            Err(Error::SaveCodeTooLarge)
+        ));
+
+        // Sections nested far too deep. The length and checksums are all valid.
+        let mut deep = SaveFile::new();
+        for _ in 0..kvp_file::MAX_SECTION_DEPTH + 1 {
+            let mut outer = SaveFile::new();
+            outer.save_section("s", deep);
+            deep = outer;
+        }
+        let mut data: Vec<u8> = vec![];
+        deep.write_file(&mut data).unwrap();
+
+        assert!(matches!(
+            SaveCode::decode(&SaveCode::encode(&data)),
+            Err(Error::SaveFileTooDeep)
+        ));
+    }
+}
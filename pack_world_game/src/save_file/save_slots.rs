@@ -0,0 +1,621 @@
+use crate::{error::*, save_file::*, trade::*};
+use elara_engine::platform_api::*;
+
+/// Name of the slot a save belongs to
+pub const SAVE_SLOT_KEY: &str = "save_slot";
+
+/// Saves from before slots have no slot name and go here
+pub const DEFAULT_SLOT_NAME: &str = "Main";
+
+pub const MAX_SAVE_SLOTS: usize = 5;
+
+/// Storage key of the slot names, one a line with the active slot first.
+/// Only the names are kept in local storage. The saves go through the platform's save data.
+pub const SAVE_SLOTS_KEY: &str = "save_slots";
+
+/// Marks the first save in the save data as the header, naming the slot being played
+pub const ACTIVE_SLOT_KEY: &str = "active_slot";
+pub const ACTIVE_SLOT_INDEX_KEY: &str = "active_slot_index";
+
+/// Past this, the oldest snapshots of the other slots are left out of the save data
+pub const SAVE_DATA_BUDGET: usize = 4 * 1024 * 1024;
+
+/// One game. Holds its saves newest first, the same data load_game takes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSlot {
+    pub name: String,
+
+    /// Full saves
+    pub data: Vec<u8>,
+
+    /// Changes since the newest full save
+    pub delta: Vec<u8>,
+
+    /// Snapshots newest first. Only change when a full save takes one.
+    pub snapshots: Vec<u8>,
+}
+
+impl SaveSlot {
+    pub fn new(name: String, data: Vec<u8>) -> Self {
+        Self {
+            name,
+            data,
+            delta: vec![],
+            snapshots: vec![],
+        }
+    }
+
+    /// The delta, the full saves, then the snapshots
+    pub fn saves(&self) -> Vec<u8> {
+        [
+            self.delta.as_slice(),
+            self.data.as_slice(),
+            self.snapshots.as_slice(),
+        ]
+        .concat()
+    }
+
+    fn push(&mut self, save: &[u8]) {
+        match SaveFile::read_bytes(save) {
+            Ok(save_file) if is_delta(&save_file) => self.delta.extend_from_slice(save),
+            Ok(save_file) if save_file.load_bool(SNAPSHOT_KEY).unwrap_or(false) => {
+                self.snapshots.extend_from_slice(save)
+            }
+            _ => self.data.extend_from_slice(save),
+        }
+    }
+}
+
+/// Every save slot, sorted by name. All of them are written out together as the platform's save data.
+/// That starts with a header naming the active slot, then the active slot's saves, then the other slots'.
+/// Each save names its slot. Data from before the header has the active slot first.
+#[derive(Debug, Clone)]
+pub struct SaveSlots {
+    pub slots: Vec<SaveSlot>,
+    pub active: usize,
+}
+
+impl SaveSlots {
+    pub fn new() -> Self {
+        Self {
+            slots: vec![SaveSlot::new(DEFAULT_SLOT_NAME.into(), vec![])],
+            active: 0,
+        }
+    }
+
+    /// Saves that can't be read stay with the slot of the save before them, so loading still reports them
+    pub fn read(data: &[u8]) -> Self {
+        let mut saves = split_saves(data);
+
+        let mut active_name: Option<String> = None;
+        if let Some(header) = saves.first().and_then(|save| active_slot_name(save)) {
+            active_name = Some(header);
+            saves.remove(0);
+        }
+
+        let names: Vec<Option<String>> = saves.iter().map(|save| slot_name(save)).collect();
+        let Some(mut current) = names.iter().flatten().next().cloned() else {
+            let mut slot = SaveSlot::new(DEFAULT_SLOT_NAME.into(), vec![]);
+            for save in saves {
+                slot.push(save);
+            }
+            return Self {
+                slots: vec![slot],
+                active: 0,
+            };
+        };
+
+        let mut slots: Vec<SaveSlot> = vec![];
+        for (save, name) in saves.iter().zip(names) {
+            if let Some(name) = name {
+                current = name;
+            }
+
+            let index = match slots.iter().position(|slot| slot.name == current) {
+                Some(index) => index,
+                None => {
+                    slots.push(SaveSlot::new(current.clone(), vec![]));
+                    slots.len() - 1
+                }
+            };
+            slots[index].push(save);
+        }
+
+        let active_name = active_name.unwrap_or_else(|| slots[0].name.clone());
+        let mut ret = Self { slots, active: 0 };
+        ret.sort(&active_name);
+        ret
+    }
+
+    /// Adds the slots named in local storage that have no saves yet, so they aren't lost before their first save
+    pub fn fetch_names(&mut self, platform_api: &PlatformApi) {
+        let Some(names) = (platform_api.local_persist_get)(SAVE_SLOTS_KEY) else {
+            return;
+        };
+
+        let active_name = self.active().name.clone();
+        for name in names.lines().filter(|name| !name.is_empty()) {
+            if self.slots.len() >= MAX_SAVE_SLOTS {
+                break;
+            }
+            if !self.slots.iter().any(|slot| slot.name == name) {
+                self.slots.push(SaveSlot::new(name.into(), vec![]));
+            }
+        }
+        self.sort(&active_name);
+    }
+
+    /// The slot names are only a few bytes, so they're written with every save
+    pub fn store_names(&self, platform_api: &PlatformApi) {
+        let mut names: Vec<&str> = vec![self.active().name.as_str()];
+        for (i, slot) in self.slots.iter().enumerate() {
+            if i != self.active {
+                names.push(&slot.name);
+            }
+        }
+        (platform_api.local_persist_set)(SAVE_SLOTS_KEY, &names.join("\n"));
+    }
+
+    /// Every slot as one block for the platform's save data. The header names the active slot.
+    /// Slots without a save aren't written. Other slots' oldest snapshots are dropped to stay in SAVE_DATA_BUDGET.
+    pub fn write(&mut self) -> Result<Vec<u8>, Error> {
+        self.trim_snapshots(SAVE_DATA_BUDGET);
+
+        let mut header = SaveFile::new();
+        header.save_str(ACTIVE_SLOT_KEY, &self.active().name);
+        header.save_u64(ACTIVE_SLOT_INDEX_KEY, self.active as u64);
+
+        let mut ret: Vec<u8> = vec![];
+        header.write_file(&mut ret)?;
+        ret.append(&mut self.active().saves());
+        for (i, slot) in self.slots.iter().enumerate() {
+            if i != self.active {
+                ret.append(&mut slot.saves());
+            }
+        }
+        Ok(ret)
+    }
+
+    /// The active slot's snapshots are the ones the player sees, so only the other slots lose any.
+    /// Takes the oldest snapshot of whichever slot's snapshots are largest, until the saves fit.
+    fn trim_snapshots(&mut self, budget: usize) {
+        let size = |slots: &[SaveSlot]| -> usize {
+            slots
+                .iter()
+                .map(|slot| slot.delta.len() + slot.data.len() + slot.snapshots.len())
+                .sum()
+        };
+
+        while size(&self.slots) > budget {
+            let largest = (0..self.slots.len())
+                .filter(|i| *i != self.active && !self.slots[*i].snapshots.is_empty())
+                .max_by_key(|i| self.slots[*i].snapshots.len());
+            let Some(largest) = largest else {
+                return;
+            };
+
+            let slot = &mut self.slots[largest];
+            let oldest = split_saves(&slot.snapshots)
+                .last()
+                .map_or(0, |save| save.len());
+            let kept = slot.snapshots.len() - oldest;
+            slot.snapshots.truncate(kept);
+        }
+    }
+
+    pub fn active(&self) -> &SaveSlot {
+        &self.slots[self.active]
+    }
+
+    pub fn set_active_data(&mut self, data: Vec<u8>) {
+        self.set_data(self.active, data);
+    }
+
+    /// Replaces a slot's saves with full saves. Its snapshots are kept.
+    pub fn set_data(&mut self, index: usize, data: Vec<u8>) {
+        let slot = &mut self.slots[index];
+        slot.data = data;
+        slot.delta.clear();
+    }
+
+    pub fn set_snapshots(&mut self, index: usize, snapshots: Vec<u8>) {
+        self.slots[index].snapshots = snapshots;
+    }
+
+    /// The changes since the active slot's newest full save
+    pub fn set_active_delta(&mut self, delta: Vec<u8>) {
+        self.slots[self.active].delta = delta;
+    }
+
+    /// Adds an empty slot and returns its index
+    pub fn create(&mut self, name: &str) -> Result<usize, Error> {
+        if self.slots.len() >= MAX_SAVE_SLOTS {
+            return Err(Error::SaveSlotLimit);
+        }
+
+        let name = self.unique_name(name);
+        Ok(self.insert(SaveSlot::new(name, vec![])))
+    }
+
+    /// Copies a slot's newest save under a new name and returns the copy's index.
+    /// The copy trades as save_id, so offers it carries over can't be paid out twice.
+    pub fn duplicate(&mut self, index: usize, save_id: u64) -> Result<usize, Error> {
+        let Some(slot) = self.slots.get(index) else {
+            return Err(Error::UnknownSaveSlot(index));
+        };
+        if self.slots.len() >= MAX_SAVE_SLOTS {
+            return Err(Error::SaveSlotLimit);
+        }
+
+        let name = self.unique_name(&format!("{} copy", slot.name));
+        let data = fork_saves(&slot.saves(), &name, save_id)?;
+        Ok(self.insert(SaveSlot::new(name, data)))
+    }
+
+    /// Removes the slot with its saves and snapshots. They're gone from the save data on the next save.
+    pub fn delete(&mut self, index: usize) -> Result<(), Error> {
+        if index >= self.slots.len() {
+            return Err(Error::UnknownSaveSlot(index));
+        }
+        if index == self.active {
+            return Err(Error::DeleteActiveSaveSlot);
+        }
+
+        self.slots.remove(index);
+        if index < self.active {
+            self.active -= 1;
+        }
+        Ok(())
+    }
+
+    fn insert(&mut self, slot: SaveSlot) -> usize {
+        let name = slot.name.clone();
+        let active_name = self.active().name.clone();
+
+        self.slots.push(slot);
+        self.sort(&active_name);
+
+        self.slots
+            .iter()
+            .position(|slot| slot.name == name)
+            .unwrap()
+    }
+
+    fn sort(&mut self, active_name: &str) {
+        self.slots.sort_by(|a, b| a.name.cmp(&b.name));
+        self.active = self
+            .slots
+            .iter()
+            .position(|slot| slot.name == active_name)
+            .unwrap_or(0);
+    }
+
+    fn unique_name(&self, name: &str) -> String {
+        let name = match name.trim() {
+            "" => "Slot",
+            name => name,
+        };
+
+        let taken = |name: &str| self.slots.iter().any(|slot| slot.name == name);
+        if !taken(name) {
+            return name.into();
+        }
+
+        (2..)
+            .map(|i| format!("{} {}", name, i))
+            .find(|numbered| !taken(numbered))
+            .unwrap()
+    }
+}
+
+/// Toast text for a failed save slot change
+pub fn save_slot_error_message(error: &Error) -> String {
+    match error {
+        Error::SaveSlotLimit => format!("You can only have {} profiles.", MAX_SAVE_SLOTS),
+        Error::DeleteActiveSaveSlot => "Switch to another profile before deleting this one.".into(),
+        _ => format!("Profile change failed {:?}", error),
+    }
+}
+
+/// Active slot named by the header of the save data, or None if the save isn't the header
+fn active_slot_name(save: &[u8]) -> Option<String> {
+    let save_file = SaveFile::read_bytes(save).ok()?;
+    Some(save_file.load_str(ACTIVE_SLOT_KEY).ok()?.into())
+}
+
+/// Slot name of a save, or None if it can't be read
+fn slot_name(save: &[u8]) -> Option<String> {
+    let save_file = SaveFile::read_bytes(save).ok()?;
+    Some(
+        save_file
+            .load_str(SAVE_SLOT_KEY)
+            .unwrap_or(DEFAULT_SLOT_NAME)
+            .into(),
+    )
+}
+
+/// Moves saves into another slot. Saves that can't be read are dropped.
+pub fn rename_saves(data: &[u8], name: &str) -> Result<Vec<u8>, Error> {
+    let mut ret: Vec<u8> = vec![];
+
+    for save in split_saves(data) {
+        let Ok(mut save_file) = SaveFile::read_bytes(save) else {
+            continue;
+        };
+
+        save_file.entries.remove(SAVE_SLOT_KEY);
+        save_file.save_str(SAVE_SLOT_KEY, name);
+        save_file.write_file(&mut ret)?;
+    }
+
+    Ok(ret)
+}
+
+/// The newest save that can be read, as the only save of another slot with its own trade id.
+/// Empty if there's no save to copy.
+fn fork_saves(data: &[u8], name: &str, save_id: u64) -> Result<Vec<u8>, Error> {
+    let saves = split_saves(data);
+    let newest = (0..saves.len())
+        .filter_map(|i| read_save(&saves, i).ok())
+        .find(|save_file| !save_file.load_bool(SNAPSHOT_KEY).unwrap_or(false));
+
+    let mut ret: Vec<u8> = vec![];
+    if let Some(mut save_file) = newest {
+        save_file.entries.remove(SAVE_SLOT_KEY);
+        save_file.save_str(SAVE_SLOT_KEY, name);
+        TradeLog::fork_save(&mut save_file, save_id)?;
+        save_file.write_file(&mut ret)?;
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::testing_infra::*;
+
+    fn save(slot: Option<&str>, gold: i64) -> Vec<u8> {
+        let mut save_file = SaveFile::new();
+        save_file.save_i64(".g", gold);
+        if let Some(slot) = slot {
+            save_file.save_str(SAVE_SLOT_KEY, slot);
+        }
+
+        let mut ret: Vec<u8> = vec![];
+        save_file.write_file(&mut ret).unwrap();
+        ret
+    }
+
+    /// A save with an extra key, like the delta and snapshot markers
+    fn marked(slot: &str, gold: i64, key: &str) -> Vec<u8> {
+        let mut save_file = SaveFile::read_bytes(&save(Some(slot), gold)).unwrap();
+        save_file.save_bool(key, true);
+
+        let mut ret: Vec<u8> = vec![];
+        save_file.write_file(&mut ret).unwrap();
+        ret
+    }
+
+    fn header(active: &str) -> Vec<u8> {
+        let mut save_file = SaveFile::new();
+        save_file.save_str(ACTIVE_SLOT_KEY, active);
+
+        let mut ret: Vec<u8> = vec![];
+        save_file.write_file(&mut ret).unwrap();
+        ret
+    }
+
+    fn gold(data: &[u8]) -> Vec<i64> {
+        split_saves(data)
+            .iter()
+            .map(|save| SaveFile::read_bytes(save).unwrap().load_i64(".g").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn unnamed_saves() {
+        let data = [save(None, 1), save(None, 2)].concat();
+        let slots = SaveSlots::read(&data);
+
+        assert_eq!(slots.slots.len(), 1);
+        assert_eq!(slots.active().name, DEFAULT_SLOT_NAME);
+        assert_eq!(slots.active().data, data);
+
+        let empty = SaveSlots::read(&[]);
+        assert_eq!(empty.slots.len(), 1);
+        assert!(empty.active().data.is_empty());
+    }
+
+    #[test]
+    fn read_write() {
+        let data = [
+            save(Some("Sandbox"), 1),
+            save(Some("Sandbox"), 2),
+            save(Some("Main"), 3),
+        ]
+        .concat();
+
+        let mut slots = SaveSlots::read(&data);
+        assert_eq!(slots.slots.len(), 2);
+        assert_eq!(slots.slots[0].name, "Main");
+        assert_eq!(slots.active().name, "Sandbox");
+        assert_eq!(gold(&slots.active().data), vec![1, 2]);
+
+        // The header names the active slot for the platform, then the saves are as they were
+        let written = slots.write().unwrap();
+        let first = split_saves(&written)[0];
+        assert_eq!(active_slot_name(first).unwrap(), "Sandbox");
+        assert_eq!(
+            SaveFile::read_bytes(first)
+                .unwrap()
+                .load_u64(ACTIVE_SLOT_INDEX_KEY)
+                .unwrap(),
+            1
+        );
+        assert_eq!(written[first.len()..], data[..]);
+
+        slots.active = 0;
+        let slots = SaveSlots::read(&slots.write().unwrap());
+        assert_eq!(slots.active().name, "Main");
+        assert_eq!(gold(&slots.slots[1].data), vec![1, 2]);
+
+        // With a header, the active slot doesn't have to be first
+        let data = [
+            header("Sandbox"),
+            save(Some("Main"), 3),
+            save(Some("Sandbox"), 1),
+        ]
+        .concat();
+        let slots = SaveSlots::read(&data);
+        assert_eq!(slots.slots.len(), 2);
+        assert_eq!(slots.active().name, "Sandbox");
+        assert_eq!(gold(&slots.active().data), vec![1]);
+    }
+
+    #[test]
+    fn deltas_and_snapshots_sorted() {
+        let data = [
+            marked("Main", 1, DELTA_BASE_KEY),
+            save(Some("Main"), 2),
+            marked("Main", 3, SNAPSHOT_KEY),
+            save(Some("Main"), 4),
+            marked("Main", 5, SNAPSHOT_KEY),
+        ]
+        .concat();
+
+        let slots = SaveSlots::read(&data);
+        let slot = slots.active();
+        assert_eq!(gold(&slot.delta), vec![1]);
+        assert_eq!(gold(&slot.data), vec![2, 4]);
+        assert_eq!(gold(&slot.snapshots), vec![3, 5]);
+        assert_eq!(gold(&slot.saves()), vec![1, 2, 4, 3, 5]);
+    }
+
+    #[test]
+    fn snapshots_trimmed_to_budget() {
+        let mut slots = SaveSlots::new();
+        slots.set_active_data(save(Some("Main"), 1));
+        slots.set_snapshots(
+            slots.active,
+            [
+                marked("Main", 2, SNAPSHOT_KEY),
+                marked("Main", 3, SNAPSHOT_KEY),
+            ]
+            .concat(),
+        );
+
+        let sandbox = slots.create("Sandbox").unwrap();
+        slots.set_data(sandbox, save(Some("Sandbox"), 4));
+        slots.set_snapshots(
+            sandbox,
+            [
+                marked("Sandbox", 5, SNAPSHOT_KEY),
+                marked("Sandbox", 6, SNAPSHOT_KEY),
+                marked("Sandbox", 7, SNAPSHOT_KEY),
+            ]
+            .concat(),
+        );
+
+        let snapshot_len = marked("Sandbox", 5, SNAPSHOT_KEY).len();
+        let total: usize = slots.slots.iter().map(|slot| slot.saves().len()).sum();
+
+        // Only the other slot's oldest snapshots go
+        slots.trim_snapshots(total - snapshot_len);
+        assert_eq!(gold(&slots.slots[sandbox].snapshots), vec![5, 6]);
+
+        slots.trim_snapshots(0);
+        assert!(slots.slots[sandbox].snapshots.is_empty());
+        assert_eq!(gold(&slots.active().snapshots), vec![2, 3]);
+        assert_eq!(gold(&slots.slots[sandbox].data), vec![4]);
+    }
+
+    #[test]
+    fn broken_save_stays_in_slot() {
+        let mut broken = save(Some("Sandbox"), 2);
+        let last = broken.len() - 1;
+        broken[last] ^= 0xFF;
+
+        let data = [
+            save(Some("Sandbox"), 1),
+            broken.clone(),
+            save(Some("Main"), 3),
+        ]
+        .concat();
+        let slots = SaveSlots::read(&data);
+
+        let sandbox = &slots.slots[1];
+        assert_eq!(sandbox.name, "Sandbox");
+        assert_eq!(sandbox.data, [save(Some("Sandbox"), 1), broken].concat());
+    }
+
+    #[test]
+    fn create_duplicate_delete() {
+        let mut slots = SaveSlots::new();
+        slots.set_active_data([save(Some("Main"), 1), save(Some("Main"), 2)].concat());
+
+        let sandbox = slots.create("Sandbox").unwrap();
+        assert_eq!(slots.slots[sandbox].name, "Sandbox");
+        assert_eq!(slots.active().name, "Main");
+        assert_eq!(
+            slots.slots[slots.create(" Sandbox ").unwrap()].name,
+            "Sandbox 2"
+        );
+        assert_eq!(slots.slots[slots.create("").unwrap()].name, "Slot");
+
+        // Only the newest save is copied, and it trades as a new save
+        let copy = slots.duplicate(slots.active, 77).unwrap();
+        let copy_data = &slots.slots[copy].data;
+        assert_eq!(slots.slots[copy].name, "Main copy");
+        assert_eq!(gold(copy_data), vec![1]);
+        assert_eq!(slot_name(copy_data).unwrap(), "Main copy");
+        assert_eq!(
+            SaveFile::read_bytes(copy_data)
+                .unwrap()
+                .load_u64("tr.id")
+                .unwrap(),
+            77
+        );
+        assert!(matches!(slots.create("Full"), Err(Error::SaveSlotLimit)));
+
+        assert!(matches!(
+            slots.delete(slots.active),
+            Err(Error::DeleteActiveSaveSlot)
+        ));
+        assert!(matches!(slots.delete(99), Err(Error::UnknownSaveSlot(99))));
+
+        // Deleting a slot before the active one keeps the same slot active
+        slots.active = slots
+            .slots
+            .iter()
+            .position(|s| s.name == "Sandbox")
+            .unwrap();
+        let sandbox = slots.active;
+        slots.delete(0).unwrap();
+        assert_eq!(slots.active, sandbox - 1);
+        assert_eq!(slots.active().name, "Sandbox");
+    }
+
+    #[test]
+    fn names_index() {
+        let platform_api = windows_plaform_api();
+
+        let mut slots = SaveSlots::new();
+        slots.set_active_data(save(Some("Main"), 1));
+        slots.create("Sandbox").unwrap();
+        slots.create("Empty").unwrap();
+        slots.active = slots.slots.iter().position(|s| s.name == "Main").unwrap();
+        slots.store_names(&platform_api);
+        assert_eq!(
+            (platform_api.local_persist_get)(SAVE_SLOTS_KEY).unwrap(),
+            "Main\nEmpty\nSandbox"
+        );
+
+        // Slots without saves aren't in the save data, only in the names
+        let mut read = SaveSlots::read(&slots.write().unwrap());
+        assert_eq!(read.slots.len(), 1);
+        read.fetch_names(&platform_api);
+        assert_eq!(read.slots.len(), 3);
+        assert_eq!(read.active().name, "Main");
+        assert_eq!(gold(&read.active().data), vec![1]);
+    }
+}
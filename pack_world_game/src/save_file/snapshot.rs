@@ -0,0 +1,179 @@
+use crate::{error::*, save_file::*};
+
+/// Marks a save as a snapshot. Snapshots are never loaded unless the player restores one.
+pub const SNAPSHOT_KEY: &str = "snapshot";
+
+const HOUR_MS: f64 = 60.0 * 60.0 * 1000.0;
+const DAY_MS: f64 = 24.0 * HOUR_MS;
+
+/// Time between snapshots
+pub const SNAPSHOT_INTERVAL_MS: f64 = HOUR_MS;
+
+/// Every snapshot younger than this is kept
+pub const HOURLY_SNAPSHOTS_MS: f64 = DAY_MS;
+
+/// Past the hourly ones, one snapshot a day is kept for this long
+pub const DAILY_SNAPSHOTS_MS: f64 = 7.0 * DAY_MS;
+
+/// An old copy of the game the player can go back to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// One save with the snapshot marker
+    pub data: Vec<u8>,
+    pub preview: SavePreview,
+}
+
+impl Snapshot {
+    pub fn take(save_file: &SaveFile) -> Result<Self, Error> {
+        let preview = SavePreview::from_save_file(save_file)?;
+
+        let mut save_file = save_file.clone();
+        save_file.entries.remove(SNAPSHOT_KEY);
+        save_file.save_bool(SNAPSHOT_KEY, true);
+
+        let mut data: Vec<u8> = vec![];
+        save_file.write_file(&mut data)?;
+
+        Ok(Self { data, preview })
+    }
+
+    /// Errors if the save isn't a snapshot
+    pub fn read(data: &[u8]) -> Result<Self, Error> {
+        let mut save_file = SaveFile::read_bytes(data)?;
+        if !save_file.load_bool(SNAPSHOT_KEY).unwrap_or(false) {
+            return Err(Error::NotASnapshot);
+        }
+
+        migrate(&mut save_file)?;
+        Ok(Self {
+            data: data.to_vec(),
+            preview: SavePreview::from_save_file(&save_file)?,
+        })
+    }
+
+    /// The snapshot as a normal save, saved at time_now.
+    /// Otherwise loading would hand out offline progress for all the time since the snapshot.
+    pub fn restore(&self, time_now: f64) -> Result<Vec<u8>, Error> {
+        let mut save_file = SaveFile::read_bytes(&self.data)?;
+
+        save_file.entries.remove(SNAPSHOT_KEY);
+        save_file.entries.remove("unix_time_saved");
+        save_file.save_f64("unix_time_saved", time_now);
+
+        let mut ret: Vec<u8> = vec![];
+        save_file.write_file(&mut ret)?;
+        Ok(ret)
+    }
+}
+
+/// Snapshots are newest first
+pub fn snapshot_due(snapshots: &[Snapshot], time_now: f64) -> bool {
+    match snapshots.first() {
+        Some(newest) => {
+            let elapsed = time_now - newest.preview.unix_time_saved;
+
+            // A clock that went backwards would otherwise stop snapshots until it caught up
+            !(0.0..SNAPSHOT_INTERVAL_MS).contains(&elapsed)
+        }
+        None => true,
+    }
+}
+
+/// Keeps every snapshot from the last day, then the newest of each day for a week
+pub fn prune_snapshots(snapshots: &mut Vec<Snapshot>, time_now: f64) {
+    snapshots.sort_by(|a, b| {
+        b.preview
+            .unix_time_saved
+            .total_cmp(&a.preview.unix_time_saved)
+    });
+
+    let mut days_kept: Vec<i64> = vec![];
+    snapshots.retain(|snapshot| {
+        let time_saved = snapshot.preview.unix_time_saved;
+        let age = time_now - time_saved;
+
+        if age < HOURLY_SNAPSHOTS_MS {
+            return true;
+        }
+        if age >= DAILY_SNAPSHOTS_MS {
+            return false;
+        }
+
+        let day = (time_saved / DAY_MS).floor() as i64;
+        if days_kept.contains(&day) {
+            return false;
+        }
+        days_kept.push(day);
+        true
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn save_file(gold: i64, time: f64) -> SaveFile {
+        let mut save_file = SaveFile::new();
+        save_file.save_i32(SAVE_VERSION_KEY, SAVE_VERSION);
+        save_file.save_i64(".g", gold);
+        save_file.save_u64(&format!("{}.0", TILE_INSTANCE_ID_CHAR), 0);
+        save_file.save_f64("unix_time_saved", time);
+        save_file
+    }
+
+    fn times(snapshots: &[Snapshot]) -> Vec<f64> {
+        snapshots
+            .iter()
+            .map(|snapshot| snapshot.preview.unix_time_saved)
+            .collect()
+    }
+
+    #[test]
+    fn take_read_restore() {
+        let snapshot = Snapshot::take(&save_file(120, 1000.0)).unwrap();
+        assert_eq!(snapshot.preview.gold, 120);
+        assert_eq!(snapshot.preview.tile_count, 1);
+        assert_eq!(Snapshot::read(&snapshot.data).unwrap(), snapshot);
+
+        let mut normal: Vec<u8> = vec![];
+        save_file(120, 1000.0).write_file(&mut normal).unwrap();
+        assert!(matches!(Snapshot::read(&normal), Err(Error::NotASnapshot)));
+
+        let restored = SaveFile::read_bytes(&snapshot.restore(5000.0).unwrap()).unwrap();
+        assert!(!restored.entries.contains_key(SNAPSHOT_KEY));
+        assert_eq!(restored.load_f64("unix_time_saved").unwrap(), 5000.0);
+        assert_eq!(restored.load_i64(".g").unwrap(), 120);
+    }
+
+    #[test]
+    fn due() {
+        let mut snapshots: Vec<Snapshot> = vec![];
+        assert!(snapshot_due(&snapshots, 0.0));
+
+        snapshots.push(Snapshot::take(&save_file(0, 10.0 * HOUR_MS)).unwrap());
+        assert!(!snapshot_due(&snapshots, 10.5 * HOUR_MS));
+        assert!(snapshot_due(&snapshots, 11.0 * HOUR_MS));
+        assert!(snapshot_due(&snapshots, 9.0 * HOUR_MS));
+    }
+
+    #[test]
+    fn prune() {
+        // One every hour for ten days
+        let now = 20.0 * DAY_MS;
+        let mut snapshots: Vec<Snapshot> = (0..10 * 24)
+            .map(|h| Snapshot::take(&save_file(0, now - h as f64 * HOUR_MS)).unwrap())
+            .rev()
+            .collect();
+
+        prune_snapshots(&mut snapshots, now);
+        let kept = times(&snapshots);
+
+        // Newest first, 24 hourly then the newest of each of the 7 days before
+        assert_eq!(kept.len(), 24 + 7);
+        assert!(kept.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(kept[24], now - 24.0 * HOUR_MS);
+        assert_eq!(kept[25], now - 25.0 * HOUR_MS);
+        assert_eq!(kept[26], now - 49.0 * HOUR_MS);
+        assert!(kept.iter().all(|time| now - time < DAILY_SNAPSHOTS_MS));
+    }
+}
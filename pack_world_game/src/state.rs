@@ -3,7 +3,9 @@ use crate::{
     game_mode::*,
     harvest_drop::*,
     pack_shop_display::*,
+    save_file::*,
     tile::*,
+    toast::*,
     ui_panels::{debug_panel::*, *},
     user_account::*,
     world::*,
@@ -21,10 +23,27 @@ use elara_engine::{
 use std::collections::HashMap;
 
 pub mod assets;
+pub mod collection_log;
 pub mod inventory;
+pub mod market;
+pub mod player_state;
+pub mod player_stats;
 
 use assets::*;
 use inventory::*;
+use player_state::*;
+
+/// A text file for the player to keep
+#[derive(Debug, Clone)]
+pub struct Download {
+    pub file_name: String,
+    pub contents: String,
+}
+
+/// Only the web runner takes downloads and hands them to the player. Elsewhere nothing would ever drain them.
+pub fn platform_has_downloads() -> bool {
+    cfg!(target_arch = "wasm32")
+}
 
 pub struct DebugState {
     pub showing_debug_panel: bool,
@@ -50,8 +69,10 @@ pub struct State {
     // these things need to be saved and loaded between runs
     pub inventory: Inventory,
     pub world: World,
+    pub player_state: PlayerState,
 
     pub harvest_drops: Vec<HarvestDrop>,
+    pub toasts: Vec<Toast>,
 
     pub ui_context: Option<UIContext>,
 
@@ -64,6 +85,11 @@ pub struct State {
 
     pub save_queued: bool,
     pub save_timer_check: f64,
+    pub save_history: SaveHistory,
+    pub save_slots: SaveSlots,
+
+    /// File waiting for the platform to take it and hand to the player
+    pub download: Option<Download>,
 }
 
 impl State {
@@ -82,6 +108,7 @@ impl State {
             assets: Assets::new(),
 
             harvest_drops: vec![],
+            toasts: vec![],
 
             font_style_body: Default::default(),
             font_style_header: Default::default(),
@@ -91,6 +118,7 @@ impl State {
 
             world: World::new(),
             inventory: Inventory::new(),
+            player_state: PlayerState::new(),
 
             ui_context: None,
 
@@ -103,6 +131,9 @@ impl State {
 
             save_queued: false,
             save_timer_check: 0.0,
+            save_history: SaveHistory::new(),
+            save_slots: SaveSlots::new(),
+            download: None,
         }
     }
 }
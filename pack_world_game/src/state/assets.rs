@@ -187,6 +187,10 @@ impl Assets {
             ItemType::FrogLeg => return self.image_frog_leg.gl_id,
             ItemType::Root => return self.image_root.gl_id,
 
+            ItemType::PotionBounty | ItemType::PotionHaste | ItemType::PotionRipen => {
+                return self.image_question_mark.gl_id
+            }
+
             ItemType::Tile(tile_type) => return self.get_tile_thumbnail(tile_type),
         };
     }
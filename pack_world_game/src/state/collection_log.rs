@@ -0,0 +1,224 @@
+use crate::{drop_table::*, error::*, item::*, pack::*, save_file::*};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollectionEntry {
+    /// Epoch ms
+    pub first_time: f64,
+    pub first_source: DropSource,
+
+    /// Total ever obtained
+    pub total: i64,
+
+    /// Lowest chance this item was ever pulled at. None if it has never been pulled from a pack.
+    pub rarest_pull: Option<f64>,
+}
+
+/// Permanent record of every item the player has gotten
+#[derive(Debug)]
+pub struct CollectionLog {
+    pub entries: HashMap<ItemType, CollectionEntry>,
+}
+
+impl CollectionLog {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn get(&self, item_type: &ItemType) -> Option<&CollectionEntry> {
+        self.entries.get(item_type)
+    }
+
+    pub fn record(&mut self, drop: Drop, source: DropSource, time: f64) {
+        let DropType::Item { item_type } = drop.drop_type else {
+            return;
+        };
+        if drop.amount <= 0 {
+            return;
+        }
+
+        let chance = source.pull_chance(drop.drop_type);
+
+        let entry = self.entries.entry(item_type).or_insert(CollectionEntry {
+            first_time: time,
+            first_source: source,
+            total: 0,
+            rarest_pull: None,
+        });
+
+        entry.total += drop.amount;
+
+        if let Some(chance) = chance {
+            entry.rarest_pull = Some(match entry.rarest_pull {
+                Some(rarest) => rarest.min(chance),
+                None => chance,
+            });
+        }
+    }
+
+    /// Returns (collected, total) of the unique items a pack can drop
+    pub fn pack_completion(&self, pack_id: PackID) -> (usize, usize) {
+        let mut items: Vec<ItemType> = vec![];
+
+        let table = get_fixed_table(pack_id.get_pack_info().table_id);
+        for drop in table.list_drops() {
+            if let DropType::Item { item_type } = drop.drop_type {
+                if !items.contains(&item_type) {
+                    items.push(item_type);
+                }
+            }
+        }
+
+        let collected = items
+            .iter()
+            .filter(|item_type| self.entries.contains_key(item_type))
+            .count();
+
+        (collected, items.len())
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        for (i, (item_type, entry)) in self.entries.iter().enumerate() {
+            let index_key = format!("{}col_index.{}", key_parent, i);
+            save_file.save_i64(&index_key, i as i64);
+
+            let key = format!("{}col.{}", key_parent, i);
+            item_type.save_file_write(format!("{}.it", key), save_file)?;
+            entry
+                .first_source
+                .save_file_write(format!("{}.s", key), save_file)?;
+            save_file.save_f64(&format!("{}.ft", key), entry.first_time);
+            save_file.save_i64(&format!("{}.n", key), entry.total);
+            if let Some(rarest) = entry.rarest_pull {
+                save_file.save_f64(&format!("{}.r", key), rarest);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        let index_prefix = format!("{}col_index", key_parent);
+        for (key, value) in &save_file.entries {
+            let parts: Vec<&str> = key.split('.').collect();
+            if parts[0] == index_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}col.{}", key_parent, i);
+
+                let item_type = ItemType::save_file_load(format!("{}.it", key), save_file)?;
+                let entry = CollectionEntry {
+                    first_source: DropSource::save_file_load(format!("{}.s", key), save_file)?,
+                    first_time: save_file.load_f64(&format!("{}.ft", key))?,
+                    total: save_file.load_i64(&format!("{}.n", key))?,
+                    rarest_pull: save_file.load_f64(&format!("{}.r", key)).ok(),
+                };
+
+                ret.entries.insert(item_type, entry);
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tile::*;
+
+    #[test]
+    fn record() {
+        let mut log = CollectionLog::new();
+
+        log.record(Drop::new_gold(10), DropSource::Other, 1.0);
+        assert_eq!(log.entries.len(), 0);
+
+        log.record(
+            Drop::new_item(ItemType::Stick, 2),
+            DropSource::Tile(TileType::Shrub),
+            5.0,
+        );
+        log.record(
+            Drop::new_item(ItemType::Stick, 3),
+            DropSource::Pack(PackID::Starter),
+            10.0,
+        );
+
+        let entry = log.get(&ItemType::Stick).unwrap();
+        assert_eq!(entry.first_time, 5.0);
+        assert_eq!(entry.first_source, DropSource::Tile(TileType::Shrub));
+        assert_eq!(entry.total, 5);
+        assert!(entry.rarest_pull.is_some());
+    }
+
+    #[test]
+    fn rarest_pull_keeps_lowest() {
+        let mut log = CollectionLog::new();
+        let drop = Drop::new_tile(TileType::Grass, 1);
+
+        log.record(drop, DropSource::Pack(PackID::Starter), 0.0);
+        let starter_chance = log
+            .get(&ItemType::Tile(TileType::Grass))
+            .unwrap()
+            .rarest_pull;
+
+        log.record(drop, DropSource::Tile(TileType::Dirt), 0.0);
+        assert_eq!(
+            log.get(&ItemType::Tile(TileType::Grass))
+                .unwrap()
+                .rarest_pull,
+            starter_chance
+        );
+    }
+
+    #[test]
+    fn pack_completion() {
+        let mut log = CollectionLog::new();
+
+        let (collected, total) = log.pack_completion(PackID::Starter);
+        assert_eq!(collected, 0);
+        assert!(total > 0);
+
+        let first = get_fixed_table(PackID::Starter.get_pack_info().table_id)
+            .list_drops()
+            .into_iter()
+            .find(|drop| drop.drop_type != DropType::Gold)
+            .unwrap();
+        log.record(first, DropSource::Other, 0.0);
+
+        assert_eq!(log.pack_completion(PackID::Starter).0, 1);
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut log = CollectionLog::new();
+        log.record(
+            Drop::new_item(ItemType::Pearl, 1),
+            DropSource::Pack(PackID::Water),
+            100.0,
+        );
+        log.record(
+            Drop::new_tile(TileType::OakTree, 1),
+            DropSource::Tile(TileType::Cave),
+            200.0,
+        );
+        log.save_file_write("".into(), &mut save_file).unwrap();
+
+        let loaded = CollectionLog::save_file_load("".into(), &save_file).unwrap();
+        assert_eq!(loaded.entries, log.entries);
+    }
+}
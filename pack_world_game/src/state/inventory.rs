@@ -4,6 +4,10 @@ use std::collections::HashMap;
 pub struct Inventory {
     pub items_seen: HashMap<ItemType, bool>,
     pub items: HashMap<ItemType, i64>,
+
+    /// Protected items can't be sold
+    pub protected: HashMap<ItemType, bool>,
+
     pub gold: i64,
     pub limit: usize,
 }
@@ -13,6 +17,7 @@ impl Inventory {
         Self {
             items: HashMap::new(),
             items_seen: HashMap::new(),
+            protected: HashMap::new(),
             limit: BANK_LIMIT_START,
             gold: 0,
         }
@@ -23,6 +28,7 @@ impl Inventory {
         self.limit = BANK_LIMIT_START;
         self.items_seen.clear();
         self.items.clear();
+        self.protected.clear();
     }
 
     pub fn drop_seen(&self, drop: &Drop) -> bool {
@@ -82,6 +88,38 @@ impl Inventory {
         return c >= count;
     }
 
+    pub fn is_protected(&self, item_type: ItemType) -> bool {
+        *self.protected.get(&item_type).unwrap_or(&false)
+    }
+
+    pub fn set_protected(&mut self, item_type: ItemType, protected: bool) {
+        if protected {
+            self.protected.insert(item_type, true);
+        } else {
+            self.protected.remove(&item_type);
+        }
+    }
+
+    /// Sell items to the merchant for a total price
+    pub fn sell_item(&mut self, item_type: ItemType, count: i64, price: i64) -> Result<(), Error> {
+        if self.is_protected(item_type) {
+            return Err(Error::ItemProtected);
+        }
+        if count <= 0 || !self.has_atleast(item_type, count) {
+            return Err(Error::NotEnoughItems);
+        }
+
+        // Checked before anything changes, so a failed sale keeps the items
+        let Some(new_gold) = self.gold.checked_add(price).filter(|gold| *gold >= 0) else {
+            return Err(Error::NegativeItemCount);
+        };
+
+        self.give_item(item_type, -count)?;
+        self.gold = new_gold;
+
+        Ok(())
+    }
+
     pub fn at_limit(&self) -> bool {
         return self.items.len() >= self.limit;
     }
@@ -101,6 +139,13 @@ impl Inventory {
         all_tiles
     }
 
+    pub fn get_all_potions(&self) -> Vec<(&ItemType, &i64)> {
+        self.items
+            .iter()
+            .filter(|(item_type, count)| item_type.potion_effect().is_some() && **count > 0)
+            .collect()
+    }
+
     pub fn save_file_write(
         &self,
         key_parent: String,
@@ -122,6 +167,11 @@ impl Inventory {
             save_file.save_i64(&count_key, *value);
         }
 
+        for (i, item_type) in self.protected.keys().enumerate() {
+            save_file.save_i64(&format!("prot_index.{}", i), i as i64);
+            item_type.save_file_write(format!("prot.{}", i), save_file)?;
+        }
+
         Ok(())
     }
 
@@ -146,6 +196,10 @@ impl Inventory {
                 let count = save_file.load_i64(&count_key)?;
 
                 inv.give_item(item, count)?;
+            } else if parts[0] == "prot_index" {
+                let index = save_file.load_i64(key)?;
+                let item = ItemType::save_file_load(format!("prot.{}", index), save_file)?;
+                inv.set_protected(item, true);
             }
         }
 
@@ -215,6 +269,7 @@ mod test {
         orig.limit = 7;
         orig.give_item(ItemType::Acorn, 123).unwrap();
         orig.give_item(ItemType::Tile(TileType::Grass), 55).unwrap();
+        orig.set_protected(ItemType::Acorn, true);
 
         orig.save_file_write("inv".into(), &mut save_file).unwrap();
 
@@ -226,5 +281,59 @@ mod test {
             *new.items.get(&ItemType::Tile(TileType::Grass)).unwrap(),
             55
         );
+        assert!(new.is_protected(ItemType::Acorn));
+        assert!(!new.is_protected(ItemType::Tile(TileType::Grass)));
+    }
+
+    #[test]
+    fn sell_item() {
+        let mut inv = Inventory::new();
+        inv.give_item(ItemType::OldBoot, 5).unwrap();
+
+        inv.sell_item(ItemType::OldBoot, 3, 9).unwrap();
+        assert_eq!(inv.gold, 9);
+        assert_eq!(*inv.items.get(&ItemType::OldBoot).unwrap(), 2);
+
+        assert!(matches!(
+            inv.sell_item(ItemType::OldBoot, 3, 9),
+            Err(Error::NotEnoughItems)
+        ));
+        assert!(matches!(
+            inv.sell_item(ItemType::OldBoot, 0, 0),
+            Err(Error::NotEnoughItems)
+        ));
+        assert_eq!(*inv.items.get(&ItemType::OldBoot).unwrap(), 2);
+    }
+
+    #[test]
+    fn sell_protected() {
+        let mut inv = Inventory::new();
+        inv.give_item(ItemType::DragonEgg, 1).unwrap();
+        inv.set_protected(ItemType::DragonEgg, true);
+
+        assert!(matches!(
+            inv.sell_item(ItemType::DragonEgg, 1, 500),
+            Err(Error::ItemProtected)
+        ));
+        assert!(inv.has_atleast(ItemType::DragonEgg, 1));
+        assert_eq!(inv.gold, 0);
+
+        inv.set_protected(ItemType::DragonEgg, false);
+        assert!(inv.sell_item(ItemType::DragonEgg, 1, 500).is_ok());
+    }
+
+    #[test]
+    fn sell_failed_keeps_items() {
+        let mut inv = Inventory::new();
+        inv.give_item(ItemType::OldBoot, 2).unwrap();
+        inv.give_gold(10).unwrap();
+
+        assert!(inv.sell_item(ItemType::OldBoot, 1, -20).is_err());
+        assert_eq!(*inv.items.get(&ItemType::OldBoot).unwrap(), 2);
+        assert_eq!(inv.gold, 10);
+
+        inv.gold = i64::MAX;
+        assert!(inv.sell_item(ItemType::OldBoot, 1, 5).is_err());
+        assert_eq!(*inv.items.get(&ItemType::OldBoot).unwrap(), 2);
     }
 }
@@ -0,0 +1,395 @@
+use crate::{drop_table::*, error::*, item::*, save_file::*};
+use elara_engine::platform_api::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Length of one market day in market time
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Time for half of an item's supply pressure to recover
+const PRESSURE_HALF_LIFE_MS: f64 = 6.0 * 60.0 * 60.0 * 1000.0;
+
+/// Supply pressure at which an item sells for half its price
+const PRESSURE_HALF_PRICE: f64 = 50.0;
+
+/// Time between price history samples
+const HISTORY_INTERVAL_MS: f64 = 60.0 * 60.0 * 1000.0;
+
+/// Number of price history samples kept per item
+pub const HISTORY_LEN: usize = 24;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MarketEventKind {
+    Boom,
+    Slump,
+}
+
+/// A price change on one item for a whole market day
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MarketEvent {
+    pub kind: MarketEventKind,
+    pub item_type: ItemType,
+}
+
+impl MarketEvent {
+    pub fn price_mult(&self) -> f64 {
+        match self.kind {
+            MarketEventKind::Boom => 2.0,
+            MarketEventKind::Slump => 0.5,
+        }
+    }
+
+    pub fn display(&self) -> String {
+        match self.kind {
+            MarketEventKind::Boom => format!("{} prices are booming!", self.item_type.user_title()),
+            MarketEventKind::Slump => {
+                format!(
+                    "The market is flooded with {}.",
+                    self.item_type.user_title()
+                )
+            }
+        }
+    }
+}
+
+/// Sell prices that drop as the player sells and recover over time.
+/// Prices only depend on the seed, market time, and sales, so the same inputs always give the same prices.
+#[derive(Debug)]
+pub struct Market {
+    pub seed: u64,
+
+    /// Ms of market time. Advances with world simulation, including offline.
+    pub elapsed_ms: f64,
+
+    pressure: HashMap<ItemType, f64>,
+
+    history: HashMap<ItemType, VecDeque<i64>>,
+}
+
+impl Market {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            elapsed_ms: 0.0,
+            pressure: HashMap::new(),
+            history: HashMap::new(),
+        }
+    }
+
+    pub fn new_seed(platform_api: &PlatformApi) -> u64 {
+        let rand = ((platform_api.rand)() * u32::MAX as f64) as u64;
+        (((platform_api.epoch_time_ms)() as u64) << 20) ^ rand
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new(0);
+    }
+
+    pub fn day(&self) -> i64 {
+        (self.elapsed_ms / DAY_MS).floor() as i64
+    }
+
+    /// Half of the days have an event on one random item
+    pub fn event_for_day(&self, day: i64) -> Option<MarketEvent> {
+        let roll = hash(self.seed ^ hash(day as u64));
+        if roll % 2 == 0 {
+            return None;
+        }
+
+        let kind = if (roll >> 1) % 2 == 0 {
+            MarketEventKind::Boom
+        } else {
+            MarketEventKind::Slump
+        };
+        let item_types = obtainable_item_types();
+        let item_type = item_types[((roll >> 2) % item_types.len() as u64) as usize];
+
+        Some(MarketEvent { kind, item_type })
+    }
+
+    pub fn todays_event(&self) -> Option<MarketEvent> {
+        self.event_for_day(self.day())
+    }
+
+    pub fn pressure(&self, item_type: ItemType) -> f64 {
+        *self.pressure.get(&item_type).unwrap_or(&0.0)
+    }
+
+    /// Current price for one item
+    pub fn price(&self, item_type: ItemType) -> i64 {
+        self.price_at_pressure(item_type, self.pressure(item_type))
+    }
+
+    fn price_at_pressure(&self, item_type: ItemType, pressure: f64) -> i64 {
+        let mut price = item_type.sell_value() as f64 / (1.0 + (pressure / PRESSURE_HALF_PRICE));
+
+        if let Some(event) = self.todays_event() {
+            if event.item_type == item_type {
+                price *= event.price_mult();
+            }
+        }
+
+        (price.round() as i64).max(1)
+    }
+
+    /// Total gold for selling count items. Each item sold lowers the price of the next.
+    pub fn quote(&self, item_type: ItemType, count: i64) -> i64 {
+        let pressure = self.pressure(item_type);
+        (0..count)
+            .map(|i| self.price_at_pressure(item_type, pressure + i as f64))
+            .sum()
+    }
+
+    pub fn record_sale(&mut self, item_type: ItemType, count: i64) {
+        *self.pressure.entry(item_type).or_insert(0.0) += count as f64;
+    }
+
+    pub fn update(&mut self, ms: f64) {
+        let mut remaining = ms;
+
+        // Step to each history sample so the samples don't depend on the frame length
+        while remaining > 0.0 {
+            let next_sample =
+                ((self.elapsed_ms / HISTORY_INTERVAL_MS).floor() + 1.0) * HISTORY_INTERVAL_MS;
+            let gap = next_sample - self.elapsed_ms;
+
+            if remaining >= gap {
+                self.recover(gap);
+                self.elapsed_ms = next_sample;
+                remaining -= gap;
+                self.sample_history();
+            } else {
+                self.recover(remaining);
+                self.elapsed_ms += remaining;
+                remaining = 0.0;
+            }
+        }
+    }
+
+    fn recover(&mut self, ms: f64) {
+        let decay = 0.5_f64.powf(ms / PRESSURE_HALF_LIFE_MS);
+        for pressure in self.pressure.values_mut() {
+            *pressure *= decay;
+        }
+        self.pressure.retain(|_, pressure| *pressure > 0.01);
+    }
+
+    fn sample_history(&mut self) {
+        for item_type in obtainable_item_types() {
+            let price = self.price(item_type);
+
+            let samples = self.history.entry(item_type).or_insert(VecDeque::new());
+            samples.push_back(price);
+            if samples.len() > HISTORY_LEN {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Oldest first
+    pub fn history(&self, item_type: ItemType) -> Vec<i64> {
+        match self.history.get(&item_type) {
+            Some(samples) => samples.iter().copied().collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        save_file.save_i64(&format!("{}mk.seed", key_parent), self.seed as i64);
+        save_file.save_f64(&format!("{}mk.t", key_parent), self.elapsed_ms);
+
+        for (i, (item_type, pressure)) in self.pressure.iter().enumerate() {
+            save_file.save_i64(&format!("{}mk_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}mk.{}", key_parent, i);
+            item_type.save_file_write(format!("{}.it", key), save_file)?;
+            save_file.save_f64(&format!("{}.p", key), *pressure);
+        }
+
+        for (i, (item_type, samples)) in self.history.iter().enumerate() {
+            save_file.save_i64(&format!("{}mkh_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}mkh.{}", key_parent, i);
+            item_type.save_file_write(format!("{}.it", key), save_file)?;
+
+            let bytes: Vec<u8> = samples
+                .iter()
+                .flat_map(|price| price.to_le_bytes())
+                .collect();
+            save_file.save_blob(&format!("{}.s", key), bytes);
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        // Saves from before the market start with a fresh one. Seed 0 gets a new seed once loaded.
+        let seed = save_file
+            .load_i64(&format!("{}mk.seed", key_parent))
+            .unwrap_or(0);
+
+        let mut ret = Self::new(seed as u64);
+        ret.elapsed_ms = save_file
+            .load_f64(&format!("{}mk.t", key_parent))
+            .unwrap_or(0.0);
+
+        let index_prefix = format!("{}mk_index", key_parent);
+        let history_prefix = format!("{}mkh_index", key_parent);
+        for (key, value) in &save_file.entries {
+            let parts: Vec<&str> = key.split('.').collect();
+            if parts[0] == index_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}mk.{}", key_parent, i);
+
+                let item_type = ItemType::save_file_load(format!("{}.it", key), save_file)?;
+                let pressure = save_file.load_f64(&format!("{}.p", key))?;
+                ret.pressure.insert(item_type, pressure);
+            } else if parts[0] == history_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}mkh.{}", key_parent, i);
+
+                let item_type = ItemType::save_file_load(format!("{}.it", key), save_file)?;
+                let samples: VecDeque<i64> = save_file
+                    .load_blob(&format!("{}.s", key))?
+                    .chunks_exact(8)
+                    .map(|bytes| i64::from_le_bytes(bytes.try_into().unwrap()))
+                    .collect();
+                ret.history.insert(item_type, samples);
+            }
+        }
+
+        Ok(ret)
+    }
+}
+
+/// splitmix64
+fn hash(input: u64) -> u64 {
+    let mut z = input.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A day with no event on the item, so only pressure moves the price
+    fn quiet_market(item_type: ItemType) -> Market {
+        let mut market = Market::new(1234);
+        while market
+            .todays_event()
+            .is_some_and(|event| event.item_type == item_type)
+        {
+            market.elapsed_ms += DAY_MS;
+        }
+        market
+    }
+
+    #[test]
+    fn selling_lowers_price() {
+        let mut market = quiet_market(ItemType::Pearl);
+        let base = market.price(ItemType::Pearl);
+        assert_eq!(base, ItemType::Pearl.sell_value());
+
+        market.record_sale(ItemType::Pearl, PRESSURE_HALF_PRICE as i64);
+        assert_eq!(market.price(ItemType::Pearl), base / 2);
+
+        // other items aren't effected
+        assert_eq!(
+            market.price(ItemType::Baby),
+            quiet_market(ItemType::Baby).price(ItemType::Baby)
+        );
+    }
+
+    #[test]
+    fn quote_includes_own_pressure() {
+        let market = quiet_market(ItemType::DragonEgg);
+        let single = market.quote(ItemType::DragonEgg, 1);
+
+        assert_eq!(single, market.price(ItemType::DragonEgg));
+        assert!(market.quote(ItemType::DragonEgg, 100) < single * 100);
+        assert_eq!(market.quote(ItemType::DragonEgg, 0), 0);
+    }
+
+    #[test]
+    fn pressure_recovers() {
+        let mut market = Market::new(1);
+        market.record_sale(ItemType::Rock, 100);
+
+        market.update(PRESSURE_HALF_LIFE_MS);
+        assert!((market.pressure(ItemType::Rock) - 50.0).abs() < 0.001);
+
+        market.update(PRESSURE_HALF_LIFE_MS * 20.0);
+        assert_eq!(market.pressure(ItemType::Rock), 0.0);
+    }
+
+    #[test]
+    fn deterministic() {
+        let mut one_step = Market::new(99);
+        let mut many_steps = Market::new(99);
+
+        one_step.record_sale(ItemType::OldBoot, 40);
+        many_steps.record_sale(ItemType::OldBoot, 40);
+
+        let total = DAY_MS * 3.5;
+        one_step.update(total);
+
+        let step: f64 = 1000.0 * 7.3;
+        let mut simmed = 0.0;
+        while simmed < total {
+            let ms = step.min(total - simmed);
+            many_steps.update(ms);
+            simmed += ms;
+        }
+
+        assert_eq!(one_step.day(), many_steps.day());
+        assert_eq!(one_step.todays_event(), many_steps.todays_event());
+        for item_type in obtainable_item_types() {
+            assert_eq!(one_step.price(item_type), many_steps.price(item_type));
+            assert_eq!(one_step.history(item_type), many_steps.history(item_type));
+        }
+        assert_eq!(one_step.history(ItemType::OldBoot).len(), HISTORY_LEN);
+
+        // Tiles can be sold too
+        let grass = ItemType::Tile(crate::tile::TileType::Grass);
+        assert_eq!(one_step.history(grass).len(), HISTORY_LEN);
+    }
+
+    #[test]
+    fn events_depend_on_seed() {
+        let a = Market::new(5);
+        let b = Market::new(5);
+
+        let events_a: Vec<Option<MarketEvent>> = (0..30).map(|day| a.event_for_day(day)).collect();
+        let events_b: Vec<Option<MarketEvent>> = (0..30).map(|day| b.event_for_day(day)).collect();
+        assert_eq!(events_a, events_b);
+        assert!(events_a.iter().any(|event| event.is_some()));
+        assert!(events_a.iter().any(|event| event.is_none()));
+
+        let c = Market::new(6);
+        let events_c: Vec<Option<MarketEvent>> = (0..30).map(|day| c.event_for_day(day)).collect();
+        assert_ne!(events_a, events_c);
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut market = Market::new(777);
+        market.record_sale(ItemType::Seaweed, 12);
+        market.record_sale(ItemType::Tile(crate::tile::TileType::Grass), 3);
+        market.update(HISTORY_INTERVAL_MS * 3.0 + 12345.0);
+        market.save_file_write("".into(), &mut save_file).unwrap();
+
+        let loaded = Market::save_file_load("".into(), &save_file).unwrap();
+        assert_eq!(loaded.seed, market.seed);
+        assert_eq!(loaded.elapsed_ms, market.elapsed_ms);
+        assert_eq!(loaded.pressure, market.pressure);
+        assert_eq!(loaded.history, market.history);
+        assert_eq!(loaded.history(ItemType::Seaweed).len(), 3);
+    }
+}
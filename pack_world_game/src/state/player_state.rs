@@ -1,11 +1,76 @@
-use crate::drop_table::*;
+use crate::{
+    achievement::*,
+    drop_table::*,
+    error::*,
+    pack::*,
+    quest::*,
+    save_file::*,
+    state::{collection_log::*, market::*, player_stats::*},
+    trade::*,
+};
 use std::collections::HashMap;
 
 // Player save file
-pub struct PlayerState {}
+pub struct PlayerState {
+    pub quest_log: QuestLog,
+    pub achievements: AchievementLog,
+    pub collection: CollectionLog,
+    pub stats: PlayerStats,
+    pub market: Market,
+    pub trades: TradeLog,
+    pub pity: PityCounters,
+}
 
 impl PlayerState {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            quest_log: QuestLog::new(),
+            achievements: AchievementLog::new(),
+            collection: CollectionLog::new(),
+            stats: PlayerStats::new(),
+            market: Market::new(0),
+            trades: TradeLog::new(),
+            pity: PityCounters::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.quest_log.clear();
+        self.achievements.clear();
+        self.collection.clear();
+        self.stats.clear();
+        self.market.clear();
+        self.trades.clear();
+        self.pity.clear();
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        self.quest_log
+            .save_file_write(key_parent.clone(), save_file)?;
+        self.achievements
+            .save_file_write(key_parent.clone(), save_file)?;
+        self.collection
+            .save_file_write(key_parent.clone(), save_file)?;
+        self.stats.save_file_write(key_parent.clone(), save_file)?;
+        self.market.save_file_write(key_parent.clone(), save_file)?;
+        self.trades.save_file_write(key_parent.clone(), save_file)?;
+        self.pity.save_file_write(key_parent, save_file)?;
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        Ok(Self {
+            quest_log: QuestLog::save_file_load(key_parent.clone(), save_file)?,
+            achievements: AchievementLog::save_file_load(key_parent.clone(), save_file)?,
+            collection: CollectionLog::save_file_load(key_parent.clone(), save_file)?,
+            stats: PlayerStats::save_file_load(key_parent.clone(), save_file)?,
+            market: Market::save_file_load(key_parent.clone(), save_file)?,
+            trades: TradeLog::save_file_load(key_parent.clone(), save_file)?,
+            pity: PityCounters::save_file_load(key_parent, save_file)?,
+        })
     }
 }
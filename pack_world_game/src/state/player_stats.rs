@@ -0,0 +1,230 @@
+use crate::{
+    error::*,
+    pack::{pack_id::*, *},
+    save_file::*,
+    tile::*,
+};
+use std::collections::HashMap;
+
+/// Lifetime counters. Never reset except when the save is cleared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerStats {
+    pub harvests: HashMap<TileType, i64>,
+    pub packs_opened: HashMap<PackID, i64>,
+
+    pub gold_earned: i64,
+    pub gold_spent: i64,
+
+    pub tiles_placed: i64,
+    pub tiles_destroyed: i64,
+
+    /// Seconds spent with the game open
+    pub time_played: f64,
+
+    /// Ms of world time simulated while the game was closed
+    pub offline_simulated_ms: f64,
+}
+
+impl PlayerStats {
+    pub fn new() -> Self {
+        Self {
+            harvests: HashMap::new(),
+            packs_opened: HashMap::new(),
+            gold_earned: 0,
+            gold_spent: 0,
+            tiles_placed: 0,
+            tiles_destroyed: 0,
+            time_played: 0.0,
+            offline_simulated_ms: 0.0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn record_harvest(&mut self, tile_type: TileType) {
+        *self.harvests.entry(tile_type).or_insert(0) += 1;
+    }
+
+    pub fn record_pack_opened(&mut self, pack_id: PackID) {
+        *self.packs_opened.entry(pack_id).or_insert(0) += 1;
+    }
+
+    /// Positive amounts count as earned, negative as spent
+    pub fn record_gold(&mut self, amount: i64) {
+        if amount > 0 {
+            self.gold_earned += amount;
+        } else {
+            self.gold_spent += -amount;
+        }
+    }
+
+    pub fn total_harvests(&self) -> i64 {
+        self.harvests.values().sum()
+    }
+
+    pub fn total_packs_opened(&self) -> i64 {
+        self.packs_opened.values().sum()
+    }
+
+    /// Export for the balancing spreadsheets. One `category,key,value` row per counter.
+    pub fn export_csv(&self) -> String {
+        let mut ret = String::from("category,key,value\n");
+
+        let mut harvests: Vec<(&TileType, &i64)> = self.harvests.iter().collect();
+        harvests.sort_by_key(|(tile_type, _)| tile_type.to_index());
+        for (tile_type, count) in harvests {
+            ret.push_str(&format!("harvests,{:?},{}\n", tile_type, count));
+        }
+
+        let mut packs: Vec<(&PackID, &i64)> = self.packs_opened.iter().collect();
+        packs.sort_by_key(|(pack_id, _)| pack_id.to_index());
+        for (pack_id, count) in packs {
+            ret.push_str(&format!("packs_opened,{:?},{}\n", pack_id, count));
+        }
+
+        ret.push_str(&format!("gold,earned,{}\n", self.gold_earned));
+        ret.push_str(&format!("gold,spent,{}\n", self.gold_spent));
+        ret.push_str(&format!("tiles,placed,{}\n", self.tiles_placed));
+        ret.push_str(&format!("tiles,destroyed,{}\n", self.tiles_destroyed));
+        ret.push_str(&format!(
+            "time,played_hours,{:.3}\n",
+            self.time_played / 3600.0
+        ));
+        ret.push_str(&format!(
+            "time,offline_hours,{:.3}\n",
+            self.offline_simulated_ms / 3_600_000.0
+        ));
+
+        ret
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        for (tile_type, count) in &self.harvests {
+            save_file.save_i64(
+                &format!("{}st.h.{}", key_parent, tile_type.to_index()),
+                *count,
+            );
+        }
+        for (pack_id, count) in &self.packs_opened {
+            save_file.save_i64(
+                &format!("{}st.p.{}", key_parent, pack_id.to_index()),
+                *count,
+            );
+        }
+
+        save_file.save_i64(&format!("{}st.ge", key_parent), self.gold_earned);
+        save_file.save_i64(&format!("{}st.gs", key_parent), self.gold_spent);
+        save_file.save_i64(&format!("{}st.tp", key_parent), self.tiles_placed);
+        save_file.save_i64(&format!("{}st.td", key_parent), self.tiles_destroyed);
+        save_file.save_f64(&format!("{}st.time", key_parent), self.time_played);
+        save_file.save_f64(&format!("{}st.off", key_parent), self.offline_simulated_ms);
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        // Saves from before stats existed won't have any of these keys
+        for tile_type in ALL_TILE_TYPES.iter() {
+            let key = format!("{}st.h.{}", key_parent, tile_type.to_index());
+            if let Ok(count) = save_file.load_i64(&key) {
+                ret.harvests.insert(*tile_type, count);
+            }
+        }
+        for pack_id in ALL_PACKS.iter() {
+            let key = format!("{}st.p.{}", key_parent, pack_id.to_index());
+            if let Ok(count) = save_file.load_i64(&key) {
+                ret.packs_opened.insert(*pack_id, count);
+            }
+        }
+
+        ret.gold_earned = save_file
+            .load_i64(&format!("{}st.ge", key_parent))
+            .unwrap_or(0);
+        ret.gold_spent = save_file
+            .load_i64(&format!("{}st.gs", key_parent))
+            .unwrap_or(0);
+        ret.tiles_placed = save_file
+            .load_i64(&format!("{}st.tp", key_parent))
+            .unwrap_or(0);
+        ret.tiles_destroyed = save_file
+            .load_i64(&format!("{}st.td", key_parent))
+            .unwrap_or(0);
+        ret.time_played = save_file
+            .load_f64(&format!("{}st.time", key_parent))
+            .unwrap_or(0.0);
+        ret.offline_simulated_ms = save_file
+            .load_f64(&format!("{}st.off", key_parent))
+            .unwrap_or(0.0);
+
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn record_gold() {
+        let mut stats = PlayerStats::new();
+
+        stats.record_gold(50);
+        stats.record_gold(-20);
+        stats.record_gold(0);
+
+        assert_eq!(stats.gold_earned, 50);
+        assert_eq!(stats.gold_spent, 20);
+    }
+
+    #[test]
+    fn export_csv() {
+        let mut stats = PlayerStats::new();
+        stats.record_harvest(TileType::Grass);
+        stats.record_harvest(TileType::Grass);
+        stats.record_pack_opened(PackID::Stick);
+        stats.time_played = 7200.0;
+
+        let csv = stats.export_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "category,key,value");
+        assert!(lines.contains(&"harvests,Grass,2"));
+        assert!(lines.contains(&"packs_opened,Stick,1"));
+        assert!(lines.contains(&"time,played_hours,2.000"));
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut stats = PlayerStats::new();
+        stats.record_harvest(TileType::OakTree);
+        stats.record_pack_opened(PackID::Water);
+        stats.record_gold(120);
+        stats.record_gold(-30);
+        stats.tiles_placed = 8;
+        stats.tiles_destroyed = 2;
+        stats.time_played = 600.5;
+        stats.offline_simulated_ms = 90_000.0;
+
+        stats.save_file_write("".into(), &mut save_file).unwrap();
+        let loaded = PlayerStats::save_file_load("".into(), &save_file).unwrap();
+
+        assert_eq!(loaded, stats);
+    }
+
+    #[test]
+    fn load_missing() {
+        let save_file = SaveFile::new();
+        let loaded = PlayerStats::save_file_load("".into(), &save_file).unwrap();
+        assert_eq!(loaded, PlayerStats::new());
+    }
+}
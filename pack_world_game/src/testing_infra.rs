@@ -1,7 +1,17 @@
 use elara_engine::{analytics::*, error::Error, platform_api::*};
 use rand::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+thread_local! {
+    /// Keyed storage. Each test runs on its own thread, so tests don't see each other's data.
+    pub static LOCAL_PERSIST: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+
+    /// The last save data written, like the file the windows platform keeps
+    pub static SAVE_GAME_DATA: RefCell<Vec<u8>> = RefCell::new(vec![]);
+}
+
 fn rand() -> f64 {
     rand::random_range(0.0..1.0)
 }
@@ -18,7 +28,8 @@ fn send_event(vent: AnalyticsEvent) {
 }
 
 fn write_save_game_data(data: Vec<u8>) -> Result<(), Error> {
-    unimplemented!()
+    SAVE_GAME_DATA.with(|saved| *saved.borrow_mut() = data);
+    Ok(())
 }
 
 fn get_save_game_data() {
@@ -30,15 +41,15 @@ fn open_url(url: String, same_tab: bool) {
 }
 
 fn local_persist_get(key: &str) -> Option<String> {
-    unimplemented!()
+    LOCAL_PERSIST.with(|storage| storage.borrow().get(key).cloned())
 }
 
 fn local_persist_set(key: &str, data: &str) {
-    // do nothing for now
+    LOCAL_PERSIST.with(|storage| storage.borrow_mut().insert(key.into(), data.into()));
 }
 
 fn local_persist_delete(key: &str) {
-    // do nothing for now
+    LOCAL_PERSIST.with(|storage| storage.borrow_mut().remove(key));
 }
 
 fn plat_print(output: &str) {
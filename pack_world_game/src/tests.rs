@@ -25,7 +25,7 @@ pub fn tile_instance_drop_count_conditions() {
         inst
     };
 
-    let mut mud_pit_inst = tile_mud_pit::new_instance(GridPos::new(1, 0));
+    let mut mud_pit_inst = TileType::MudPit.create_instance(GridPos::new(1, 0));
 
     // insert fish into world
     let _ = world.insert_tile_instance(GridPos::new(0, 0), fish_inst);
@@ -74,7 +74,7 @@ pub fn global_drop_count_mod() {
         inst
     };
 
-    let mut spring_inst = tile_spring::new_instance(GridPos::new(1, 0));
+    let mut spring_inst = TileType::Spring.create_instance(GridPos::new(1, 0));
 
     // insert grass into world
     let _ = world.insert_tile_instance(GridPos::new(0, 0), grass_inst);
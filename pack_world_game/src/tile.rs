@@ -28,6 +28,11 @@ pub mod tile_methods;
 pub mod tile_type;
 pub mod tiles;
 
+#[allow(non_snake_case)]
+#[rustfmt::skip]
+mod GEN_tiles;
+pub use GEN_tiles::*;
+
 pub use {tile_definition::*, tile_instance::*, tile_methods::*, tile_type::*};
 
 pub fn draw_tile_grid_pos(
@@ -0,0 +1,675 @@
+// Generated by build.rs from design/tiles.txt. Edit that instead.
+
+use crate::{
+    drop_table::*,
+    error::*,
+    grid::*,
+    item::*,
+    save_file::*,
+    tile::{tiles::*, *},
+    world::*,
+};
+use elara_engine::time::*;
+use std::sync::LazyLock;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum TileType {
+    Dirt,
+    Grass,
+    Boulder,
+    OakTree,
+    BirdNest,
+    Cave,
+    Shrub,
+    MudPit,
+    TallGrass,
+    Frog,
+    Water,
+    Newt,
+    Reed,
+    Clam,
+    MudFish,
+    Spring,
+    Kelp,
+    Crab,
+    MudHenge,
+    MudChicken,
+    Goblin,
+    MudPig,
+}
+
+pub const ALL_TILE_TYPES: LazyLock<Vec<TileType>> = LazyLock::new(|| {
+    vec![
+        TileType::Dirt,
+        TileType::Grass,
+        TileType::Boulder,
+        TileType::OakTree,
+        TileType::BirdNest,
+        TileType::Cave,
+        TileType::Shrub,
+        TileType::MudPit,
+        TileType::TallGrass,
+        TileType::Frog,
+        TileType::Water,
+        TileType::Newt,
+        TileType::Reed,
+        TileType::Clam,
+        TileType::MudFish,
+        TileType::Spring,
+        TileType::Kelp,
+        TileType::Crab,
+        TileType::MudHenge,
+        TileType::MudChicken,
+        TileType::Goblin,
+        TileType::MudPig,
+    ]
+});
+
+/// This is just manual dynamic dispatch because Dyn breaks hot realoding.
+#[derive(Debug, Clone)]
+pub enum TileMethods {
+    Dirt,
+    Grass,
+    Boulder,
+    OakTree(TileOakTree),
+    BirdNest(TileBirdNest),
+    Cave,
+    Shrub,
+    MudPit,
+    TallGrass,
+    Frog,
+    Water,
+    Newt,
+    Reed,
+    Clam,
+    MudFish,
+    Spring,
+    Kelp,
+    Crab,
+    MudHenge,
+    MudChicken,
+    Goblin,
+    MudPig,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TileSnapshot {
+    Dirt,
+    Grass,
+    Boulder,
+    OakTree { has_nest: bool },
+    BirdNest,
+    Cave,
+    Shrub,
+    MudPit,
+    TallGrass,
+    Frog,
+    Water,
+    Newt,
+    Reed,
+    Clam,
+    MudFish,
+    Spring,
+    Kelp,
+    Crab,
+    MudHenge,
+    MudChicken,
+    Goblin,
+    MudPig,
+}
+
+impl TileType {
+    pub fn get_definition(&self) -> &'static TileDefinition {
+        match self {
+            TileType::Dirt => &DIRT_DEF,
+            TileType::Grass => &GRASS_DEF,
+            TileType::Boulder => &BOULDER_DEF,
+            TileType::OakTree => &OAK_TREE_DEF,
+            TileType::BirdNest => &BIRD_NEST_DEF,
+            TileType::Cave => &CAVE_DEF,
+            TileType::Shrub => &SHRUB_DEF,
+            TileType::MudPit => &MUD_PIT_DEF,
+            TileType::TallGrass => &TALL_GRASS_DEF,
+            TileType::Frog => &FROG_DEF,
+            TileType::Water => &WATER_DEF,
+            TileType::Newt => &NEWT_DEF,
+            TileType::Reed => &REED_DEF,
+            TileType::Clam => &CLAM_DEF,
+            TileType::MudFish => &MUD_FISH_DEF,
+            TileType::Spring => &SPRING_DEF,
+            TileType::Kelp => &KELP_DEF,
+            TileType::Crab => &CRAB_DEF,
+            TileType::MudHenge => &MUD_HENGE_DEF,
+            TileType::MudChicken => &MUD_CHICKEN_DEF,
+            TileType::Goblin => &GOBLIN_DEF,
+            TileType::MudPig => &MUD_PIG_DEF,
+        }
+    }
+
+    pub fn to_index(&self) -> i32 {
+        match self {
+            Self::Dirt => 0,
+            Self::Grass => 1,
+            Self::Boulder => 2,
+            Self::OakTree => 6,
+            Self::BirdNest => 4,
+            Self::Cave => 5,
+            Self::Shrub => 3,
+            Self::MudPit => 7,
+            Self::TallGrass => 8,
+            Self::Frog => 9,
+            Self::Water => 10,
+            Self::Newt => 11,
+            Self::Reed => 12,
+            Self::Clam => 13,
+            Self::MudFish => 14,
+            Self::Spring => 15,
+            Self::Kelp => 16,
+            Self::Crab => 17,
+            Self::MudHenge => 18,
+            Self::MudChicken => 19,
+            Self::Goblin => 20,
+            Self::MudPig => 21,
+        }
+    }
+
+    pub fn from_index(idx: i32) -> Result<Self, Error> {
+        match idx {
+            0 => Ok(Self::Dirt),
+            1 => Ok(Self::Grass),
+            2 => Ok(Self::Boulder),
+            6 => Ok(Self::OakTree),
+            4 => Ok(Self::BirdNest),
+            5 => Ok(Self::Cave),
+            3 => Ok(Self::Shrub),
+            7 => Ok(Self::MudPit),
+            8 => Ok(Self::TallGrass),
+            9 => Ok(Self::Frog),
+            10 => Ok(Self::Water),
+            11 => Ok(Self::Newt),
+            12 => Ok(Self::Reed),
+            13 => Ok(Self::Clam),
+            14 => Ok(Self::MudFish),
+            15 => Ok(Self::Spring),
+            16 => Ok(Self::Kelp),
+            17 => Ok(Self::Crab),
+            18 => Ok(Self::MudHenge),
+            19 => Ok(Self::MudChicken),
+            20 => Ok(Self::Goblin),
+            21 => Ok(Self::MudPig),
+            _ => Err(Error::InvalidTileTypeIndex(idx)),
+        }
+    }
+}
+
+impl TileMethods {
+    pub fn to_index(&self) -> i32 {
+        match self {
+            TileMethods::Dirt => 1,
+            TileMethods::Grass => 2,
+            TileMethods::Boulder => 3,
+            TileMethods::OakTree(_) => 4,
+            TileMethods::BirdNest(_) => 5,
+            TileMethods::Cave => 6,
+            TileMethods::Shrub => 7,
+            TileMethods::MudPit => 8,
+            TileMethods::TallGrass => 9,
+            TileMethods::Frog => 10,
+            TileMethods::Water => 11,
+            TileMethods::Newt => 12,
+            TileMethods::Reed => 13,
+            TileMethods::Clam => 14,
+            TileMethods::MudFish => 15,
+            TileMethods::Spring => 16,
+            TileMethods::Kelp => 17,
+            TileMethods::Crab => 18,
+            TileMethods::MudHenge => 19,
+            TileMethods::MudChicken => 20,
+            TileMethods::Goblin => 21,
+            TileMethods::MudPig => 22,
+        }
+    }
+
+    /// Only custom tiles have state to save
+    pub fn save_state(&self, state_key: String, save_file: &mut SaveFile) -> Result<(), Error> {
+        match self {
+            TileMethods::OakTree(state) => state.save_file_write(state_key, save_file),
+            TileMethods::BirdNest(state) => state.save_file_write(state_key, save_file),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn load_state(index: i32, state_key: String, save_file: &SaveFile) -> Result<Self, Error> {
+        match index {
+            1 => Ok(Self::Dirt),
+            2 => Ok(Self::Grass),
+            3 => Ok(Self::Boulder),
+            4 => TileOakTree::save_file_load(state_key, save_file),
+            5 => TileBirdNest::save_file_load(state_key, save_file),
+            6 => Ok(Self::Cave),
+            7 => Ok(Self::Shrub),
+            8 => Ok(Self::MudPit),
+            9 => Ok(Self::TallGrass),
+            10 => Ok(Self::Frog),
+            11 => Ok(Self::Water),
+            12 => Ok(Self::Newt),
+            13 => Ok(Self::Reed),
+            14 => Ok(Self::Clam),
+            15 => Ok(Self::MudFish),
+            16 => Ok(Self::Spring),
+            17 => Ok(Self::Kelp),
+            18 => Ok(Self::Crab),
+            19 => Ok(Self::MudHenge),
+            20 => Ok(Self::MudChicken),
+            21 => Ok(Self::Goblin),
+            22 => Ok(Self::MudPig),
+            _ => Err(Error::UnknownTileMethodID(index)),
+        }
+    }
+
+    pub fn into_snapshot(&self) -> TileSnapshot {
+        match self {
+            TileMethods::Dirt => TileSnapshot::Dirt,
+            TileMethods::Grass => TileSnapshot::Grass,
+            TileMethods::Boulder => TileSnapshot::Boulder,
+            TileMethods::OakTree(state) => state.snapshot(),
+            TileMethods::BirdNest(_) => TileSnapshot::BirdNest,
+            TileMethods::Cave => TileSnapshot::Cave,
+            TileMethods::Shrub => TileSnapshot::Shrub,
+            TileMethods::MudPit => TileSnapshot::MudPit,
+            TileMethods::TallGrass => TileSnapshot::TallGrass,
+            TileMethods::Frog => TileSnapshot::Frog,
+            TileMethods::Water => TileSnapshot::Water,
+            TileMethods::Newt => TileSnapshot::Newt,
+            TileMethods::Reed => TileSnapshot::Reed,
+            TileMethods::Clam => TileSnapshot::Clam,
+            TileMethods::MudFish => TileSnapshot::MudFish,
+            TileMethods::Spring => TileSnapshot::Spring,
+            TileMethods::Kelp => TileSnapshot::Kelp,
+            TileMethods::Crab => TileSnapshot::Crab,
+            TileMethods::MudHenge => TileSnapshot::MudHenge,
+            TileMethods::MudChicken => TileSnapshot::MudChicken,
+            TileMethods::Goblin => TileSnapshot::Goblin,
+            TileMethods::MudPig => TileSnapshot::MudPig,
+        }
+    }
+}
+
+pub static DIRT_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Dirt",
+    description: "Placed on empty space. Creates ground for other tiles.",
+    world_layer: WorldLayer::Ground,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::ValidPosition()],
+    placement_global_mod: vec![],
+    new_instance: new_dirt,
+});
+
+fn new_dirt(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::Dirt, grid_pos, TileMethods::Dirt)
+}
+
+pub static GRASS_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Grass",
+    description: "Drops basic resources. Reduce cooldown by 10% if adjacent to water.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_grass,
+});
+
+fn new_grass(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Grass, grid_pos, TileMethods::Grass);
+    let mut harvest = TileCompHarvest::new(Time::new(TimeUnit::Seconds(18.0)), FixedTableID::Grass, false);
+    harvest.add_length_condition(-0.1, WorldCondition::AdjacentTo(TileSnapshot::Water));
+    harvest.add_drop_condition((EntryOutput::new_item(ItemType::Acorn, 1), 10.0), WorldCondition::AdjacentTo(TileSnapshot::OakTree { has_nest: true }));
+    inst.comp_harvest = Some(harvest);
+    inst
+}
+
+pub static BOULDER_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Boulder",
+    description: "Drops basic resources.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_boulder,
+});
+
+fn new_boulder(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Boulder, grid_pos, TileMethods::Boulder);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Seconds(120.0)), FixedTableID::Boulder, false));
+    inst
+}
+
+pub static OAK_TREE_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Oak Tree",
+    description: "Drops construction resources.",
+    world_layer: WorldLayer::Floor,
+    footprint: GridPos::new(0, 0).to_rect_iter(2, 2).collect(),
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_oak_tree,
+});
+
+fn new_oak_tree(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::OakTree, grid_pos, TileMethods::OakTree(TileOakTree::new()));
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Seconds(360.0)), FixedTableID::OakTree, false));
+    inst
+}
+
+pub static BIRD_NEST_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Bird Nest",
+    description: "Must be placed in a tree. Adds acorn drops to adjacent grass.",
+    world_layer: WorldLayer::TreeAttachment,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::OakTree { has_nest: false })],
+    placement_global_mod: vec![],
+    new_instance: new_bird_nest,
+});
+
+fn new_bird_nest(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::BirdNest, grid_pos, TileMethods::BirdNest(TileBirdNest::new()))
+}
+
+pub static CAVE_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Cave",
+    description: "Drops babies and eggs.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_cave,
+});
+
+fn new_cave(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Cave, grid_pos, TileMethods::Cave);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Days(1.5)), FixedTableID::Cave, false));
+    inst
+}
+
+pub static SHRUB_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Shrub",
+    description: "Drops basic food.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_shrub,
+});
+
+fn new_shrub(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Shrub, grid_pos, TileMethods::Shrub);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Seconds(40.0)), FixedTableID::Shrub, false));
+    inst
+}
+
+pub static MUD_PIT_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Mud Pit",
+    description: "Drops mud babies and ground tiles.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_mud_pit,
+});
+
+fn new_mud_pit(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::MudPit, grid_pos, TileMethods::MudPit);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Minutes(4.0)), FixedTableID::MudPit, false));
+    inst
+}
+
+pub static TALL_GRASS_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Tall Grass",
+    description: "Tall Grass",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_tall_grass,
+});
+
+fn new_tall_grass(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::TallGrass, grid_pos, TileMethods::TallGrass);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Seconds(60.0)), FixedTableID::TallGrass, false));
+    inst
+}
+
+pub static FROG_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Frog",
+    description: "Must be placed in tall grass. Drops potion resourcs.",
+    world_layer: WorldLayer::Walker,
+    footprint: GridPos::new(0, 0).to_rect_iter(4, 4).collect(),
+    placing_draw_footprint: true,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::TallGrass)],
+    placement_global_mod: vec![],
+    new_instance: new_frog,
+});
+
+fn new_frog(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Frog, grid_pos, TileMethods::Frog);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Hours(3.0)), FixedTableID::Frog, false));
+    inst.comp_wander = Some(TileCompWander { range: 4, target_grid_offset: GridPos::new(1, 1), curr_world_pos: grid_to_world(&grid_pos) });
+    inst
+}
+
+pub static WATER_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Water",
+    description: "Placed on empty space. Creates water for other tiles.",
+    world_layer: WorldLayer::Ground,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::ValidPosition()],
+    placement_global_mod: vec![],
+    new_instance: new_water,
+});
+
+fn new_water(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::Water, grid_pos, TileMethods::Water)
+}
+
+pub static NEWT_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Newt",
+    description: "Must be placed in water. Drops potion resources.",
+    world_layer: WorldLayer::Walker,
+    footprint: GridPos::new(0, 0).to_rect_iter(4, 4).collect(),
+    placing_draw_footprint: true,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water)],
+    placement_global_mod: vec![],
+    new_instance: new_newt,
+});
+
+fn new_newt(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Newt, grid_pos, TileMethods::Newt);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Hours(3.0)), FixedTableID::Newt, false));
+    inst.comp_wander = Some(TileCompWander { range: 4, target_grid_offset: GridPos::new(1, 1), curr_world_pos: grid_to_world(&grid_pos) });
+    inst
+}
+
+pub static REED_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Reed",
+    description: "Must be placed in mud. Drops potion resources",
+    world_layer: WorldLayer::Planted,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::MudPit)],
+    placement_global_mod: vec![],
+    new_instance: new_reed,
+});
+
+fn new_reed(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Reed, grid_pos, TileMethods::Reed);
+    let mut harvest = TileCompHarvest::new(Time::new(TimeUnit::Seconds(20.0)), FixedTableID::SmallGold, false);
+    harvest.destroy_after_harvest = true;
+    inst.comp_harvest = Some(harvest);
+    inst
+}
+
+pub static CLAM_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Clam",
+    description: "Drops trash and occasionally pearls.",
+    world_layer: WorldLayer::Walker,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water)],
+    placement_global_mod: vec![],
+    new_instance: new_clam,
+});
+
+fn new_clam(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Clam, grid_pos, TileMethods::Clam);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Seconds(20.0)), FixedTableID::Clam, false));
+    inst
+}
+
+pub static MUD_FISH_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "MudFish",
+    description: "Automatically drops dirt tiles. Drops double if adjacent to a MudPit. Dies after 3 days.",
+    world_layer: WorldLayer::Walker,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water)],
+    placement_global_mod: vec![],
+    new_instance: new_mud_fish,
+});
+
+fn new_mud_fish(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::MudFish, grid_pos, TileMethods::MudFish);
+    let mut harvest = TileCompHarvest::new(Time::new(TimeUnit::Hours(6.0)), FixedTableID::Dirt, true);
+    harvest.add_drop_count_condition(2.0, WorldCondition::AdjacentTo(TileSnapshot::MudPit));
+    inst.comp_harvest = Some(harvest);
+    inst.comp_auto_death = Some(TileCompAutoDeath::new(Time::new(TimeUnit::Days(3.0))));
+    inst
+}
+
+pub static SPRING_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Spring",
+    description: "Doubles the drops of all adjacent tiles.",
+    world_layer: WorldLayer::Floor,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![GlobalMod::new(GlobalModKind::DropCount(2.0), GridPos::new(0, 0).to_adjacents_iter().collect())],
+    new_instance: new_spring,
+});
+
+fn new_spring(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::Spring, grid_pos, TileMethods::Spring)
+}
+
+pub static KELP_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Kelp",
+    description: "Does nothing. Provides homes for sea creatures.",
+    world_layer: WorldLayer::Planted,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water)],
+    placement_global_mod: vec![],
+    new_instance: new_kelp,
+});
+
+fn new_kelp(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::Kelp, grid_pos, TileMethods::Kelp)
+}
+
+pub static CRAB_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Crab",
+    description: "Automatically harvests anything adjacent to myself. Must be adjacent to a kelp.",
+    world_layer: WorldLayer::Walker,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water), WorldCondition::AdjacentTo(TileSnapshot::Kelp)],
+    placement_global_mod: vec![],
+    new_instance: new_crab,
+});
+
+fn new_crab(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::Crab, grid_pos, TileMethods::Crab);
+    inst.comp_harvest_others = Some(TileCompHarvestOthers::new(Time::new(TimeUnit::Seconds(10.0)), GridPos::new(0, 0).to_adjacents_iter().collect()));
+    inst
+}
+
+pub static MUD_HENGE_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Mud Henge",
+    description: "Gives mud hearts. Destroyed after harvesting.",
+    world_layer: WorldLayer::Floor,
+    footprint: GridPos::new(0, 0).to_rect_iter(2, 2).collect(),
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_mud_henge,
+});
+
+fn new_mud_henge(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::MudHenge, grid_pos, TileMethods::MudHenge);
+    let mut harvest = TileCompHarvest::new(Time::new(TimeUnit::Days(1.0)), FixedTableID::MudHenge, false);
+    harvest.destroy_after_harvest = true;
+    inst.comp_harvest = Some(harvest);
+    inst
+}
+
+pub static MUD_CHICKEN_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Mud Chicken",
+    description: "Automatically harvests all tiles within its area. Dies after 3 days.",
+    world_layer: WorldLayer::Walker,
+    footprint: GridPos::new(0, 0).to_rect_iter(4, 4).collect(),
+    placing_draw_footprint: true,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
+    placement_global_mod: vec![],
+    new_instance: new_mud_chicken,
+});
+
+fn new_mud_chicken(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::MudChicken, grid_pos, TileMethods::MudChicken);
+    inst.comp_harvest_others = Some(TileCompHarvestOthers::new(Time::new(TimeUnit::Seconds(10.0)), GridPos::new(0, 0).to_rect_iter(4, 4).collect()));
+    inst.comp_wander = Some(TileCompWander { range: 4, target_grid_offset: GridPos::new(1, 1), curr_world_pos: grid_to_world(&grid_pos) });
+    inst.comp_auto_death = Some(TileCompAutoDeath::new(Time::new(TimeUnit::Days(3.0))));
+    inst
+}
+
+pub static GOBLIN_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Goblin",
+    description: "Placed in cave. Doubles drops.",
+    world_layer: WorldLayer::Walker,
+    footprint: vec![GridPos::new(0, 0)],
+    placing_draw_footprint: false,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Cave)],
+    placement_global_mod: vec![GlobalMod::new(GlobalModKind::DropCount(2.0), vec![GridPos::new(0, 0)])],
+    new_instance: new_goblin,
+});
+
+fn new_goblin(grid_pos: GridPos) -> TileInstance {
+    TileInstance::new(TileType::Goblin, grid_pos, TileMethods::Goblin)
+}
+
+pub static MUD_PIG_DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
+    title: "Mud Pig",
+    description: "Lives in mud pits. Automatically collects potion resources. Dies after 3 days.",
+    world_layer: WorldLayer::Walker,
+    footprint: GridPos::new(0, 0).to_rect_iter(2, 2).collect(),
+    placing_draw_footprint: true,
+    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::MudPit)],
+    placement_global_mod: vec![],
+    new_instance: new_mud_pig,
+});
+
+fn new_mud_pig(grid_pos: GridPos) -> TileInstance {
+    let mut inst = TileInstance::new(TileType::MudPig, grid_pos, TileMethods::MudPig);
+    inst.comp_harvest = Some(TileCompHarvest::new(Time::new(TimeUnit::Hours(6.0)), FixedTableID::MudPig, true));
+    inst.comp_wander = Some(TileCompWander { range: 2, target_grid_offset: GridPos::new(1, 1), curr_world_pos: grid_to_world(&grid_pos) });
+    inst.comp_auto_death = Some(TileCompAutoDeath::new(Time::new(TimeUnit::Days(3.0))));
+    inst
+}
+
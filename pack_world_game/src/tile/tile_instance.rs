@@ -64,6 +64,10 @@ impl TileInstance {
         }
     }
 
+    pub fn methods(&self) -> &TileMethods {
+        &self.methods
+    }
+
     /// Some other tile is placed ontop of this one.
     /// top_id is the entity_id of the newly placed tile.
     pub fn tile_placed_ontop(&mut self, tile_type: TileType, top_id: EntityID) {
@@ -114,7 +118,12 @@ impl TileInstance {
         }
     }
 
-    pub fn harvest(&mut self, world_snapshot: &WorldSnapshot, platform_api: &PlatformApi) {
+    /// Returns signals for anything that cares the tile was harvested
+    pub fn harvest(
+        &mut self,
+        world_snapshot: &WorldSnapshot,
+        platform_api: &PlatformApi,
+    ) -> Vec<UpdateSignal> {
         if let Some(timer) = &mut self.comp_harvest {
             if timer.can_harvest() {
                 let drop = timer.harvest(world_snapshot, &self.grid_pos, platform_api);
@@ -122,8 +131,14 @@ impl TileInstance {
                 self.drops_queue.append(&mut drop.to_individual());
 
                 self.destroy_after_drops = timer.destroy_after_harvest;
+
+                return vec![UpdateSignal::TileHarvested {
+                    tile_type: self.tile_type,
+                }];
             }
         }
+
+        vec![]
     }
 
     pub fn can_harvest(&self) -> bool {
@@ -148,6 +163,9 @@ impl TileInstance {
             let drop_opt = timer.inc(delta_time, world_snapshot, &self.grid_pos, platform_api);
             if let Some(drop) = drop_opt {
                 self.drops_queue.append(&mut drop.to_individual());
+                sigs.push(UpdateSignal::TileHarvested {
+                    tile_type: self.tile_type,
+                });
             }
         }
 
@@ -172,6 +190,21 @@ impl TileInstance {
         sigs
     }
 
+    /// Move every timer on without harvesting or dying. Whatever comes due happens on the next sim update.
+    pub fn catch_up(&mut self, delta_time: f64, world_snapshot: &WorldSnapshot) {
+        if let Some(timer) = &mut self.comp_harvest {
+            timer.advance(delta_time, world_snapshot, &self.grid_pos);
+        }
+
+        if let Some(ad) = &mut self.comp_auto_death {
+            ad.inc(Time::new(TimeUnit::Seconds(delta_time)));
+        }
+
+        if let Some(ho) = &mut self.comp_harvest_others {
+            ho.advance(Time::new(TimeUnit::Seconds(delta_time)));
+        }
+    }
+
     pub fn update_world_conditions(&mut self, world_snapshot: &WorldSnapshot) {
         let gp = self.grid_pos;
         if let Some(timer) = &mut self.comp_harvest {
@@ -200,6 +233,7 @@ impl TileInstance {
                     return vec![UpdateSignal::AddHarvestDrop {
                         drop: self.drops_queue.pop().unwrap(),
                         origin: self.grid_pos,
+                        source: DropSource::Tile(self.tile_type),
                     }];
                 }
             } else {
@@ -262,8 +296,6 @@ impl TileInstance {
 
     /// Convert the tile into a tilesnapshot
     pub fn into_snapshot(&self) -> TileSnapshot {
-        // TODO make this conversion part of the tile definition
-
         /*
         I made multiple attempts to to remove the need for the snapshots.
         But this actually seems the best. Otherwise we need equals checks on the
@@ -274,32 +306,7 @@ impl TileInstance {
         manually doing conversions into a new structure.
         */
 
-        match &self.methods {
-            TileMethods::Dirt => TileSnapshot::Dirt,
-            TileMethods::Water => TileSnapshot::Water,
-            TileMethods::Grass => TileSnapshot::Grass,
-            TileMethods::Boulder => TileSnapshot::Boulder,
-            TileMethods::OakTree(state) => TileSnapshot::OakTree {
-                has_nest: state.has_nest,
-            },
-            TileMethods::BirdNest(state) => TileSnapshot::BirdNest,
-            TileMethods::Cave => TileSnapshot::Cave,
-            TileMethods::Shrub => TileSnapshot::Shrub,
-            TileMethods::MudPit => TileSnapshot::MudPit,
-            TileMethods::TallGrass => TileSnapshot::TallGrass,
-            TileMethods::Frog => TileSnapshot::Frog,
-            TileMethods::Newt => TileSnapshot::Newt,
-            TileMethods::Reed => TileSnapshot::Reed,
-            TileMethods::Clam => TileSnapshot::Clam,
-            TileMethods::MudFish => TileSnapshot::MudFish,
-            TileMethods::Spring => TileSnapshot::Spring,
-            TileMethods::Kelp => TileSnapshot::Kelp,
-            TileMethods::Crab => TileSnapshot::Crab,
-            TileMethods::MudHenge => TileSnapshot::MudHenge,
-            TileMethods::MudChicken => TileSnapshot::MudChicken,
-            TileMethods::Goblin => TileSnapshot::Goblin,
-            TileMethods::MudPig => TileSnapshot::MudPig,
-        }
+        self.methods.into_snapshot()
     }
 
     pub fn save_file_write(
@@ -384,7 +391,7 @@ mod test {
 
     #[test]
     fn grass_saving() {
-        let inst_orig = crate::tiles::tile_cave::new_instance(GridPos::new(10, 5));
+        let inst_orig = TileType::Cave.create_instance(GridPos::new(10, 5));
 
         let mut save_file = SaveFile::new();
         inst_orig
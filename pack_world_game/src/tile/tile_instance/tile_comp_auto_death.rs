@@ -19,6 +19,10 @@ impl TileCompAutoDeath {
         self.timer.greater_than_zero()
     }
 
+    pub fn remaining_ms(&self) -> f64 {
+        self.timer.as_milliseconds().value()
+    }
+
     pub fn save_file_write(
         &self,
         key_parent: String,
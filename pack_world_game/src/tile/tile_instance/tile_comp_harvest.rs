@@ -90,6 +90,14 @@ impl TileCompHarvest {
         })
     }
 
+    /// Entries the drop conditions can add. Whether they're active or not.
+    pub fn condition_entries(&self) -> Vec<EntryOutput> {
+        self.drop_conditions
+            .iter()
+            .map(|dc| dc.entry.0.clone())
+            .collect()
+    }
+
     pub fn update_world_conditions(&mut self, pos: GridPos, world_snapshot: &WorldSnapshot) {
         // length conditions
         for lc in &mut self.length_condition {
@@ -120,8 +128,7 @@ impl TileCompHarvest {
         grid_pos: &GridPos,
         platform_api: &PlatformApi,
     ) -> Option<Drop> {
-        self.time += time;
-        self.time = self.time.clamp(0.0, self.length());
+        self.advance(time, world_snapshot, grid_pos);
 
         if self.self_harvest && self.time >= self.length() {
             return Some(self.harvest(world_snapshot, grid_pos, platform_api));
@@ -130,6 +137,17 @@ impl TileCompHarvest {
         return None;
     }
 
+    /// Move the timer on without harvesting
+    pub fn advance(&mut self, time: f64, world_snapshot: &WorldSnapshot, grid_pos: &GridPos) {
+        let speed_mod = *world_snapshot
+            .harvest_speed_mod
+            .get(grid_pos)
+            .unwrap_or(&1.0);
+
+        self.time += time * speed_mod;
+        self.time = self.time.clamp(0.0, self.length());
+    }
+
     pub fn can_harvest(&self) -> bool {
         self.time >= self.length()
     }
@@ -138,6 +156,11 @@ impl TileCompHarvest {
         self.time = 0.0;
     }
 
+    /// Skip the remaining time so the tile is ready to harvest
+    pub fn finish(&mut self) {
+        self.time = self.length();
+    }
+
     #[must_use]
     pub fn harvest(
         &mut self,
@@ -27,8 +27,7 @@ impl TileCompHarvestOthers {
         grid_pos: &GridPos,
         world_snapshot: &WorldSnapshot,
     ) -> Vec<UpdateSignal> {
-        self.timer = self.timer + time_step;
-        self.timer.clamp_ms(0.0, self.len.as_milliseconds().value());
+        self.advance(time_step);
 
         let mut ret: Vec<UpdateSignal> = vec![];
 
@@ -47,6 +46,12 @@ impl TileCompHarvestOthers {
         ret
     }
 
+    /// Move the timer on without harvesting
+    pub fn advance(&mut self, time_step: Time) {
+        self.timer = self.timer + time_step;
+        self.timer.clamp_ms(0.0, self.len.as_milliseconds().value());
+    }
+
     pub fn perc_done(&self) -> f64 {
         self.timer.as_milliseconds().value() / self.len.as_milliseconds().value()
     }
@@ -4,7 +4,7 @@ use crate::{
     grid::*,
     save_file::{load, *},
     state::{assets::*, *},
-    tile::{tile_instance::*, tile_type::*, tiles::*},
+    tile::{tile_instance::*, tile_type::*, tiles::*, TileMethods},
     update_signal::*,
     world::*,
 };
@@ -16,61 +16,9 @@ use elara_engine::{
     vectors::*,
 };
 
-/// This is just manual dynamic dispatch because Dyn breaks hot realoding.
-#[derive(Debug, Clone)]
-pub enum TileMethods {
-    Dirt,
-    Grass,
-    Boulder,
-    OakTree(TileOakTree),
-    BirdNest(TileBirdNest),
-    Cave,
-    Shrub,
-    MudPit,
-    TallGrass,
-    Frog,
-    Water,
-    Newt,
-    Reed,
-    Clam,
-    MudFish,
-    Spring,
-    Kelp,
-    Crab,
-    MudHenge,
-    MudChicken,
-    Goblin,
-    MudPig,
-}
+// The TileMethods enum and its save indices are generated from design/tiles.txt
 
 impl TileMethods {
-    pub fn to_index(&self) -> i32 {
-        match self {
-            TileMethods::Dirt => 1,
-            TileMethods::Grass => 2,
-            TileMethods::Boulder => 3,
-            TileMethods::OakTree(state) => 4,
-            TileMethods::BirdNest(state) => 5,
-            TileMethods::Cave => 6,
-            TileMethods::Shrub => 7,
-            TileMethods::MudPit => 8,
-            TileMethods::TallGrass => 9,
-            TileMethods::Frog => 10,
-            TileMethods::Water => 11,
-            TileMethods::Newt => 12,
-            TileMethods::Reed => 13,
-            TileMethods::Clam => 14,
-            TileMethods::MudFish => 15,
-            TileMethods::Spring => 16,
-            TileMethods::Kelp => 17,
-            TileMethods::Crab => 18,
-            TileMethods::MudHenge => 19,
-            TileMethods::MudChicken => 20,
-            TileMethods::Goblin => 21,
-            TileMethods::MudPig => 22,
-        }
-    }
-
     pub fn save_file_write(
         &self,
         key_parent: String,
@@ -82,17 +30,7 @@ impl TileMethods {
         save_file.save_i32(&type_key, self.to_index());
 
         // Save tile specific state
-        match self {
-            TileMethods::OakTree(state) => {
-                state.save_file_write(state_key, save_file)?;
-            }
-            TileMethods::BirdNest(state) => {
-                state.save_file_write(state_key, save_file)?;
-            }
-            _ => {}
-        }
-
-        Ok(())
+        self.save_state(state_key, save_file)
     }
 
     pub fn save_file_load(
@@ -104,33 +42,7 @@ impl TileMethods {
         let state_key = format!("{}.s", key_parent);
 
         let id = save_file.load_i32(&type_key)?;
-        match id {
-            1 => Ok(Self::Dirt),
-            2 => Ok(Self::Grass),
-            3 => Ok(Self::Boulder),
-            4 => Ok(TileOakTree::save_file_load(state_key, save_file)?),
-            5 => Ok(TileBirdNest::save_file_load(state_key, save_file)?),
-            6 => Ok(Self::Cave),
-            7 => Ok(Self::Shrub),
-            8 => Ok(Self::MudPit),
-            9 => Ok(Self::TallGrass),
-            10 => Ok(Self::Frog),
-            11 => Ok(Self::Water),
-            12 => Ok(Self::Newt),
-            13 => Ok(Self::Reed),
-            14 => Ok(Self::Clam),
-            15 => Ok(Self::MudFish),
-            16 => Ok(Self::Spring),
-            17 => Ok(Self::Kelp),
-            18 => Ok(Self::Crab),
-            19 => Ok(Self::MudHenge),
-            20 => Ok(Self::MudChicken),
-            21 => Ok(Self::Goblin),
-            22 => Ok(Self::MudPig),
-            _ => {
-                return Err(Error::UnknownTileMethodID(id));
-            }
-        }
+        Self::load_state(id, state_key, save_file)
     }
 }
 
@@ -1,28 +1,10 @@
 use crate::tile::*;
 use elara_engine::vectors::*;
 
+// Only tiles with custom behaviour live here. Everything else is data in design/tiles.txt.
+
 pub mod tile_bird_nest;
-pub mod tile_boulder;
-pub mod tile_cave;
-pub mod tile_clam;
-pub mod tile_crab;
-pub mod tile_dirt;
-pub mod tile_frog;
-pub mod tile_goblin;
-pub mod tile_grass;
-pub mod tile_kelp;
-pub mod tile_mud_chicken;
-pub mod tile_mud_fish;
-pub mod tile_mud_henge;
-pub mod tile_mud_pig;
-pub mod tile_mud_pit;
-pub mod tile_newt;
 pub mod tile_oak_tree;
-pub mod tile_reed;
-pub mod tile_shrub;
-pub mod tile_spring;
-pub mod tile_tall_grass;
-pub mod tile_water;
 
 pub use tile_bird_nest::TileBirdNest;
 pub use tile_oak_tree::TileOakTree;
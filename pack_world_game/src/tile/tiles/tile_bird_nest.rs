@@ -11,42 +11,20 @@ use elara_engine::{
     render::{material::*, render_command::*, render_pack::*, shader::*},
     ui::*,
 };
-use std::sync::LazyLock;
-
-pub static DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
-    title: "Bird Nest",
-    description: "Must be placed in a tree. Adds acorn drops to adjacent grass.",
-    world_layer: WorldLayer::TreeAttachment,
-    footprint: vec![GridPos::new(0, 0)],
-
-    placing_draw_footprint: false,
-
-    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::OakTree {
-        has_nest: false,
-    })],
-    placement_global_mod: vec![],
-
-    new_instance: new_instance,
-});
 
 #[derive(Debug, Clone)]
 pub struct TileBirdNest {
     pub tree_origin: GridPos,
 }
 
-pub fn new_instance(grid_pos: GridPos) -> TileInstance {
-    let mut inst = TileInstance::new(
-        TileType::BirdNest,
-        grid_pos,
-        TileMethods::BirdNest(TileBirdNest {
+impl TileBirdNest {
+    /// The tree origin is found once placed
+    pub fn new() -> Self {
+        Self {
             tree_origin: GridPos::new(0, 0),
-        }),
-    );
-
-    inst
-}
+        }
+    }
 
-impl TileBirdNest {
     pub fn tile_placed(&mut self, current_tiles: Vec<&TileInstance>) {
         for inst in current_tiles {
             if inst.tile_type == TileType::OakTree {
@@ -1,35 +0,0 @@
-use crate::{
-    drop_table::*,
-    grid::*,
-    item::*,
-    save_file::*,
-    state::{inventory::*, *},
-    tile::*,
-    world::*,
-};
-use elara_engine::{
-    color::*,
-    platform_api::*,
-    rect::*,
-    render::{material::*, render_command::*, render_pack::*, shader::*},
-    time::*,
-    ui::*,
-};
-use std::sync::LazyLock;
-
-pub static DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
-    title: "Kelp",
-    description: "Does nothing. Provides homes for sea creatures.",
-    world_layer: WorldLayer::Planted,
-    footprint: vec![GridPos::new(0, 0)],
-    placing_draw_footprint: false,
-
-    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Water)],
-    new_instance: new_instance,
-    placement_global_mod: vec![],
-});
-
-pub fn new_instance(grid_pos: GridPos) -> TileInstance {
-    let mut inst = TileInstance::new(TileType::Kelp, grid_pos, TileMethods::Kelp);
-    inst
-}
@@ -13,20 +13,6 @@ use elara_engine::{
     time::*,
     ui::*,
 };
-use std::sync::LazyLock;
-
-pub static DEF: LazyLock<TileDefinition> = LazyLock::new(|| TileDefinition {
-    title: "Oak Tree",
-    description: "Drops construction resources.",
-    world_layer: WorldLayer::Floor,
-    footprint: GridPos::new(0, 0).to_rect_iter(2, 2).collect(),
-    placing_draw_footprint: false,
-
-    placement_constraints: vec![WorldCondition::OriginContains(TileSnapshot::Dirt)],
-    placement_global_mod: vec![],
-
-    new_instance: new_instance,
-});
 
 #[derive(Debug, Clone)]
 pub struct TileOakTree {
@@ -35,26 +21,20 @@ pub struct TileOakTree {
     pub nest_id: Option<EntityID>,
 }
 
-pub fn new_instance(grid_pos: GridPos) -> TileInstance {
-    let mut inst = TileInstance::new(
-        TileType::OakTree,
-        grid_pos,
-        TileMethods::OakTree(TileOakTree {
+impl TileOakTree {
+    pub fn new() -> Self {
+        Self {
             has_nest: false,
             nest_id: None,
-        }),
-    );
-
-    inst.comp_harvest = Some(TileCompHarvest::new(
-        Time::new(TimeUnit::Seconds(360.0)),
-        FixedTableID::OakTree,
-        false,
-    ));
+        }
+    }
 
-    inst
-}
+    pub fn snapshot(&self) -> TileSnapshot {
+        TileSnapshot::OakTree {
+            has_nest: self.has_nest,
+        }
+    }
 
-impl TileOakTree {
     pub fn tile_placed_ontop(&mut self, tile_type: TileType, top_id: EntityID) {
         if tile_type == TileType::BirdNest {
             self.has_nest = true;
@@ -0,0 +1,55 @@
+use elara_engine::{color::*, rect::*, ui::*, vectors::*};
+
+/// How long a toast stays on screen, in seconds
+pub static TOAST_LEN: f64 = 4.0;
+
+pub static TOAST_WIDTH: f64 = 500.0;
+pub static TOAST_HEIGHT: f64 = 50.0;
+
+/// Short notification message shown at the top of the screen
+pub struct Toast {
+    pub message: String,
+    time: f64,
+}
+
+impl Toast {
+    pub fn new(message: String) -> Self {
+        Self { message, time: 0.0 }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.time >= TOAST_LEN
+    }
+
+    /// Index is the position in the toast stack
+    pub fn update_and_draw(
+        &mut self,
+        delta_time: f64,
+        index: usize,
+        mut ui_frame_state: &mut UIFrameState,
+        ui_context: &mut UIContext,
+    ) {
+        self.time += delta_time;
+
+        let center = VecTwo::new(
+            ui_frame_state.resolution.x * 0.5,
+            100.0 + (index as f64 * (TOAST_HEIGHT + 10.0)),
+        );
+
+        begin_panel(
+            Rect::new_center(center, VecTwo::new(TOAST_WIDTH, TOAST_HEIGHT)),
+            *THEME_PANEL_BG,
+            &mut ui_frame_state,
+            ui_context,
+        );
+        draw_text(
+            &self.message,
+            VecTwo::new(15.0, 32.0),
+            COLOR_WHITE,
+            &ui_context.font_body.clone(),
+            ui_frame_state,
+            ui_context,
+        );
+        end_panel(&mut ui_frame_state, ui_context);
+    }
+}
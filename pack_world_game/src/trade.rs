@@ -0,0 +1,293 @@
+use crate::{drop_table::*, error::*, item::*, state::inventory::*, tile::*};
+
+pub mod trade_log;
+pub mod trade_seal;
+
+pub use trade_log::*;
+pub use trade_seal::*;
+
+/// Every trade code starts with this. Bump it if the format changes.
+pub const TRADE_CODE_PREFIX: &str = "PWT3";
+
+/// One player offering to give a drop in exchange for another
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TradeOffer {
+    /// Save id of the player making the offer
+    pub from: u64,
+    pub nonce: u64,
+
+    pub give: Drop,
+    pub want: Drop,
+
+    /// Only the offering save can make a settle code that opens this. See offer_lock.
+    pub lock: u64,
+}
+
+/// Receipt for paying what an offer wants. Sent back to the offering player to complete the trade.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TradeAccept {
+    pub offer_from: u64,
+    pub nonce: u64,
+    pub accepted_by: u64,
+}
+
+/// Sent from the offering player back to the accepting player. Pays out their receipt once.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TradeSettle {
+    pub offer_from: u64,
+    pub nonce: u64,
+    pub accepted_by: u64,
+
+    /// True pays out the offered drop. False refunds the payment, because the offer was already closed.
+    pub delivered: bool,
+
+    /// Opens the offer's lock, for this outcome and accepting save only
+    pub proof: TradeProof,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TradeCode {
+    Offer(TradeOffer),
+    Accept(TradeAccept),
+    Settle(TradeSettle),
+}
+
+impl TradeCode {
+    pub fn encode(&self) -> String {
+        let body = match self {
+            TradeCode::Offer(offer) => format!(
+                "{}-O-{:x}-{:x}-{}-{}-{:x}",
+                TRADE_CODE_PREFIX,
+                offer.from,
+                offer.nonce,
+                encode_drop(&offer.give),
+                encode_drop(&offer.want),
+                offer.lock
+            ),
+            TradeCode::Accept(accept) => format!(
+                "{}-A-{:x}-{:x}-{:x}",
+                TRADE_CODE_PREFIX, accept.offer_from, accept.nonce, accept.accepted_by
+            ),
+            TradeCode::Settle(settle) => {
+                let chains: Vec<String> = settle
+                    .proof
+                    .chains
+                    .iter()
+                    .map(|chain| format!("{:x}", chain))
+                    .collect();
+
+                format!(
+                    "{}-S-{:x}-{:x}-{:x}-{}-{}-{:x}",
+                    TRADE_CODE_PREFIX,
+                    settle.offer_from,
+                    settle.nonce,
+                    settle.accepted_by,
+                    if settle.delivered { "d" } else { "r" },
+                    chains.join("-"),
+                    settle.proof.other_key
+                )
+            }
+        };
+
+        format!("{}-{:x}", body, checksum(&body))
+    }
+
+    pub fn decode(code: &str) -> Result<Self, Error> {
+        let code = code.trim();
+
+        let Some((body, sum)) = code.rsplit_once('-') else {
+            return Err(Error::InvalidTradeCode);
+        };
+        let sum = u64::from_str_radix(sum, 16).map_err(|_| Error::InvalidTradeCode)?;
+        if sum != checksum(body) {
+            return Err(Error::TradeCodeDamaged);
+        }
+
+        let parts: Vec<&str> = body.split('-').collect();
+        if parts.len() < 2 || parts[0] != TRADE_CODE_PREFIX {
+            return Err(Error::InvalidTradeCode);
+        }
+
+        match (parts[1], parts.len()) {
+            ("O", 7) => Ok(TradeCode::Offer(TradeOffer {
+                from: parse_hex(parts[2])?,
+                nonce: parse_hex(parts[3])?,
+                give: decode_drop(parts[4])?,
+                want: decode_drop(parts[5])?,
+                lock: parse_hex(parts[6])?,
+            })),
+            ("A", 5) => Ok(TradeCode::Accept(TradeAccept {
+                offer_from: parse_hex(parts[2])?,
+                nonce: parse_hex(parts[3])?,
+                accepted_by: parse_hex(parts[4])?,
+            })),
+            ("S", len) if len == 6 + (SEAL_DIGITS + 1) + 1 => {
+                let mut chains = [0; SEAL_DIGITS + 1];
+                for (chain, part) in chains.iter_mut().zip(&parts[6..]) {
+                    *chain = parse_hex(part)?;
+                }
+
+                Ok(TradeCode::Settle(TradeSettle {
+                    offer_from: parse_hex(parts[2])?,
+                    nonce: parse_hex(parts[3])?,
+                    accepted_by: parse_hex(parts[4])?,
+                    delivered: match parts[5] {
+                        "d" => true,
+                        "r" => false,
+                        _ => return Err(Error::InvalidTradeCode),
+                    },
+                    proof: TradeProof {
+                        chains,
+                        other_key: parse_hex(parts[len - 1])?,
+                    },
+                }))
+            }
+            _ => Err(Error::InvalidTradeCode),
+        }
+    }
+}
+
+pub fn can_afford_drop(inventory: &Inventory, drop: &Drop) -> bool {
+    match drop.drop_type {
+        DropType::Gold => inventory.gold >= drop.amount,
+        DropType::Item { item_type } => inventory.has_atleast(item_type, drop.amount),
+    }
+}
+
+/// Toast text for a failed trade
+pub fn trade_error_message(error: &Error) -> String {
+    match error {
+        Error::InvalidTradeCode => "That isn't a valid trade code.".into(),
+        Error::TradeCodeDamaged => "That trade code is damaged. Copy it again.".into(),
+        Error::TradeOwnOffer => "You can't accept your own offer.".into(),
+        Error::TradeAlreadyUsed => "That trade code was already used.".into(),
+        Error::UnknownTrade => "That trade code isn't for this save.".into(),
+        Error::TradeCodeForged => "That trade code wasn't made by the other player.".into(),
+        Error::NotEnoughItems => "You don't have enough for that trade.".into(),
+        _ => format!("Trade failed {:?}", error),
+    }
+}
+
+/// FNV-1a. Catches typos and codes cut short while copying.
+/// Anyone can compute it, so it doesn't prove who made a code. A settle's proof does that.
+fn checksum(body: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in body.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn parse_hex(input: &str) -> Result<u64, Error> {
+    u64::from_str_radix(input, 16).map_err(|_| Error::InvalidTradeCode)
+}
+
+/// g{amount} for gold, i{item}x{amount} for items, t{tile}x{amount} for tiles
+fn encode_drop(drop: &Drop) -> String {
+    match drop.drop_type {
+        DropType::Gold => format!("g{}", drop.amount),
+        DropType::Item {
+            item_type: ItemType::Tile(tile_type),
+        } => format!("t{}x{}", tile_type.to_index(), drop.amount),
+        DropType::Item { item_type } => format!("i{}x{}", item_type.to_index(), drop.amount),
+    }
+}
+
+fn decode_drop(input: &str) -> Result<Drop, Error> {
+    let parse_amount = |amount: &str| -> Result<i64, Error> {
+        match amount.parse::<i64>() {
+            Ok(amount) if amount > 0 => Ok(amount),
+            _ => Err(Error::InvalidTradeCode),
+        }
+    };
+
+    if let Some(amount) = input.strip_prefix('g') {
+        return Ok(Drop::new_gold(parse_amount(amount)?));
+    }
+
+    let (kind, rest) = input.split_at(input.chars().next().map_or(0, |c| c.len_utf8()));
+    let Some((id, amount)) = rest.split_once('x') else {
+        return Err(Error::InvalidTradeCode);
+    };
+    let id: i32 = id.parse().map_err(|_| Error::InvalidTradeCode)?;
+
+    let item_type = match kind {
+        "i" => ItemType::from_index(id).map_err(|_| Error::InvalidTradeCode)?,
+        "t" => ItemType::Tile(TileType::from_index(id).map_err(|_| Error::InvalidTradeCode)?),
+        _ => return Err(Error::InvalidTradeCode),
+    };
+
+    Ok(Drop::new_item(item_type, parse_amount(amount)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let offer = TradeCode::Offer(TradeOffer {
+            from: 0xabc123,
+            nonce: 4,
+            give: Drop::new_item(ItemType::Pearl, 10),
+            want: Drop::new_gold(200),
+            lock: 0xfeed,
+        });
+        assert_eq!(TradeCode::decode(&offer.encode()).unwrap(), offer);
+
+        let tiles = TradeCode::Offer(TradeOffer {
+            from: 1,
+            nonce: 0,
+            give: Drop::new_tile(TileType::MudHenge, 1),
+            want: Drop::new_item(ItemType::DragonEgg, 2),
+            lock: 0,
+        });
+        assert_eq!(TradeCode::decode(&tiles.encode()).unwrap(), tiles);
+
+        let accept = TradeCode::Accept(TradeAccept {
+            offer_from: 0xabc123,
+            nonce: 4,
+            accepted_by: 99,
+        });
+        assert_eq!(
+            TradeCode::decode(&format!("  {}\n", accept.encode())).unwrap(),
+            accept
+        );
+
+        let settle = TradeCode::Settle(TradeSettle {
+            offer_from: 0xabc123,
+            nonce: 4,
+            accepted_by: 99,
+            delivered: false,
+            proof: TradeProof {
+                chains: [0x1, 0xabc, u64::MAX],
+                other_key: 0x42,
+            },
+        });
+        assert_eq!(TradeCode::decode(&settle.encode()).unwrap(), settle);
+    }
+
+    #[test]
+    fn damaged() {
+        let code = TradeCode::Offer(TradeOffer {
+            from: 7,
+            nonce: 1,
+            give: Drop::new_item(ItemType::Pearl, 10),
+            want: Drop::new_gold(200),
+            lock: 3,
+        })
+        .encode();
+
+        let typo = code.replace("g200", "g2");
+        assert!(matches!(
+            TradeCode::decode(&typo),
+            Err(Error::TradeCodeDamaged)
+        ));
+
+        assert!(matches!(
+            TradeCode::decode("not a code"),
+            Err(Error::InvalidTradeCode)
+        ));
+    }
+}
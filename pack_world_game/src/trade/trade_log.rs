@@ -0,0 +1,824 @@
+use crate::{drop_table::*, error::*, save_file::*, state::inventory::*, trade::*};
+use elara_engine::platform_api::*;
+
+/// This save's side of all trades
+#[derive(Debug)]
+pub struct TradeLog {
+    /// Identifies this save in trade codes
+    pub save_id: u64,
+
+    /// Makes the locks on our offers and the proofs that open them. Never goes in a trade code.
+    pub secret: u64,
+    next_nonce: u64,
+
+    /// Offers this save made. The given drop is held here until a receipt completes the trade or it's cancelled.
+    pub pending: Vec<TradeOffer>,
+
+    /// (nonce, accepted_by) of our offers that were delivered. A repeated receipt gets the same settle code.
+    delivered: Vec<(u64, u64)>,
+
+    /// Offers this save paid for, waiting on the settle code
+    pub receipts: Vec<TradeOffer>,
+
+    /// (from, nonce) of offers this save accepted
+    accepted: Vec<(u64, u64)>,
+
+    /// Last code made, for the player to copy. Not saved.
+    pub last_code: Option<String>,
+}
+
+impl TradeLog {
+    pub fn new() -> Self {
+        Self {
+            save_id: 0,
+            secret: 0,
+            next_nonce: 0,
+            pending: vec![],
+            delivered: vec![],
+            receipts: vec![],
+            accepted: vec![],
+            last_code: None,
+        }
+    }
+
+    pub fn new_save_id(platform_api: &PlatformApi) -> u64 {
+        let rand = ((platform_api.rand)() * u32::MAX as f64) as u64;
+        (((platform_api.epoch_time_ms)() as u64) << 20) ^ rand
+    }
+
+    pub fn new_secret(platform_api: &PlatformApi) -> u64 {
+        let mut ret: u64 = 0;
+        for _ in 0..4 {
+            ret = (ret << 16) ^ ((platform_api.rand)() * u16::MAX as f64) as u64;
+        }
+        ret
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Gives a copy of a save its own id and no trades. It gets its own secret when loaded.
+    /// Offers and receipts stay with the original, so a trade can't be paid out to both.
+    pub fn fork_save(save_file: &mut SaveFile, save_id: u64) -> Result<(), Error> {
+        save_file.entries.retain(|key, _| !is_trade_key(key));
+
+        let mut log = Self::new();
+        log.save_id = save_id;
+        log.save_file_write("".into(), save_file)
+    }
+
+    /// Moves the given drop out of the inventory and returns the offer code
+    pub fn create_offer(
+        &mut self,
+        give: Drop,
+        want: Drop,
+        inventory: &mut Inventory,
+    ) -> Result<String, Error> {
+        if give.amount <= 0 || want.amount <= 0 || give.drop_type == want.drop_type {
+            return Err(Error::InvalidTradeCode);
+        }
+        if !can_afford_drop(inventory, &give) {
+            return Err(Error::NotEnoughItems);
+        }
+
+        inventory.give_drop(Drop::new(give.drop_type, -give.amount))?;
+
+        let mut offer = TradeOffer {
+            from: self.save_id,
+            nonce: self.next_nonce,
+            give,
+            want,
+            lock: 0,
+        };
+        offer.lock = offer_lock(self.secret, &offer);
+        self.next_nonce += 1;
+        self.pending.push(offer);
+
+        let code = TradeCode::Offer(offer).encode();
+        self.last_code = Some(code.clone());
+        Ok(code)
+    }
+
+    /// Pays what the offer wants and keeps a receipt. Returns the receipt code for the offering player.
+    /// Nothing here can tell a real offer from a made up one, so the receipt keeps the offer's lock.
+    /// The offered drop only arrives with a settle code that opens it, which only the offering save can make.
+    pub fn accept_offer(&mut self, code: &str, inventory: &mut Inventory) -> Result<String, Error> {
+        let TradeCode::Offer(offer) = TradeCode::decode(code)? else {
+            return Err(Error::InvalidTradeCode);
+        };
+
+        if offer.from == self.save_id {
+            return Err(Error::TradeOwnOffer);
+        }
+        if self.accepted.contains(&(offer.from, offer.nonce)) {
+            return Err(Error::TradeAlreadyUsed);
+        }
+        if !can_afford_drop(inventory, &offer.want) {
+            return Err(Error::NotEnoughItems);
+        }
+
+        inventory.give_drop(Drop::new(offer.want.drop_type, -offer.want.amount))?;
+        self.accepted.push((offer.from, offer.nonce));
+        self.receipts.push(offer);
+
+        let code = TradeCode::Accept(TradeAccept {
+            offer_from: offer.from,
+            nonce: offer.nonce,
+            accepted_by: self.save_id,
+        })
+        .encode();
+        self.last_code = Some(code.clone());
+        Ok(code)
+    }
+
+    /// Takes a receipt for one of our offers. The first receipt for a pending offer gets the payment,
+    /// and the offered drop goes to them. Receipts for closed offers get a refund.
+    /// Returns the settle code for the accepting player.
+    pub fn complete_trade(
+        &mut self,
+        code: &str,
+        inventory: &mut Inventory,
+    ) -> Result<TradeSettle, Error> {
+        let TradeCode::Accept(accept) = TradeCode::decode(code)? else {
+            return Err(Error::InvalidTradeCode);
+        };
+
+        if accept.offer_from != self.save_id || accept.nonce >= self.next_nonce {
+            return Err(Error::UnknownTrade);
+        }
+
+        let pending = self
+            .pending
+            .iter()
+            .position(|offer| offer.nonce == accept.nonce);
+        let delivered = match pending {
+            Some(i) => {
+                let offer = self.pending.remove(i);
+                inventory.give_drop(offer.want)?;
+                self.delivered.push((accept.nonce, accept.accepted_by));
+                true
+            }
+            None => self.delivered.contains(&(accept.nonce, accept.accepted_by)),
+        };
+
+        let mut settle = TradeSettle {
+            offer_from: accept.offer_from,
+            nonce: accept.nonce,
+            accepted_by: accept.accepted_by,
+            delivered,
+            proof: TradeProof::default(),
+        };
+        settle.proof = seal_settle(self.secret, &settle);
+        self.last_code = Some(TradeCode::Settle(settle).encode());
+        Ok(settle)
+    }
+
+    /// Pays out a receipt with the settle code. Returns what was received.
+    /// The code's proof has to open the lock of the offer we paid for.
+    pub fn redeem_settle(&mut self, code: &str, inventory: &mut Inventory) -> Result<Drop, Error> {
+        let TradeCode::Settle(settle) = TradeCode::decode(code)? else {
+            return Err(Error::InvalidTradeCode);
+        };
+
+        if settle.accepted_by != self.save_id {
+            return Err(Error::UnknownTrade);
+        }
+        let Some(i) = self
+            .receipts
+            .iter()
+            .position(|offer| offer.from == settle.offer_from && offer.nonce == settle.nonce)
+        else {
+            return Err(Error::TradeAlreadyUsed);
+        };
+        if !verify_settle(&self.receipts[i], &settle) {
+            return Err(Error::TradeCodeForged);
+        }
+
+        let offer = self.receipts.remove(i);
+        let received = if settle.delivered {
+            offer.give
+        } else {
+            offer.want
+        };
+        inventory.give_drop(received)?;
+        Ok(received)
+    }
+
+    /// Returns the held drop. Not possible once a receipt completed the offer.
+    /// Receipts that arrive after cancelling get a refund.
+    pub fn cancel_offer(&mut self, nonce: u64, inventory: &mut Inventory) -> Result<(), Error> {
+        let Some(i) = self.pending.iter().position(|offer| offer.nonce == nonce) else {
+            return Err(Error::UnknownTrade);
+        };
+
+        let offer = self.pending.remove(i);
+        inventory.give_drop(offer.give)?;
+        Ok(())
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        save_file.save_u64(&format!("{}tr.id", key_parent), self.save_id);
+        save_file.save_u64(&format!("{}tr.k", key_parent), self.secret);
+        save_file.save_u64(&format!("{}tr.n", key_parent), self.next_nonce);
+
+        for (i, offer) in self.pending.iter().enumerate() {
+            save_file.save_i64(&format!("{}trp_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}trp.{}", key_parent, i);
+            save_file.save_u64(&format!("{}.o", key), offer.nonce);
+            save_file.save_u64(&format!("{}.l", key), offer.lock);
+            offer
+                .give
+                .save_file_write(format!("{}.g", key), save_file)?;
+            offer
+                .want
+                .save_file_write(format!("{}.w", key), save_file)?;
+        }
+
+        for (i, (nonce, accepted_by)) in self.delivered.iter().enumerate() {
+            save_file.save_i64(&format!("{}trd_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}trd.{}", key_parent, i);
+            save_file.save_u64(&format!("{}.o", key), *nonce);
+            save_file.save_u64(&format!("{}.b", key), *accepted_by);
+        }
+
+        for (i, offer) in self.receipts.iter().enumerate() {
+            save_file.save_i64(&format!("{}trr_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}trr.{}", key_parent, i);
+            save_file.save_u64(&format!("{}.f", key), offer.from);
+            save_file.save_u64(&format!("{}.o", key), offer.nonce);
+            save_file.save_u64(&format!("{}.l", key), offer.lock);
+            offer
+                .give
+                .save_file_write(format!("{}.g", key), save_file)?;
+            offer
+                .want
+                .save_file_write(format!("{}.w", key), save_file)?;
+        }
+
+        for (i, (from, nonce)) in self.accepted.iter().enumerate() {
+            save_file.save_i64(&format!("{}tra_index.{}", key_parent, i), i as i64);
+
+            let key = format!("{}tra.{}", key_parent, i);
+            save_file.save_u64(&format!("{}.f", key), *from);
+            save_file.save_u64(&format!("{}.o", key), *nonce);
+        }
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let mut ret = Self::new();
+
+        // Saves from before trading have no id or secret. The caller assigns them.
+        ret.save_id = save_file
+            .load_u64(&format!("{}tr.id", key_parent))
+            .unwrap_or(0);
+        ret.secret = save_file
+            .load_u64(&format!("{}tr.k", key_parent))
+            .unwrap_or(0);
+        ret.next_nonce = save_file
+            .load_u64(&format!("{}tr.n", key_parent))
+            .unwrap_or(0);
+
+        let pending_prefix = format!("{}trp_index", key_parent);
+        let delivered_prefix = format!("{}trd_index", key_parent);
+        let receipts_prefix = format!("{}trr_index", key_parent);
+        let accepted_prefix = format!("{}tra_index", key_parent);
+        for (key, value) in &save_file.entries {
+            let parts: Vec<&str> = key.split('.').collect();
+
+            if parts[0] == pending_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}trp.{}", key_parent, i);
+
+                ret.pending.push(TradeOffer {
+                    from: ret.save_id,
+                    nonce: save_file.load_u64(&format!("{}.o", key))?,
+                    give: Drop::save_file_load(format!("{}.g", key), save_file)?,
+                    want: Drop::save_file_load(format!("{}.w", key), save_file)?,
+                    lock: save_file.load_u64(&format!("{}.l", key))?,
+                });
+            } else if parts[0] == delivered_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}trd.{}", key_parent, i);
+
+                ret.delivered.push((
+                    save_file.load_u64(&format!("{}.o", key))?,
+                    save_file.load_u64(&format!("{}.b", key))?,
+                ));
+            } else if parts[0] == receipts_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}trr.{}", key_parent, i);
+
+                ret.receipts.push(TradeOffer {
+                    from: save_file.load_u64(&format!("{}.f", key))?,
+                    nonce: save_file.load_u64(&format!("{}.o", key))?,
+                    give: Drop::save_file_load(format!("{}.g", key), save_file)?,
+                    want: Drop::save_file_load(format!("{}.w", key), save_file)?,
+                    lock: save_file.load_u64(&format!("{}.l", key))?,
+                });
+            } else if parts[0] == accepted_prefix {
+                let i = save_file.load_i64(key)?;
+                let key = format!("{}tra.{}", key_parent, i);
+
+                ret.accepted.push((
+                    save_file.load_u64(&format!("{}.f", key))?,
+                    save_file.load_u64(&format!("{}.o", key))?,
+                ));
+            }
+        }
+        ret.pending.sort_by_key(|offer| offer.nonce);
+        ret.delivered.sort();
+        ret.receipts.sort_by_key(|offer| (offer.from, offer.nonce));
+
+        Ok(ret)
+    }
+}
+
+/// Keys written by save_file_write with no key parent
+fn is_trade_key(key: &str) -> bool {
+    let first = key.split('.').next().unwrap_or("");
+    [
+        "tr",
+        "trp_index",
+        "trp",
+        "trd_index",
+        "trd",
+        "trr_index",
+        "trr",
+        "tra_index",
+        "tra",
+    ]
+    .contains(&first)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::item::*;
+
+    fn trader(save_id: u64) -> TradeLog {
+        let mut log = TradeLog::new();
+        log.save_id = save_id;
+        log.secret = save_id ^ 0x5eed_5eed;
+        log
+    }
+
+    #[test]
+    fn full_trade() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.give_item(ItemType::Pearl, 15).unwrap();
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 500;
+
+        let offer = alice
+            .create_offer(
+                Drop::new_item(ItemType::Pearl, 10),
+                Drop::new_gold(200),
+                &mut alice_inv,
+            )
+            .unwrap();
+
+        // held in escrow
+        assert!(alice_inv.has_atleast(ItemType::Pearl, 5));
+        assert!(!alice_inv.has_atleast(ItemType::Pearl, 6));
+        assert_eq!(alice.pending.len(), 1);
+
+        // pays and waits on the settle code
+        let accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        assert_eq!(bob_inv.gold, 300);
+        assert!(!bob_inv.has_atleast(ItemType::Pearl, 1));
+        assert_eq!(bob.receipts.len(), 1);
+
+        let settle = alice.complete_trade(&accept, &mut alice_inv).unwrap();
+        assert!(settle.delivered);
+        assert_eq!(alice_inv.gold, 200);
+        assert!(alice.pending.is_empty());
+
+        let settle_code = alice.last_code.clone().unwrap();
+        let received = bob.redeem_settle(&settle_code, &mut bob_inv).unwrap();
+        assert_eq!(received, Drop::new_item(ItemType::Pearl, 10));
+        assert!(bob_inv.has_atleast(ItemType::Pearl, 10));
+        assert!(bob.receipts.is_empty());
+    }
+
+    #[test]
+    fn single_use() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.give_item(ItemType::Stick, 2).unwrap();
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 100;
+
+        let offer = alice
+            .create_offer(
+                Drop::new_item(ItemType::Stick, 1),
+                Drop::new_gold(5),
+                &mut alice_inv,
+            )
+            .unwrap();
+
+        let accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        assert!(matches!(
+            bob.accept_offer(&offer, &mut bob_inv),
+            Err(Error::TradeAlreadyUsed)
+        ));
+        assert_eq!(bob_inv.gold, 95);
+
+        alice.complete_trade(&accept, &mut alice_inv).unwrap();
+        let settle_code = alice.last_code.clone().unwrap();
+
+        // a repeated receipt gets the same settle code and pays nothing more
+        let again = alice.complete_trade(&accept, &mut alice_inv).unwrap();
+        assert!(again.delivered);
+        assert_eq!(alice.last_code.clone().unwrap(), settle_code);
+        assert_eq!(alice_inv.gold, 5);
+
+        bob.redeem_settle(&settle_code, &mut bob_inv).unwrap();
+        assert!(matches!(
+            bob.redeem_settle(&settle_code, &mut bob_inv),
+            Err(Error::TradeAlreadyUsed)
+        ));
+        assert!(bob_inv.has_atleast(ItemType::Stick, 1));
+        assert!(!bob_inv.has_atleast(ItemType::Stick, 2));
+    }
+
+    #[test]
+    fn shared_offer_pays_once() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.give_item(ItemType::Stick, 1).unwrap();
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 100;
+
+        let mut carol = trader(3);
+        let mut carol_inv = Inventory::new();
+        carol_inv.gold = 100;
+
+        let offer = alice
+            .create_offer(
+                Drop::new_item(ItemType::Stick, 1),
+                Drop::new_gold(5),
+                &mut alice_inv,
+            )
+            .unwrap();
+
+        let bob_accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        let carol_accept = carol.accept_offer(&offer, &mut carol_inv).unwrap();
+
+        assert!(
+            alice
+                .complete_trade(&bob_accept, &mut alice_inv)
+                .unwrap()
+                .delivered
+        );
+        let bob_settle = alice.last_code.clone().unwrap();
+        assert!(
+            !alice
+                .complete_trade(&carol_accept, &mut alice_inv)
+                .unwrap()
+                .delivered
+        );
+        let carol_settle = alice.last_code.clone().unwrap();
+        assert_eq!(alice_inv.gold, 5);
+
+        bob.redeem_settle(&bob_settle, &mut bob_inv).unwrap();
+        assert!(bob_inv.has_atleast(ItemType::Stick, 1));
+
+        // carol gets the gold back, not the stick
+        assert_eq!(
+            carol.redeem_settle(&carol_settle, &mut carol_inv).unwrap(),
+            Drop::new_gold(5)
+        );
+        assert_eq!(carol_inv.gold, 100);
+        assert!(!carol_inv.has_atleast(ItemType::Stick, 1));
+
+        // settle codes only work for the save they were made for
+        assert!(matches!(
+            carol.redeem_settle(&bob_settle, &mut carol_inv),
+            Err(Error::UnknownTrade)
+        ));
+    }
+
+    #[test]
+    fn hand_built_codes_rejected() {
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 100;
+
+        // An offer from a save that doesn't exist, and a settle for it
+        let offer = TradeCode::Offer(TradeOffer {
+            from: 9,
+            nonce: 0,
+            give: Drop::new_item(ItemType::DragonEgg, 100),
+            want: Drop::new_gold(1),
+            lock: 0x1234,
+        })
+        .encode();
+        bob.accept_offer(&offer, &mut bob_inv).unwrap();
+
+        let settle = TradeCode::Settle(TradeSettle {
+            offer_from: 9,
+            nonce: 0,
+            accepted_by: 2,
+            delivered: true,
+            proof: TradeProof::default(),
+        })
+        .encode();
+        assert!(matches!(
+            bob.redeem_settle(&settle, &mut bob_inv),
+            Err(Error::TradeCodeForged)
+        ));
+        assert!(!bob_inv.has_atleast(ItemType::DragonEgg, 1));
+        assert_eq!(bob.receipts.len(), 1);
+    }
+
+    #[test]
+    fn edited_codes_rejected() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.give_item(ItemType::Pearl, 10).unwrap();
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 100;
+
+        let mut carol = trader(3);
+        let mut carol_inv = Inventory::new();
+        carol_inv.gold = 100;
+
+        let offer = alice
+            .create_offer(
+                Drop::new_item(ItemType::Pearl, 1),
+                Drop::new_gold(5),
+                &mut alice_inv,
+            )
+            .unwrap();
+
+        // Carol asks for more than was offered
+        let TradeCode::Offer(real) = TradeCode::decode(&offer).unwrap() else {
+            panic!();
+        };
+        let greedy = TradeCode::Offer(TradeOffer {
+            give: Drop::new_item(ItemType::Pearl, 10),
+            ..real
+        })
+        .encode();
+
+        let bob_accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        let carol_accept = carol.accept_offer(&greedy, &mut carol_inv).unwrap();
+
+        let bob_settle = alice.complete_trade(&bob_accept, &mut alice_inv).unwrap();
+        let carol_settle = alice.complete_trade(&carol_accept, &mut alice_inv).unwrap();
+        assert!(!carol_settle.delivered);
+
+        // Bob's delivery moved to Carol, and Carol's refund moved to Bob
+        let moved = |settle: TradeSettle, accepted_by: u64| {
+            TradeCode::Settle(TradeSettle {
+                accepted_by,
+                ..settle
+            })
+            .encode()
+        };
+        assert!(matches!(
+            carol.redeem_settle(&moved(bob_settle, 3), &mut carol_inv),
+            Err(Error::TradeCodeForged)
+        ));
+        assert!(matches!(
+            bob.redeem_settle(&moved(carol_settle, 2), &mut bob_inv),
+            Err(Error::TradeCodeForged)
+        ));
+
+        // A delivery turned into a refund
+        let refund = TradeCode::Settle(TradeSettle {
+            delivered: false,
+            ..bob_settle
+        })
+        .encode();
+        assert!(matches!(
+            bob.redeem_settle(&refund, &mut bob_inv),
+            Err(Error::TradeCodeForged)
+        ));
+
+        // The real settle still works. Carol's receipt isn't for the offer Alice made, so nothing pays it.
+        bob.redeem_settle(&TradeCode::Settle(bob_settle).encode(), &mut bob_inv)
+            .unwrap();
+        assert!(bob_inv.has_atleast(ItemType::Pearl, 1));
+        assert!(matches!(
+            carol.redeem_settle(&TradeCode::Settle(carol_settle).encode(), &mut carol_inv),
+            Err(Error::TradeCodeForged)
+        ));
+        assert!(!carol_inv.has_atleast(ItemType::Pearl, 1));
+    }
+
+    #[test]
+    fn validation() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.give_item(ItemType::Rock, 3).unwrap();
+
+        assert!(matches!(
+            alice.create_offer(
+                Drop::new_item(ItemType::Rock, 4),
+                Drop::new_gold(1),
+                &mut alice_inv
+            ),
+            Err(Error::NotEnoughItems)
+        ));
+
+        let offer = alice
+            .create_offer(
+                Drop::new_item(ItemType::Rock, 3),
+                Drop::new_gold(50),
+                &mut alice_inv,
+            )
+            .unwrap();
+        assert!(matches!(
+            alice.accept_offer(&offer, &mut alice_inv),
+            Err(Error::TradeOwnOffer)
+        ));
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.gold = 10;
+        assert!(matches!(
+            bob.accept_offer(&offer, &mut bob_inv),
+            Err(Error::NotEnoughItems)
+        ));
+        assert_eq!(bob_inv.gold, 10);
+
+        // someone elses accept code
+        let stranger = TradeCode::Accept(TradeAccept {
+            offer_from: 3,
+            nonce: 0,
+            accepted_by: 2,
+        })
+        .encode();
+        assert!(matches!(
+            alice.complete_trade(&stranger, &mut alice_inv),
+            Err(Error::UnknownTrade)
+        ));
+    }
+
+    #[test]
+    fn cancel_refunds() {
+        let mut alice = trader(1);
+        let mut inv = Inventory::new();
+        inv.gold = 100;
+
+        alice
+            .create_offer(
+                Drop::new_gold(60),
+                Drop::new_item(ItemType::Berry, 5),
+                &mut inv,
+            )
+            .unwrap();
+        assert_eq!(inv.gold, 40);
+
+        let nonce = alice.pending[0].nonce;
+        alice.cancel_offer(nonce, &mut inv).unwrap();
+        assert_eq!(inv.gold, 100);
+        assert!(alice.cancel_offer(nonce, &mut inv).is_err());
+    }
+
+    #[test]
+    fn cancel_after_receipt() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.gold = 100;
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.give_item(ItemType::Berry, 5).unwrap();
+
+        let offer = alice
+            .create_offer(
+                Drop::new_gold(60),
+                Drop::new_item(ItemType::Berry, 5),
+                &mut alice_inv,
+            )
+            .unwrap();
+        let nonce = alice.pending[0].nonce;
+
+        let accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        alice.complete_trade(&accept, &mut alice_inv).unwrap();
+
+        // completed, so there is nothing left to refund
+        assert!(matches!(
+            alice.cancel_offer(nonce, &mut alice_inv),
+            Err(Error::UnknownTrade)
+        ));
+        assert_eq!(alice_inv.gold, 40);
+    }
+
+    #[test]
+    fn receipt_after_cancel() {
+        let mut alice = trader(1);
+        let mut alice_inv = Inventory::new();
+        alice_inv.gold = 100;
+
+        let mut bob = trader(2);
+        let mut bob_inv = Inventory::new();
+        bob_inv.give_item(ItemType::Berry, 5).unwrap();
+
+        let offer = alice
+            .create_offer(
+                Drop::new_gold(60),
+                Drop::new_item(ItemType::Berry, 5),
+                &mut alice_inv,
+            )
+            .unwrap();
+        let nonce = alice.pending[0].nonce;
+        alice.cancel_offer(nonce, &mut alice_inv).unwrap();
+
+        let accept = bob.accept_offer(&offer, &mut bob_inv).unwrap();
+        let settle = alice.complete_trade(&accept, &mut alice_inv).unwrap();
+        assert!(!settle.delivered);
+        assert_eq!(alice_inv.gold, 100);
+        assert!(!alice_inv.has_atleast(ItemType::Berry, 1));
+
+        let settle_code = alice.last_code.clone().unwrap();
+        bob.redeem_settle(&settle_code, &mut bob_inv).unwrap();
+        assert!(bob_inv.has_atleast(ItemType::Berry, 5));
+        assert_eq!(bob_inv.gold, 0);
+    }
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut alice = trader(0x1234_5678_9abc);
+        let mut inv = Inventory::new();
+        inv.give_item(ItemType::Pearl, 5).unwrap();
+        alice
+            .create_offer(
+                Drop::new_item(ItemType::Pearl, 5),
+                Drop::new_gold(100),
+                &mut inv,
+            )
+            .unwrap();
+        alice.accepted.push((7, 3));
+        alice.delivered.push((0, 9));
+        alice.receipts.push(TradeOffer {
+            from: 7,
+            nonce: 3,
+            give: Drop::new_item(ItemType::Rock, 2),
+            want: Drop::new_gold(4),
+            lock: 0xabcd,
+        });
+
+        alice.save_file_write("".into(), &mut save_file).unwrap();
+        let loaded = TradeLog::save_file_load("".into(), &save_file).unwrap();
+
+        assert_eq!(loaded.save_id, alice.save_id);
+        assert_eq!(loaded.secret, alice.secret);
+        assert_eq!(loaded.next_nonce, alice.next_nonce);
+        assert_eq!(loaded.pending, alice.pending);
+        assert_eq!(loaded.delivered, alice.delivered);
+        assert_eq!(loaded.receipts, alice.receipts);
+        assert_eq!(loaded.accepted, alice.accepted);
+    }
+
+    #[test]
+    fn fork_save() {
+        let mut save_file = SaveFile::new();
+        save_file.save_i64("st.g", 12);
+
+        let mut alice = trader(1);
+        let mut inv = Inventory::new();
+        inv.give_item(ItemType::Pearl, 5).unwrap();
+        alice
+            .create_offer(
+                Drop::new_item(ItemType::Pearl, 5),
+                Drop::new_gold(100),
+                &mut inv,
+            )
+            .unwrap();
+        alice.accepted.push((7, 3));
+        alice.save_file_write("".into(), &mut save_file).unwrap();
+
+        TradeLog::fork_save(&mut save_file, 2).unwrap();
+        let copy = TradeLog::save_file_load("".into(), &save_file).unwrap();
+
+        assert_eq!(copy.save_id, 2);
+        assert_eq!(copy.secret, 0);
+        assert!(copy.pending.is_empty());
+        assert!(copy.accepted.is_empty());
+        assert_eq!(save_file.load_i64("st.g").unwrap(), 12);
+    }
+}
@@ -0,0 +1,313 @@
+use crate::trade::*;
+
+/// Digits of the accepting save's id that a proof signs
+pub const SEAL_DIGITS: usize = 2;
+
+/// Each digit is 0..SEAL_BASE, so a digit's chain is SEAL_BASE - 1 hashes long
+const SEAL_BASE: u64 = 256;
+
+/// The checksum chain is as long as every digit chain together
+const SEAL_CHECK_LEN: u64 = SEAL_DIGITS as u64 * (SEAL_BASE - 1);
+
+/// Proof in a settle code that it came from the save that made the offer.
+/// A one time hash chain signature (Winternitz) of the accepting save's id, under the key of the settle's outcome.
+/// The keys come from the offering save's secret, and the offer's lock commits to both of them.
+/// Moving a proof to another save id, or to the other outcome, means hashing a chain backwards.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct TradeProof {
+    /// One value per digit, then the checksum
+    pub chains: [u64; SEAL_DIGITS + 1],
+
+    /// Key of the other outcome, so the lock can be rebuilt
+    pub other_key: u64,
+}
+
+/// Lock for an offer code. Commits to the offer's terms and both outcome keys.
+pub fn offer_lock(secret: u64, offer: &TradeOffer) -> u64 {
+    lock_of(
+        offer,
+        outcome_key(secret, offer.from, offer.nonce, true),
+        outcome_key(secret, offer.from, offer.nonce, false),
+    )
+}
+
+/// Proof for a settle code. Only the save holding secret can make one.
+pub fn seal_settle(secret: u64, settle: &TradeSettle) -> TradeProof {
+    let seeds = seeds(secret, settle.offer_from, settle.nonce, settle.delivered);
+    let steps = steps(settle.accepted_by);
+
+    let mut chains = [0; SEAL_DIGITS + 1];
+    for (i, chain) in chains.iter_mut().enumerate() {
+        *chain = hash_chain(seeds[i], steps[i]);
+    }
+
+    TradeProof {
+        chains,
+        other_key: outcome_key(secret, settle.offer_from, settle.nonce, !settle.delivered),
+    }
+}
+
+/// True if the settle's proof opens the lock of the offer it settles
+pub fn verify_settle(offer: &TradeOffer, settle: &TradeSettle) -> bool {
+    if offer.from != settle.offer_from || offer.nonce != settle.nonce {
+        return false;
+    }
+
+    let steps = steps(settle.accepted_by);
+    let mut ends = [0; SEAL_DIGITS + 1];
+    for (i, end) in ends.iter_mut().enumerate() {
+        *end = hash_chain(settle.proof.chains[i], chain_len(i) - steps[i]);
+    }
+
+    let key = key_of(&ends);
+    let lock = if settle.delivered {
+        lock_of(offer, key, settle.proof.other_key)
+    } else {
+        lock_of(offer, settle.proof.other_key, key)
+    };
+    lock == offer.lock
+}
+
+/// Hashes each of the outcome's chains to its end
+fn outcome_key(secret: u64, from: u64, nonce: u64, delivered: bool) -> u64 {
+    let seeds = seeds(secret, from, nonce, delivered);
+
+    let mut ends = [0; SEAL_DIGITS + 1];
+    for (i, end) in ends.iter_mut().enumerate() {
+        *end = hash_chain(seeds[i], chain_len(i));
+    }
+    key_of(&ends)
+}
+
+fn seeds(secret: u64, from: u64, nonce: u64, delivered: bool) -> [u64; SEAL_DIGITS + 1] {
+    let mut ret = [0; SEAL_DIGITS + 1];
+    for (i, seed) in ret.iter_mut().enumerate() {
+        *seed = hash(
+            format!(
+                "seed-{:x}-{:x}-{:x}-{}-{}",
+                secret, from, nonce, delivered, i
+            )
+            .as_bytes(),
+        );
+    }
+    ret
+}
+
+fn chain_len(i: usize) -> u64 {
+    if i < SEAL_DIGITS {
+        SEAL_BASE - 1
+    } else {
+        SEAL_CHECK_LEN
+    }
+}
+
+/// How far along each chain a proof for this save id is. The checksum goes down as the digits go up,
+/// so no other id can be reached by only hashing forward.
+fn steps(accepted_by: u64) -> [u64; SEAL_DIGITS + 1] {
+    let digits = hash(format!("id-{:x}", accepted_by).as_bytes());
+
+    let mut ret = [0; SEAL_DIGITS + 1];
+    for (i, step) in ret.iter_mut().take(SEAL_DIGITS).enumerate() {
+        *step = (digits >> (i * 8)) % SEAL_BASE;
+    }
+    ret[SEAL_DIGITS] = ret[..SEAL_DIGITS]
+        .iter()
+        .map(|digit| SEAL_BASE - 1 - digit)
+        .sum();
+    ret
+}
+
+fn key_of(ends: &[u64]) -> u64 {
+    let ends: Vec<String> = ends.iter().map(|end| format!("{:x}", end)).collect();
+    hash(format!("key-{}", ends.join("-")).as_bytes())
+}
+
+fn lock_of(offer: &TradeOffer, delivered_key: u64, refunded_key: u64) -> u64 {
+    hash(
+        format!(
+            "lock-{:x}-{:x}-{}-{}-{:x}-{:x}",
+            offer.from,
+            offer.nonce,
+            encode_drop(&offer.give),
+            encode_drop(&offer.want),
+            delivered_key,
+            refunded_key
+        )
+        .as_bytes(),
+    )
+}
+
+fn hash_chain(start: u64, steps: u64) -> u64 {
+    let mut ret = start;
+    for _ in 0..steps {
+        ret = hash(&ret.to_le_bytes());
+    }
+    ret
+}
+
+/// First 8 bytes of the SHA-256
+fn hash(data: &[u8]) -> u64 {
+    let digest = sha256(data);
+    u64::from_le_bytes(digest[0..8].try_into().unwrap())
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (word, bytes) in w.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for (k, w) in SHA256_K.iter().zip(w) {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(*k)
+                .wrapping_add(w);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (value, add) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut ret = [0u8; 32];
+    for (bytes, value) in ret.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{drop_table::*, item::*};
+
+    fn hex(data: &[u8]) -> String {
+        data.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn sha256_known() {
+        assert_eq!(
+            hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        // two blocks
+        assert_eq!(
+            hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn proof_bound_to_save_and_outcome() {
+        let mut offer = TradeOffer {
+            from: 1,
+            nonce: 4,
+            give: Drop::new_item(ItemType::Pearl, 10),
+            want: Drop::new_gold(200),
+            lock: 0,
+        };
+        offer.lock = offer_lock(77, &offer);
+
+        let mut settle = TradeSettle {
+            offer_from: 1,
+            nonce: 4,
+            accepted_by: 2,
+            delivered: true,
+            proof: TradeProof::default(),
+        };
+        settle.proof = seal_settle(77, &settle);
+        assert!(verify_settle(&offer, &settle));
+
+        // another save, the other outcome, or other terms
+        assert!(!verify_settle(
+            &offer,
+            &TradeSettle {
+                accepted_by: 3,
+                ..settle
+            }
+        ));
+        assert!(!verify_settle(
+            &offer,
+            &TradeSettle {
+                delivered: false,
+                ..settle
+            }
+        ));
+        assert!(!verify_settle(
+            &TradeOffer {
+                give: Drop::new_item(ItemType::Pearl, 11),
+                ..offer
+            },
+            &settle
+        ));
+
+        // a different secret can't make one
+        let mut forged = settle;
+        forged.proof = seal_settle(78, &settle);
+        assert!(!verify_settle(&offer, &forged));
+
+        // refunds have their own proof
+        let mut refund = TradeSettle {
+            delivered: false,
+            ..settle
+        };
+        refund.proof = seal_settle(77, &refund);
+        assert!(verify_settle(&offer, &refund));
+    }
+}
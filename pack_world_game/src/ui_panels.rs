@@ -2,31 +2,50 @@ use crate::{
     account_system::*,
     game_mode::*,
     pack::*,
-    state::{assets::*, inventory::*, *},
+    save_file::*,
+    state::{assets::*, inventory::*, player_state::*, *},
     tile::*,
     UpdateSignal,
 };
 use elara_engine::{color::*, networking::*, platform_api::*, typeface::*, ui::*, vectors::*};
 
+pub mod achievements_panel;
+pub mod collection_panel;
 pub mod create_account_panel;
 pub mod debug_panel;
 pub mod home_panel;
+pub mod load_report_panel;
 pub mod nav_tabs_panel;
 pub mod open_pack_panel;
 pub mod pack_details_panel;
 pub mod pairing_code_panel;
+pub mod profile_panel;
+pub mod quest_log_panel;
+pub mod restore_panel;
+pub mod save_transfer_panel;
 pub mod shop_panel;
+pub mod stats_panel;
 pub mod tile_library_panel;
+pub mod trade_panel;
 
+use achievements_panel::*;
+use collection_panel::*;
 use create_account_panel::*;
 use debug_panel::*;
 use home_panel::*;
+use load_report_panel::*;
 use nav_tabs_panel::*;
 use open_pack_panel::*;
 use pack_details_panel::*;
 use pairing_code_panel::*;
+use profile_panel::*;
+use quest_log_panel::*;
+use restore_panel::*;
+use save_transfer_panel::*;
 use shop_panel::*;
+use stats_panel::*;
 use tile_library_panel::*;
+use trade_panel::*;
 
 pub enum UIPanel {
     NavTabs(NavTabsPanel),
@@ -38,6 +57,15 @@ pub enum UIPanel {
     PackDetails(PackDetailsData),
     CreateAccount(CreateAccountPanel),
     PairingCode(PairingCodePanel),
+    QuestLog(QuestLogPanel),
+    Achievements(AchievementsPanel),
+    Collection(CollectionPanel),
+    Stats(StatsPanel),
+    Trade(TradePanel),
+    SaveTransfer(SaveTransferPanel),
+    Profile(ProfilePanel),
+    Restore(RestorePanel),
+    LoadReport(LoadReportPanel),
 }
 
 impl UIPanel {
@@ -47,6 +75,9 @@ impl UIPanel {
         networking_system: &mut NetworkingSystem,
         ui_state: &mut UIFrameState,
         inventory: &Inventory,
+        player_state: &PlayerState,
+        save_slots: &SaveSlots,
+        save_history: &SaveHistory,
         assets: &mut Assets,
         ui_context: &mut UIContext,
         platform_api: &PlatformApi,
@@ -66,23 +97,63 @@ impl UIPanel {
                 ui_context,
                 platform_api,
             ),
-            UIPanel::OpenPack(state) => {
-                state.update(ui_state, inventory, assets, ui_context, platform_api)
-            }
+            UIPanel::OpenPack(state) => state.update(
+                ui_state,
+                player_state,
+                inventory,
+                assets,
+                ui_context,
+                platform_api,
+            ),
             UIPanel::DebugPanel(state) => state.update(ui_state, inventory, assets, ui_context),
-            UIPanel::PackDetails(state) => state.update(ui_state, inventory, assets, ui_context),
+            UIPanel::PackDetails(state) => {
+                state.update(ui_state, player_state, inventory, assets, ui_context)
+            }
             UIPanel::CreateAccount(state) => {
                 state.update(networking_system, ui_state, inventory, assets, ui_context)
             }
             UIPanel::PairingCode(state) => {
                 state.update(networking_system, ui_state, inventory, assets, ui_context)
             }
+            UIPanel::QuestLog(state) => {
+                state.update(ui_state, player_state, inventory, assets, ui_context)
+            }
+            UIPanel::Achievements(state) => {
+                state.update(ui_state, player_state, assets, ui_context)
+            }
+            UIPanel::Collection(state) => state.update(
+                ui_state,
+                player_state,
+                inventory,
+                assets,
+                ui_context,
+                platform_api,
+            ),
+            UIPanel::Stats(state) => state.update(ui_state, player_state, ui_context),
+            UIPanel::Trade(state) => state.update(ui_state, player_state, inventory, ui_context),
+            UIPanel::SaveTransfer(state) => state.update(ui_state, ui_context, platform_api),
+            UIPanel::Profile(state) => state.update(ui_state, save_slots, ui_context),
+            UIPanel::Restore(state) => {
+                state.update(ui_state, save_history, ui_context, platform_api)
+            }
+            UIPanel::LoadReport(state) => state.update(ui_state, save_history, ui_context),
         }
     }
 
     pub fn owns_screen(&mut self) -> bool {
         match self {
-            UIPanel::CreateAccount(_) | UIPanel::PairingCode(_) | UIPanel::PackDetails(_) => true,
+            UIPanel::CreateAccount(_)
+            | UIPanel::PairingCode(_)
+            | UIPanel::PackDetails(_)
+            | UIPanel::QuestLog(_)
+            | UIPanel::Achievements(_)
+            | UIPanel::Collection(_)
+            | UIPanel::Stats(_)
+            | UIPanel::Trade(_)
+            | UIPanel::SaveTransfer(_)
+            | UIPanel::Profile(_)
+            | UIPanel::Restore(_)
+            | UIPanel::LoadReport(_) => true,
             _ => false,
         }
     }
@@ -109,6 +180,15 @@ pub enum CreatePanelData {
     PackDetails { pack_id: PackID },
     CreateAccount,
     PairingCode { email: String },
+    QuestLog,
+    Achievements,
+    Collection,
+    Stats,
+    Trade,
+    SaveTransfer,
+    Profile,
+    Restore,
+    LoadReport,
 }
 
 impl CreatePanelData {
@@ -134,6 +214,15 @@ impl CreatePanelData {
             CreatePanelData::PairingCode { email } => {
                 UIPanel::PairingCode(PairingCodePanel::new(email.clone()))
             }
+            CreatePanelData::QuestLog => UIPanel::QuestLog(QuestLogPanel {}),
+            CreatePanelData::Achievements => UIPanel::Achievements(AchievementsPanel {}),
+            CreatePanelData::Collection => UIPanel::Collection(CollectionPanel {}),
+            CreatePanelData::Stats => UIPanel::Stats(StatsPanel {}),
+            CreatePanelData::Trade => UIPanel::Trade(TradePanel::new()),
+            CreatePanelData::SaveTransfer => UIPanel::SaveTransfer(SaveTransferPanel::new()),
+            CreatePanelData::Profile => UIPanel::Profile(ProfilePanel::new()),
+            CreatePanelData::Restore => UIPanel::Restore(RestorePanel::new()),
+            CreatePanelData::LoadReport => UIPanel::LoadReport(LoadReportPanel {}),
         }
     }
 }
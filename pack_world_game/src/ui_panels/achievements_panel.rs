@@ -0,0 +1,131 @@
+use crate::{
+    achievement::*,
+    state::{assets::*, player_state::*, *},
+    ui_panels::*,
+    UpdateSignal,
+};
+use elara_engine::{color::*, rect::*, typeface::*, ui::*, vectors::*};
+
+pub struct AchievementsPanel {}
+
+impl AchievementsPanel {
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
+        assets: &mut Assets,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(900.0, 800.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        let unlocked_count = ALL_ACHIEVEMENTS
+            .iter()
+            .filter(|id| player_state.achievements.is_unlocked(**id))
+            .count();
+
+        draw_text(
+            &format!(
+                "Achievements {} / {}",
+                unlocked_count,
+                ALL_ACHIEVEMENTS.len()
+            ),
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // gallery
+        {
+            let icon_size: f64 = 80.0;
+
+            let grid_rects = get_grid_layout(GridLayoutInfo {
+                bounds_width: 860.0,
+                col_count: 2,
+                cell_height: 100.0,
+                gutter: 20.0,
+                cells_count: ALL_ACHIEVEMENTS.len() as i32,
+            });
+
+            for (i, id) in ALL_ACHIEVEMENTS.iter().enumerate() {
+                let mut cell = grid_rects[i];
+                cell.translate(VecTwo::new(10.0, 120.0));
+
+                let unlocked = player_state.achievements.is_unlocked(*id);
+                let secret = id.hidden() && !unlocked;
+
+                let icon_rect = Rect::new_top_size(cell.top_left, icon_size, icon_size);
+                if secret {
+                    draw_image(
+                        icon_rect,
+                        assets.image_question_mark.gl_id.unwrap(),
+                        COLOR_WHITE,
+                        ui_state,
+                        ui_context,
+                    );
+                } else {
+                    let icon_color = if unlocked {
+                        COLOR_WHITE
+                    } else {
+                        Color::new(1.0, 1.0, 1.0, 0.2)
+                    };
+
+                    draw_image(icon_rect, id.icon(assets), icon_color, ui_state, ui_context);
+                }
+
+                let (title, desc) = if secret {
+                    ("???".to_string(), "Hidden".to_string())
+                } else {
+                    (id.title().to_string(), id.description())
+                };
+
+                let text_color = if unlocked {
+                    COLOR_WHITE
+                } else {
+                    *THEME_TEXT_MUT
+                };
+
+                draw_text(
+                    &title,
+                    cell.top_left + VecTwo::new(icon_size + 15.0, 30.0),
+                    text_color,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+                draw_text(
+                    &desc,
+                    cell.top_left + VecTwo::new(icon_size + 15.0, 60.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    drop_table::*,
+    item::*,
+    pack::{pack_id::*, *},
+    state::{assets::*, inventory::*, player_state::*, *},
+    tile::*,
+    ui_panels::*,
+    UpdateSignal,
+};
+use elara_engine::{color::*, platform_api::*, rect::*, time::*, typeface::*, ui::*, vectors::*};
+
+pub struct CollectionPanel {}
+
+impl CollectionPanel {
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
+        inventory: &Inventory,
+        assets: &mut Assets,
+        ui_context: &mut UIContext,
+        platform_api: &PlatformApi,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+        let collection = &player_state.collection;
+
+        let all_items: Vec<ItemType> = ALL_ITEM_TYPES
+            .iter()
+            .copied()
+            .chain(
+                ALL_TILE_TYPES
+                    .iter()
+                    .map(|tile_type| ItemType::Tile(*tile_type)),
+            )
+            .collect();
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 850.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        // title
+        {
+            let collected = all_items
+                .iter()
+                .filter(|item_type| collection.get(item_type).is_some())
+                .count();
+
+            draw_text(
+                &format!(
+                    "Collection {} / {} ({:.0}%)",
+                    collected,
+                    all_items.len(),
+                    (collected as f64 / all_items.len() as f64) * 100.0
+                ),
+                VecTwo::new(10.0, 40.0),
+                COLOR_WHITE,
+                &ui_context.font_header.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // pack completion
+        for (i, pack_id) in ALL_PACKS.iter().enumerate() {
+            let (collected, total) = collection.pack_completion(*pack_id);
+            let perc = if total > 0 {
+                (collected as f64 / total as f64) * 100.0
+            } else {
+                0.0
+            };
+
+            let col = if collected == total {
+                COLOR_GREEN
+            } else {
+                *THEME_TEXT
+            };
+
+            draw_text(
+                &format!(
+                    "{} {} / {} ({:.0}%)",
+                    pack_id.get_pack_info().display_name,
+                    collected,
+                    total,
+                    perc
+                ),
+                VecTwo::new(
+                    10.0 + ((i % 3) as f64 * 320.0),
+                    130.0 + ((i / 3) as f64 * 30.0),
+                ),
+                col,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        // item grid
+        let mut hovering: Option<ItemType> = None;
+        {
+            let icon_size: f64 = 70.0;
+            let col_count = 10;
+
+            let grid_rects = get_grid_layout(GridLayoutInfo {
+                bounds_width: col_count as f64 * (icon_size + 20.0),
+                col_count: col_count,
+                cell_height: icon_size + 20.0,
+                gutter: 10.0,
+                cells_count: all_items.len() as i32,
+            });
+
+            for (i, item_type) in all_items.iter().enumerate() {
+                let mut cell = grid_rects[i];
+                cell.translate(VecTwo::new(40.0, 210.0));
+
+                draw_drop_icon(
+                    icon_size,
+                    cell.get_center(),
+                    &Drop::new_item(*item_type, 1),
+                    ui_state,
+                    ui_context,
+                    assets,
+                    inventory,
+                );
+
+                let mut rect_offset = cell;
+                rect_offset.translate(ui_state.get_origin());
+                if rect_offset.contains(ui_context.mouse.pos) {
+                    hovering = Some(*item_type);
+                }
+            }
+        }
+
+        // hover details
+        if let Some(item_type) = hovering {
+            let origin = VecTwo::new(10.0, 720.0);
+
+            let lines: Vec<String> = match collection.get(&item_type) {
+                Some(entry) => {
+                    let since = Time::new(TimeUnit::MilliSeconds(
+                        (platform_api.epoch_time_ms)() - entry.first_time,
+                    ));
+
+                    let rarest = match entry.rarest_pull {
+                        Some(chance) => format!("Rarest pull {:.2}%", chance * 100.0),
+                        None => "Never pulled from a pack".into(),
+                    };
+
+                    vec![
+                        item_type.user_title().to_string(),
+                        format!(
+                            "First found {} ago from {}",
+                            since.display(),
+                            entry.first_source.display()
+                        ),
+                        format!("{} total  {}", entry.total, rarest),
+                    ]
+                }
+                None => vec!["???".into(), "Not found yet".into()],
+            };
+
+            for (i, line) in lines.iter().enumerate() {
+                draw_text(
+                    line,
+                    origin + VecTwo::new(0.0, i as f64 * 30.0),
+                    if i == 0 { COLOR_WHITE } else { *THEME_TEXT_MUT },
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
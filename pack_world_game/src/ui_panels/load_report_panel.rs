@@ -0,0 +1,92 @@
+use crate::{save_file::*, ui_panels::*, UpdateSignal};
+use elara_engine::{color::*, rect::*, typeface::*, ui::*, vectors::*};
+
+/// Lines shown before the rest are cut off
+const MAX_LINES: usize = 13;
+
+/// Tells the player which parts of their save couldn't be loaded
+pub struct LoadReportPanel {}
+
+impl LoadReportPanel {
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        save_history: &SaveHistory,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 850.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Save Recovered",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        let intro = [
+            "Part of your save couldn't be loaded. Everything else is back.",
+            "The broken parts are kept in case a later update can recover them.",
+        ];
+        for (i, line) in intro.iter().enumerate() {
+            draw_text(
+                line,
+                VecTwo::new(10.0, 130.0 + i as f64 * 30.0),
+                *THEME_TEXT_MUT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        if let Some(report) = &save_history.last_load {
+            let lines: Vec<String> = report.display().lines().map(|l| l.to_string()).collect();
+
+            for (i, line) in lines.iter().take(MAX_LINES).enumerate() {
+                draw_text(
+                    line,
+                    VecTwo::new(10.0, 220.0 + i as f64 * 40.0),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+
+            if lines.len() > MAX_LINES {
+                draw_text(
+                    &format!("and {} more", lines.len() - MAX_LINES),
+                    VecTwo::new(10.0, 220.0 + MAX_LINES as f64 * 40.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
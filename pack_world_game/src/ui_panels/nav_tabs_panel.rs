@@ -69,6 +69,90 @@ impl NavTabsPanel {
                 new_mode: GameModeKind::Inventory,
             });
         }
+        if draw_text_button(
+            "Quests",
+            VecTwo::new(385.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::QuestLog));
+        }
+        if draw_text_button(
+            "Achievements",
+            VecTwo::new(500.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::Achievements));
+        }
+        if draw_text_button(
+            "Collection",
+            VecTwo::new(690.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::Collection));
+        }
+        if draw_text_button(
+            "Stats",
+            VecTwo::new(860.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::Stats));
+        }
+        if draw_text_button(
+            "Trade",
+            VecTwo::new(960.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::Trade));
+        }
+        if draw_text_button(
+            "Transfer",
+            VecTwo::new(1060.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::SaveTransfer));
+        }
+        if draw_text_button(
+            "Profiles",
+            VecTwo::new(1200.0, 40.0),
+            &ui_context.font_nav.clone(),
+            false,
+            None,
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            ret.push(UpdateSignal::PushPanel(CreatePanelData::Profile));
+        }
 
         {
             let twitter =
@@ -2,7 +2,7 @@ use crate::{
     drop_table::*,
     item::*,
     pack::*,
-    state::{assets, inventory::*, *},
+    state::{assets, inventory::*, player_state::*, *},
     ui_panels::{nav_tabs_panel::*, *},
     UpdateSignal,
 };
@@ -38,6 +38,7 @@ impl OpenPackPanel {
     pub fn update(
         &mut self,
         mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
         inventory: &Inventory,
         assets: &mut Assets,
         ui_context: &mut UIContext,
@@ -66,8 +67,12 @@ impl OpenPackPanel {
                 ui_context,
             ) {
                 // pull item from pack and give
-                let pull = pack_info.pull(platform_api);
-                update_signals.push(UpdateSignal::GiveDrop(pull));
+                let misses = player_state.pity.misses(self.pack_id);
+                let pull = pack_info.pull(misses, platform_api);
+                update_signals.push(UpdateSignal::GiveDrop {
+                    drop: pull,
+                    source: DropSource::Pack(self.pack_id),
+                });
 
                 self.items_remaining -= 1;
 
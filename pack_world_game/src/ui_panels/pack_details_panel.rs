@@ -4,7 +4,7 @@ use crate::{
     drop_table::*,
     item::*,
     pack::*,
-    state::{assets, inventory::*, *},
+    state::{assets, inventory::*, player_state::*, *},
     ui_panels::{nav_tabs_panel::*, *},
     UpdateSignal,
 };
@@ -24,6 +24,7 @@ impl PackDetailsData {
     pub fn update(
         &mut self,
         mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
         inventory: &Inventory,
         assets: &mut Assets,
         ui_context: &mut UIContext,
@@ -71,9 +72,9 @@ impl PackDetailsData {
                 ui_state,
                 ui_context,
             );
-            let list = get_fixed_table(pack_info.table_id).list_drops();
+            let list = pack_info.drop_odds();
 
-            for (j, drop) in list.iter().enumerate() {
+            for (j, odds) in list.iter().enumerate() {
                 let mut cost_origin =
                     desc_origin + VecTwo::new((icon_size + 30.0) * j as f64, y + 40.0);
                 cost_origin.x += 40.0;
@@ -82,13 +83,48 @@ impl PackDetailsData {
                 draw_drop_icon(
                     icon_size,
                     cost_origin,
-                    &drop,
+                    &odds.drop,
                     ui_state,
                     ui_context,
                     assets,
                     inventory,
                 );
+
+                draw_text(
+                    &format!("{:.2}%", odds.chance * 100.0),
+                    cost_origin + VecTwo::new(-30.0, 60.0),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
             }
+
+            draw_text(
+                &format!(
+                    "Chance per pull. Each pack has {} pulls.",
+                    pack_info.content_count
+                ),
+                desc_origin + VecTwo::new(0.0, y + 150.0),
+                *THEME_TEXT_MUT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        // pity counter
+        if let Some(rule) = &pack_info.pity {
+            let misses = player_state.pity.misses(self.pack_id);
+
+            draw_text(
+                &rule.display(get_fixed_table(pack_info.table_id), misses),
+                VecTwo::new(10.0, 300.0),
+                *THEME_TEXT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
         }
 
         // close button
@@ -0,0 +1,181 @@
+use crate::{save_file::*, ui_panels::*, UpdateSignal};
+use elara_engine::{color::*, rect::*, typeface::*, ui::*, vectors::*};
+
+/// Create, switch, copy and delete save slots
+pub struct ProfilePanel {
+    new_name: String,
+
+    /// Slot waiting on a second click to delete
+    confirm_delete: Option<usize>,
+}
+
+impl ProfilePanel {
+    pub fn new() -> Self {
+        Self {
+            new_name: String::new(),
+            confirm_delete: None,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        save_slots: &SaveSlots,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 700.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Profiles",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // snapshots of the active slot
+        if draw_button(
+            "Restore",
+            ButtonStyleData::new_outline(None),
+            &Rect::new_top_size(VecTwo::new(130.0, 60.0), 120.0, 30.0),
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            update_signals.push(UpdateSignal::PushPanel(CreatePanelData::Restore));
+        }
+
+        // slots
+        for (i, slot) in save_slots.slots.iter().enumerate() {
+            let origin = VecTwo::new(10.0, 160.0 + (i as f64 * 50.0));
+            let active = i == save_slots.active;
+
+            draw_text(
+                &slot.name,
+                origin,
+                if active { COLOR_WHITE } else { *THEME_TEXT },
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            if active {
+                draw_text(
+                    "Playing",
+                    origin + VecTwo::new(350.0, 0.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            } else if draw_button_id(
+                i as i32,
+                "Play",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(origin + VecTwo::new(350.0, -25.0), 100.0, 30.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::SwitchSaveSlot { index: i });
+            }
+
+            if draw_button_id(
+                i as i32,
+                "Copy",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(origin + VecTwo::new(470.0, -25.0), 100.0, 30.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::DuplicateSaveSlot { index: i });
+            }
+
+            if active {
+                continue;
+            }
+
+            if self.confirm_delete == Some(i) {
+                if draw_button_id(
+                    i as i32,
+                    "Really delete",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(origin + VecTwo::new(590.0, -25.0), 180.0, 30.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    update_signals.push(UpdateSignal::DeleteSaveSlot { index: i });
+                    self.confirm_delete = None;
+                }
+            } else if draw_button_id(
+                i as i32,
+                "Delete",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(origin + VecTwo::new(590.0, -25.0), 100.0, 30.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                self.confirm_delete = Some(i);
+            }
+        }
+
+        // new slot
+        if save_slots.slots.len() < MAX_SAVE_SLOTS {
+            let origin = VecTwo::new(10.0, 160.0 + (MAX_SAVE_SLOTS as f64 * 50.0) + 30.0);
+
+            InputField::draw(
+                "New profile",
+                "profile_name",
+                &mut self.new_name,
+                origin,
+                300.0,
+                &ui_context.font_nav.clone(),
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+                std::line!(),
+            );
+
+            if draw_button(
+                "Create",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(origin + VecTwo::new(350.0, 10.0), 100.0, 35.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::CreateSaveSlot {
+                    name: self.new_name.clone(),
+                });
+                self.new_name.clear();
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{
+    drop_table::*,
+    quest::*,
+    state::{assets::*, inventory::*, player_state::*, *},
+    ui_panels::*,
+    UpdateSignal,
+};
+use elara_engine::{color::*, rect::*, typeface::*, ui::*, vectors::*};
+
+pub struct QuestLogPanel {}
+
+impl QuestLogPanel {
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
+        inventory: &Inventory,
+        assets: &mut Assets,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(900.0, 800.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Quests",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // quest list
+        {
+            let row_height: f64 = 130.0;
+            let icon_size: f64 = 50.0;
+
+            let quest_log = &player_state.quest_log;
+            let active = quest_log.active_quests();
+
+            if active.is_empty() {
+                draw_text(
+                    "All quests complete!",
+                    VecTwo::new(10.0, 140.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+
+            for (i, quest_id) in active.iter().enumerate() {
+                let quest = quest_id.get_quest_info();
+                let origin = VecTwo::new(10.0, 140.0 + (i as f64 * row_height));
+
+                draw_text(
+                    &quest.title,
+                    origin,
+                    COLOR_WHITE,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+
+                draw_text(
+                    &quest.description,
+                    origin + VecTwo::new(0.0, 30.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+
+                let target = quest.objective.target();
+                let prog = quest_log.progress(*quest_id).clamp(0, target);
+                draw_text(
+                    &format!("{} / {}", prog, target),
+                    origin + VecTwo::new(0.0, 60.0),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+
+                // rewards
+                for (j, drop) in quest.rewards.iter().enumerate() {
+                    draw_drop_icon(
+                        icon_size,
+                        origin + VecTwo::new(420.0 + (j as f64 * (icon_size + 20.0)), -10.0),
+                        drop,
+                        ui_state,
+                        ui_context,
+                        assets,
+                        inventory,
+                    );
+                }
+
+                if quest_log.is_complete(*quest_id) {
+                    let claim_rect =
+                        Rect::new_top_size(origin + VecTwo::new(720.0, -25.0), 120.0, 35.0);
+
+                    if draw_button_id(
+                        quest_id.to_index(),
+                        "Claim",
+                        ButtonStyleData::new_outline(None),
+                        &claim_rect,
+                        ui_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        update_signals.push(UpdateSignal::ClaimQuest(*quest_id));
+                    }
+                }
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
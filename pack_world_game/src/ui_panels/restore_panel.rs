@@ -0,0 +1,130 @@
+use crate::{save_file::*, ui_panels::*, UpdateSignal};
+use elara_engine::{color::*, platform_api::*, rect::*, typeface::*, ui::*, vectors::*};
+
+/// Snapshots shown in each column
+const ROWS: usize = 16;
+
+/// Lists the snapshots of the active slot and restores one
+pub struct RestorePanel {
+    /// Snapshot waiting on a second click to restore
+    confirm_restore: Option<usize>,
+}
+
+impl RestorePanel {
+    pub fn new() -> Self {
+        Self {
+            confirm_restore: None,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        save_history: &SaveHistory,
+        ui_context: &mut UIContext,
+        platform_api: &PlatformApi,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1300.0, 850.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Restore",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        draw_text(
+            "Go back to an earlier copy of this profile. Your current game is kept as a backup.",
+            VecTwo::new(10.0, 130.0),
+            *THEME_TEXT_MUT,
+            &ui_context.font_body.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        if save_history.snapshots.is_empty() {
+            draw_text(
+                "No snapshots yet. One is taken every hour.",
+                VecTwo::new(10.0, 180.0),
+                *THEME_TEXT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+        }
+
+        let time_now = (platform_api.epoch_time_ms)();
+        for (i, snapshot) in save_history.snapshots.iter().enumerate() {
+            let origin = VecTwo::new(
+                10.0 + (i / ROWS) as f64 * 640.0,
+                190.0 + (i % ROWS) as f64 * 40.0,
+            );
+            let preview = &snapshot.preview;
+
+            draw_text(
+                &format!(
+                    "{}   {} gold   {} tiles",
+                    preview.saved_ago(time_now),
+                    preview.gold,
+                    preview.tile_count
+                ),
+                origin,
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            let button_origin = origin + VecTwo::new(420.0, -25.0);
+            if self.confirm_restore == Some(i) {
+                if draw_button_id(
+                    i as i32,
+                    "Really restore",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(button_origin, 180.0, 30.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    update_signals.push(UpdateSignal::RestoreSnapshot { index: i });
+                    self.confirm_restore = None;
+                }
+            } else if draw_button_id(
+                i as i32,
+                "Restore",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(button_origin, 120.0, 30.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                self.confirm_restore = Some(i);
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{save_file::*, state::*, ui_panels::*, UpdateSignal};
+use elara_engine::{color::*, platform_api::*, rect::*, typeface::*, ui::*, vectors::*};
+
+/// Moves a game to another device with a save code
+pub struct SaveTransferPanel {
+    /// Last valid code the player pasted
+    pasted: Option<(String, SavePreview)>,
+
+    /// Why the last paste couldn't be used
+    paste_error: Option<String>,
+
+    /// Replacing the current game needs a second click
+    confirming: bool,
+}
+
+impl SaveTransferPanel {
+    pub fn new() -> Self {
+        Self {
+            pasted: None,
+            paste_error: None,
+            confirming: false,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        ui_context: &mut UIContext,
+        platform_api: &PlatformApi,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+
+        if let Some(paste) = &ui_context.paste {
+            match SaveCode::decode(paste) {
+                Ok(save_code) => {
+                    self.pasted = Some((paste.clone(), save_code.preview));
+                    self.paste_error = None;
+                }
+                Err(error) => {
+                    self.pasted = None;
+                    self.paste_error = Some(save_code_error_message(&error));
+                }
+            }
+            self.confirming = false;
+        }
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 600.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Transfer Save",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // export
+        if platform_has_downloads() {
+            draw_text(
+                "Download your game as a save code to play it somewhere else",
+                VecTwo::new(10.0, 140.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            if draw_button(
+                "Export",
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(VecTwo::new(10.0, 160.0), 120.0, 35.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::ExportSave);
+            }
+        }
+
+        // pasted code
+        {
+            let origin = VecTwo::new(10.0, 260.0);
+
+            match (&self.pasted, &self.paste_error) {
+                (Some((raw, preview)), _) => {
+                    let lines = vec![
+                        format!("{} gold", preview.gold),
+                        format!("{} tiles", preview.tile_count),
+                        format!(
+                            "Saved {}",
+                            preview.saved_ago((platform_api.epoch_time_ms)())
+                        ),
+                    ];
+                    for (i, line) in lines.iter().enumerate() {
+                        draw_text(
+                            line,
+                            origin + VecTwo::new(0.0, i as f64 * 30.0),
+                            COLOR_WHITE,
+                            &ui_context.font_body.clone(),
+                            ui_state,
+                            ui_context,
+                        );
+                    }
+
+                    let button_origin = origin + VecTwo::new(0.0, 110.0);
+                    if !self.confirming {
+                        if draw_button(
+                            "Replace my game",
+                            ButtonStyleData::new_outline(None),
+                            &Rect::new_top_size(button_origin, 220.0, 35.0),
+                            ui_state,
+                            std::line!(),
+                            ui_context,
+                        ) {
+                            self.confirming = true;
+                        }
+                    } else {
+                        draw_text(
+                            "Your current game will be replaced. Are you sure?",
+                            button_origin,
+                            *THEME_TEXT,
+                            &ui_context.font_body.clone(),
+                            ui_state,
+                            ui_context,
+                        );
+
+                        if draw_button(
+                            "Yes, replace",
+                            ButtonStyleData::new_outline(None),
+                            &Rect::new_top_size(
+                                button_origin + VecTwo::new(0.0, 20.0),
+                                180.0,
+                                35.0,
+                            ),
+                            ui_state,
+                            std::line!(),
+                            ui_context,
+                        ) {
+                            update_signals.push(UpdateSignal::ImportSave { code: raw.clone() });
+                            self.pasted = None;
+                            self.confirming = false;
+                        }
+
+                        if draw_button(
+                            "Cancel",
+                            ButtonStyleData::new_outline(None),
+                            &Rect::new_top_size(
+                                button_origin + VecTwo::new(200.0, 20.0),
+                                120.0,
+                                35.0,
+                            ),
+                            ui_state,
+                            std::line!(),
+                            ui_context,
+                        ) {
+                            self.confirming = false;
+                        }
+                    }
+                }
+
+                (None, Some(error)) => {
+                    draw_text(
+                        error,
+                        origin,
+                        *THEME_TEXT,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+                }
+
+                (None, None) => {
+                    draw_text(
+                        "Paste a save code to load it here",
+                        origin,
+                        *THEME_TEXT_MUT,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+                }
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{
+    pack::{pack_id::*, *},
+    state::{player_state::*, *},
+    tile::*,
+    ui_panels::*,
+    UpdateSignal,
+};
+use elara_engine::{color::*, rect::*, time::*, typeface::*, ui::*, vectors::*};
+
+pub struct StatsPanel {}
+
+impl StatsPanel {
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+        let stats = &player_state.stats;
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 850.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Stats",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // export button
+        if platform_has_downloads() {
+            let export_rect = Rect::new_top_size(VecTwo::new(120.0, 60.0), 140.0, 30.0);
+
+            if draw_button(
+                "Export CSV",
+                ButtonStyleData::new_outline(None),
+                &export_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::ExportStats);
+            }
+        }
+
+        let row_height: f64 = 30.0;
+
+        // general
+        {
+            let lines: Vec<String> = vec![
+                format!(
+                    "Time played {}",
+                    Time::new(TimeUnit::Seconds(stats.time_played)).display()
+                ),
+                format!(
+                    "Offline time simulated {}",
+                    Time::new(TimeUnit::MilliSeconds(stats.offline_simulated_ms)).display()
+                ),
+                format!("Gold earned {}", stats.gold_earned),
+                format!("Gold spent {}", stats.gold_spent),
+                format!("Tiles placed {}", stats.tiles_placed),
+                format!("Tiles destroyed {}", stats.tiles_destroyed),
+                format!("Harvests {}", stats.total_harvests()),
+                format!("Packs opened {}", stats.total_packs_opened()),
+            ];
+
+            for (i, line) in lines.iter().enumerate() {
+                draw_text(
+                    line,
+                    VecTwo::new(10.0, 140.0 + (i as f64 * row_height)),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        // harvests per tile
+        {
+            draw_text(
+                "Harvests",
+                VecTwo::new(400.0, 140.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            let mut i = 0;
+            for tile_type in ALL_TILE_TYPES.iter() {
+                let Some(count) = stats.harvests.get(tile_type) else {
+                    continue;
+                };
+
+                draw_text(
+                    &format!("{} {}", tile_type.get_definition().title, count),
+                    VecTwo::new(400.0, 170.0 + (i as f64 * row_height)),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+                i += 1;
+            }
+        }
+
+        // packs opened
+        {
+            draw_text(
+                "Packs Opened",
+                VecTwo::new(700.0, 140.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            for (i, pack_id) in ALL_PACKS.iter().enumerate() {
+                let count = stats.packs_opened.get(pack_id).unwrap_or(&0);
+
+                draw_text(
+                    &format!("{} {}", pack_id.get_pack_info().display_name, count),
+                    VecTwo::new(700.0, 170.0 + (i as f64 * row_height)),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+}
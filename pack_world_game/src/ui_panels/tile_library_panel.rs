@@ -280,6 +280,9 @@ impl TileLibraryPanel {
             | ItemType::EyeOfNewt
             | ItemType::DragonEgg
             | ItemType::Baby
+            | ItemType::PotionBounty
+            | ItemType::PotionHaste
+            | ItemType::PotionRipen
             | ItemType::Acorn => {
                 if draw_button_id(
                     i,
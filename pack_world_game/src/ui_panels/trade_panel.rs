@@ -0,0 +1,441 @@
+use crate::{
+    drop_table::*,
+    item::*,
+    state::{inventory::*, player_state::*, *},
+    tile::*,
+    trade::*,
+    ui_panels::*,
+    UpdateSignal,
+};
+use elara_engine::{color::*, rect::*, typeface::*, ui::*, vectors::*};
+
+pub struct TradePanel {
+    give_index: usize,
+    give_amount: i64,
+
+    want_index: usize,
+    want_amount: i64,
+
+    /// Last valid code the player pasted
+    pasted: Option<(String, TradeCode)>,
+}
+
+impl TradePanel {
+    pub fn new() -> Self {
+        Self {
+            give_index: 0,
+            give_amount: 1,
+            want_index: 0,
+            want_amount: 1,
+            pasted: None,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        mut ui_state: &mut UIFrameState,
+        player_state: &PlayerState,
+        inventory: &Inventory,
+        ui_context: &mut UIContext,
+    ) -> Vec<UpdateSignal> {
+        let mut update_signals: Vec<UpdateSignal> = vec![];
+        let trades = &player_state.trades;
+
+        if let Some(paste) = &ui_context.paste {
+            if let Ok(code) = TradeCode::decode(paste) {
+                self.pasted = Some((paste.clone(), code));
+            }
+        }
+
+        let panel_r = Rect::new_center(ui_state.resolution * 0.5, VecTwo::new(1000.0, 850.0));
+        begin_panel(panel_r, *THEME_PANEL_BG, &mut ui_state, ui_context);
+
+        draw_text(
+            "Trade",
+            VecTwo::new(10.0, 40.0),
+            COLOR_WHITE,
+            &ui_context.font_header.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        // close button
+        {
+            let close_rect = Rect::new_top_size(VecTwo::new(10.0, 60.0), 100.0, 30.0);
+
+            if draw_button(
+                "Close",
+                ButtonStyleData::new_outline(None),
+                &close_rect,
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                update_signals.push(UpdateSignal::PreviousPanel());
+            }
+        }
+
+        // offer builder
+        {
+            let mut give_options: Vec<DropType> = vec![DropType::Gold];
+            let mut owned: Vec<ItemType> = inventory
+                .items
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(item_type, _)| *item_type)
+                .collect();
+            owned.sort_by_key(|item_type| match item_type {
+                ItemType::Tile(tile_type) => 100 + tile_type.to_index(),
+                _ => item_type.to_index(),
+            });
+            give_options.extend(owned.iter().map(|item_type| DropType::Item {
+                item_type: *item_type,
+            }));
+
+            let want_options: Vec<DropType> = std::iter::once(DropType::Gold)
+                .chain(ALL_ITEM_TYPES.iter().map(|item_type| DropType::Item {
+                    item_type: *item_type,
+                }))
+                .chain(ALL_TILE_TYPES.iter().map(|tile_type| DropType::Item {
+                    item_type: ItemType::Tile(*tile_type),
+                }))
+                .collect();
+
+            self.give_index = self.give_index.min(give_options.len() - 1);
+
+            draw_text(
+                "Make an offer",
+                VecTwo::new(10.0, 140.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            Self::drop_picker(
+                0,
+                "Give",
+                VecTwo::new(10.0, 170.0),
+                &give_options,
+                &mut self.give_index,
+                &mut self.give_amount,
+                ui_state,
+                ui_context,
+            );
+            Self::drop_picker(
+                1,
+                "For",
+                VecTwo::new(10.0, 220.0),
+                &want_options,
+                &mut self.want_index,
+                &mut self.want_amount,
+                ui_state,
+                ui_context,
+            );
+
+            let give = Drop::new(give_options[self.give_index], self.give_amount);
+            let want = Drop::new(want_options[self.want_index], self.want_amount);
+
+            if can_afford_drop(inventory, &give) && give.drop_type != want.drop_type {
+                if draw_button(
+                    "Create Code",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(VecTwo::new(10.0, 280.0), 160.0, 35.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    update_signals.push(UpdateSignal::CreateTradeOffer { give, want });
+                }
+            } else {
+                draw_text(
+                    "You can't offer that",
+                    VecTwo::new(10.0, 305.0),
+                    *THEME_TEXT_MUT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        // code to share
+        if let Some(code) = &trades.last_code {
+            draw_text(
+                "Share this code",
+                VecTwo::new(10.0, 360.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+            draw_text(
+                code,
+                VecTwo::new(10.0, 390.0),
+                *THEME_TEXT,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            if platform_has_downloads()
+                && draw_button(
+                    "Copy",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(VecTwo::new(200.0, 335.0), 100.0, 30.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                )
+            {
+                update_signals.push(UpdateSignal::ExportTradeCode);
+            }
+        }
+
+        // pasted code
+        {
+            let origin = VecTwo::new(10.0, 450.0);
+
+            match &self.pasted {
+                None => {
+                    draw_text(
+                        "Paste a trade code to accept, complete or collect a trade",
+                        origin,
+                        *THEME_TEXT_MUT,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+                }
+
+                Some((raw, TradeCode::Offer(offer))) => {
+                    draw_text(
+                        &format!(
+                            "Offer: get {} for {}",
+                            offer.give.display(),
+                            offer.want.display()
+                        ),
+                        origin,
+                        COLOR_WHITE,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+
+                    if draw_button(
+                        "Accept",
+                        ButtonStyleData::new_outline(None),
+                        &Rect::new_top_size(origin + VecTwo::new(0.0, 15.0), 120.0, 35.0),
+                        ui_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        update_signals.push(UpdateSignal::AcceptTradeOffer { code: raw.clone() });
+                        self.pasted = None;
+                    }
+                }
+
+                Some((raw, TradeCode::Accept(_))) => {
+                    draw_text(
+                        "Another player accepted your offer",
+                        origin,
+                        COLOR_WHITE,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+
+                    if draw_button(
+                        "Complete",
+                        ButtonStyleData::new_outline(None),
+                        &Rect::new_top_size(origin + VecTwo::new(0.0, 15.0), 120.0, 35.0),
+                        ui_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        update_signals.push(UpdateSignal::CompleteTrade { code: raw.clone() });
+                        self.pasted = None;
+                    }
+                }
+
+                Some((raw, TradeCode::Settle(settle))) => {
+                    let message = if settle.delivered {
+                        "Your trade is ready to collect"
+                    } else {
+                        "That offer was closed. Your payment can be refunded"
+                    };
+                    draw_text(
+                        message,
+                        origin,
+                        COLOR_WHITE,
+                        &ui_context.font_body.clone(),
+                        ui_state,
+                        ui_context,
+                    );
+
+                    if draw_button(
+                        "Collect",
+                        ButtonStyleData::new_outline(None),
+                        &Rect::new_top_size(origin + VecTwo::new(0.0, 15.0), 120.0, 35.0),
+                        ui_state,
+                        std::line!(),
+                        ui_context,
+                    ) {
+                        update_signals.push(UpdateSignal::RedeemTrade { code: raw.clone() });
+                        self.pasted = None;
+                    }
+                }
+            }
+        }
+
+        // pending offers
+        {
+            draw_text(
+                "Your open offers",
+                VecTwo::new(10.0, 560.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            for (i, offer) in trades.pending.iter().enumerate() {
+                let origin = VecTwo::new(10.0, 600.0 + (i as f64 * 40.0));
+
+                draw_text(
+                    &format!("{} for {}", offer.give.display(), offer.want.display()),
+                    origin,
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+
+                if draw_button_id(
+                    i as i32,
+                    "Cancel",
+                    ButtonStyleData::new_outline(None),
+                    &Rect::new_top_size(origin + VecTwo::new(500.0, -25.0), 100.0, 30.0),
+                    ui_state,
+                    std::line!(),
+                    ui_context,
+                ) {
+                    update_signals.push(UpdateSignal::CancelTradeOffer { nonce: offer.nonce });
+                }
+            }
+        }
+
+        // accepted offers waiting on the settle code
+        {
+            draw_text(
+                "Waiting on",
+                VecTwo::new(650.0, 560.0),
+                COLOR_WHITE,
+                &ui_context.font_body.clone(),
+                ui_state,
+                ui_context,
+            );
+
+            for (i, offer) in trades.receipts.iter().enumerate() {
+                draw_text(
+                    &format!("{} for {}", offer.give.display(), offer.want.display()),
+                    VecTwo::new(650.0, 600.0 + (i as f64 * 40.0)),
+                    *THEME_TEXT,
+                    &ui_context.font_body.clone(),
+                    ui_state,
+                    ui_context,
+                );
+            }
+        }
+
+        end_panel(&mut ui_state, ui_context);
+
+        update_signals
+    }
+
+    fn drop_picker(
+        id: i32,
+        label: &str,
+        origin: VecTwo,
+        options: &Vec<DropType>,
+        index: &mut usize,
+        amount: &mut i64,
+        ui_state: &mut UIFrameState,
+        ui_context: &mut UIContext,
+    ) {
+        draw_text(
+            label,
+            origin + VecTwo::new(0.0, 25.0),
+            *THEME_TEXT,
+            &ui_context.font_body.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        if draw_button_id(
+            id,
+            "<",
+            ButtonStyleData::new_outline(None),
+            &Rect::new_top_size(origin + VecTwo::new(60.0, 0.0), 35.0, 35.0),
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            *index = (*index + options.len() - 1) % options.len();
+        }
+
+        let title: String = match options[*index] {
+            DropType::Gold => "Gold".into(),
+            DropType::Item { item_type } => item_type.user_title().to_string(),
+        };
+        draw_text(
+            &title,
+            origin + VecTwo::new(105.0, 25.0),
+            COLOR_WHITE,
+            &ui_context.font_body.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        if draw_button_id(
+            id,
+            ">",
+            ButtonStyleData::new_outline(None),
+            &Rect::new_top_size(origin + VecTwo::new(300.0, 0.0), 35.0, 35.0),
+            ui_state,
+            std::line!(),
+            ui_context,
+        ) {
+            *index = (*index + 1) % options.len();
+        }
+
+        draw_text(
+            &format!("{}", amount),
+            origin + VecTwo::new(490.0, 25.0),
+            COLOR_WHITE,
+            &ui_context.font_body.clone(),
+            ui_state,
+            ui_context,
+        );
+
+        let steps: Vec<(&str, i64, f64)> = vec![
+            ("-10", -10, 360.0),
+            ("-1", -1, 420.0),
+            ("+1", 1, 570.0),
+            ("+10", 10, 630.0),
+        ];
+        for (i, (step_label, step, x)) in steps.iter().enumerate() {
+            if draw_button_id(
+                id * 10 + i as i32,
+                step_label,
+                ButtonStyleData::new_outline(None),
+                &Rect::new_top_size(origin + VecTwo::new(*x, 0.0), 50.0, 35.0),
+                ui_state,
+                std::line!(),
+                ui_context,
+            ) {
+                *amount = (*amount + step).max(1);
+            }
+        }
+    }
+}
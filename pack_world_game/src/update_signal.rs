@@ -1,13 +1,17 @@
 use crate::{
+    achievement::*,
     drop_table::*,
     game_mode::*,
     grid::*,
     harvest_drop::*,
     item::*,
     pack::*,
+    quest::*,
     save_file::*,
     state::{inventory::*, *},
     tile::*,
+    toast::*,
+    trade::*,
     ui_panels::{home_panel::*, *},
     user_account::*,
     world::world_layer::*,
@@ -39,47 +43,166 @@ pub enum UpdateSignal {
     PreviousPanel(),
 
     /// Add an item to inventory
-    GiveItem { item_type: ItemType, count: i64 },
+    GiveItem {
+        item_type: ItemType,
+        count: i64,
+    },
 
     /// Open a pack
     OpenPack(PackID),
 
     /// Change the game mode
-    SetGameMode { new_mode: GameModeKind },
+    SetGameMode {
+        new_mode: GameModeKind,
+    },
 
     /// Purchase a bank slot
     PurchaseBankSlot,
 
     /// Give gold
-    GiveGold { amount: i64 },
+    GiveGold {
+        amount: i64,
+    },
 
     /// Give a drop
-    GiveDrop(Drop),
+    GiveDrop {
+        drop: Drop,
+        source: DropSource,
+    },
 
     /// Setup a harvest drop
-    AddHarvestDrop { drop: Drop, origin: GridPos },
+    AddHarvestDrop {
+        drop: Drop,
+        origin: GridPos,
+        source: DropSource,
+    },
 
     /// Destroy a tile
-    DestroyTile { pos: GridPos, layer: WorldLayer },
+    DestroyTile {
+        pos: GridPos,
+        layer: WorldLayer,
+    },
 
     /// Trigger a game save
     SaveGame,
 
     /// Open url
-    OpenURL { url: String },
+    OpenURL {
+        url: String,
+    },
 
     /// Trigger rendering a tile thumbnail
-    TriggerRenderTileThumbnail { tile_type: TileType },
+    TriggerRenderTileThumbnail {
+        tile_type: TileType,
+    },
 
     /// Set current account from supabase account info
-    LoginUserFromSupabase { user_json: String },
+    LoginUserFromSupabase {
+        user_json: String,
+    },
 
     /// Logout
     Logout,
 
     /// Try to harvest a tile.
     /// Will do nothing if the tile isn't harvestable or isn't ready to harvest.
-    TryHarvestTile { entity_id: EntityID },
+    TryHarvestTile {
+        entity_id: EntityID,
+    },
+
+    /// Use a potion on a grid position. Consumes the potion if it had an effect.
+    UsePotion {
+        item_type: ItemType,
+        pos: GridPos,
+    },
+
+    /// Sell items to the merchant for gold
+    SellItem {
+        item_type: ItemType,
+        count: i64,
+    },
+
+    /// Protected items can't be sold
+    SetItemProtected {
+        item_type: ItemType,
+        protected: bool,
+    },
+
+    /// Hold the given drop and make a trade code for it
+    CreateTradeOffer {
+        give: Drop,
+        want: Drop,
+    },
+
+    /// Accept another player's offer code
+    AcceptTradeOffer {
+        code: String,
+    },
+
+    /// Finish one of our offers with the receipt code from the accepting player
+    CompleteTrade {
+        code: String,
+    },
+
+    /// Collect an accepted offer with the settle code from the offering player
+    RedeemTrade {
+        code: String,
+    },
+
+    CancelTradeOffer {
+        nonce: u64,
+    },
+
+    /// Hand the last trade code to the platform to copy and download
+    ExportTradeCode,
+
+    /// Make a save code of the current game for the platform to download
+    ExportSave,
+
+    /// Hand the lifetime stats to the platform to download as a CSV
+    ExportStats,
+
+    /// Replace the current game with a pasted save code
+    ImportSave {
+        code: String,
+    },
+
+    /// Play another save slot. The current game is saved first.
+    SwitchSaveSlot {
+        index: usize,
+    },
+
+    /// Add a save slot with a new game and play it
+    CreateSaveSlot {
+        name: String,
+    },
+
+    DuplicateSaveSlot {
+        index: usize,
+    },
+
+    /// The active slot can't be deleted
+    DeleteSaveSlot {
+        index: usize,
+    },
+
+    /// Go back to a snapshot of the active slot. The current game is kept as a backup.
+    RestoreSnapshot {
+        index: usize,
+    },
+
+    /// The player placed a tile
+    TilePlaced {
+        tile_type: TileType,
+    },
+
+    /// A tile was harvested, either by the player or automatically
+    TileHarvested {
+        tile_type: TileType,
+    },
+
+    /// Claim the rewards of a completed quest
+    ClaimQuest(QuestID),
 }
 
 pub fn handle_signals(
@@ -124,12 +247,30 @@ pub fn handle_signals(
                     vec![UpdateSignal::SaveGame]
                 }
 
-                UpdateSignal::GiveDrop(drop) => {
+                UpdateSignal::GiveDrop { drop, source } => {
                     gs.inventory.give_drop(*drop).unwrap();
+                    if drop.drop_type == DropType::Gold {
+                        gs.player_state.stats.record_gold(drop.amount);
+                    }
+                    if let DropSource::Pack(pack_id) = source {
+                        gs.player_state.pity.record_pull(*pack_id, drop);
+                    }
+                    gs.player_state.collection.record(
+                        *drop,
+                        *source,
+                        (platform_api.epoch_time_ms)(),
+                    );
+                    gs.player_state
+                        .quest_log
+                        .handle_event(QuestEvent::GotDrop(*drop));
                     vec![UpdateSignal::SaveGame]
                 }
 
-                UpdateSignal::AddHarvestDrop { drop, origin } => {
+                UpdateSignal::AddHarvestDrop {
+                    drop,
+                    origin,
+                    source,
+                } => {
                     let cam: &Camera = &es
                         .render_system
                         .render_packs
@@ -141,8 +282,12 @@ pub fn handle_signals(
                     let screen_pos_origin: VecTwo =
                         cam.world_to_screen(world_pos, es.window_resolution);
 
-                    gs.harvest_drops
-                        .push(HarvestDrop::new(*drop, screen_pos_origin, platform_api));
+                    gs.harvest_drops.push(HarvestDrop::new(
+                        *drop,
+                        *source,
+                        screen_pos_origin,
+                        platform_api,
+                    ));
                     vec![]
                 }
 
@@ -156,6 +301,10 @@ pub fn handle_signals(
 
                     (platform_api.send_event)(AnalyticsEvent::PackOpen(format!("{:?}", pack_id)));
                     pack_info.spend(&mut gs.inventory);
+                    gs.player_state.stats.record_pack_opened(*pack_id);
+                    gs.player_state
+                        .quest_log
+                        .handle_event(QuestEvent::OpenedPack(*pack_id));
 
                     let new_panel_data = CreatePanelData::OpenPack { pack_id: *pack_id };
                     vec![
@@ -171,6 +320,9 @@ pub fn handle_signals(
 
                 UpdateSignal::PurchaseBankSlot => {
                     if gs.inventory.gold >= gs.inventory.next_slot_cost() {
+                        gs.player_state
+                            .stats
+                            .record_gold(-gs.inventory.next_slot_cost());
                         gs.inventory.gold -= gs.inventory.next_slot_cost();
                         gs.inventory.limit += 1;
                     }
@@ -179,12 +331,13 @@ pub fn handle_signals(
 
                 UpdateSignal::GiveGold { amount } => {
                     let _ = gs.inventory.give_gold(*amount);
+                    gs.player_state.stats.record_gold(*amount);
                     vec![UpdateSignal::SaveGame]
                 }
 
                 UpdateSignal::SaveGame => {
                     /*
-                    match save_game(&gs.world, &gs.inventory, platform_api) {
+                    match save_game(&gs.world, &gs.inventory, &gs.player_state, platform_api) {
                         Ok(()) => println!("Game saved successfully"),
                         Err(error) => println!("Error saving game {:?}", error),
                     }
@@ -196,6 +349,7 @@ pub fn handle_signals(
 
                 UpdateSignal::DestroyTile { pos, layer } => {
                     gs.world.destroy_tile(*pos, *layer);
+                    gs.player_state.stats.tiles_destroyed += 1;
                     vec![UpdateSignal::SaveGame]
                 }
 
@@ -229,13 +383,353 @@ pub fn handle_signals(
 
                 UpdateSignal::TryHarvestTile { entity_id } => {
                     let world_snapshot = gs.world.get_world_snapshot();
-                    if let Some(tile_inst) = gs.world.entities.get_mut(entity_id) {
-                        tile_inst.harvest(&world_snapshot, platform_api);
+                    if gs.world.entities.contains_key(entity_id) {
+                        gs.world
+                            .get_entity_mut(entity_id)
+                            .harvest(&world_snapshot, platform_api)
+                    } else {
+                        vec![]
+                    }
+                }
+
+                UpdateSignal::UsePotion { item_type, pos } => {
+                    let Some(effect) = item_type.potion_effect() else {
+                        continue;
+                    };
+                    if !gs.inventory.has_atleast(*item_type, 1) {
+                        continue;
+                    }
+
+                    match gs.world.use_potion(effect, *pos) {
+                        Ok(()) => {
+                            let _ = gs.inventory.give_item(*item_type, -1);
+                            vec![UpdateSignal::SaveGame]
+                        }
+                        Err(error) => {
+                            println!("Error using potion {:?}", error);
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::SellItem { item_type, count } => {
+                    let gold = gs.player_state.market.quote(*item_type, *count);
+
+                    match gs.inventory.sell_item(*item_type, *count, gold) {
+                        Ok(()) => {
+                            gs.player_state.market.record_sale(*item_type, *count);
+                            gs.player_state.stats.record_gold(gold);
+                            vec![UpdateSignal::SaveGame]
+                        }
+                        Err(error) => {
+                            println!("Error selling item {:?}", error);
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::SetItemProtected {
+                    item_type,
+                    protected,
+                } => {
+                    gs.inventory.set_protected(*item_type, *protected);
+                    vec![UpdateSignal::SaveGame]
+                }
+
+                UpdateSignal::CreateTradeOffer { give, want } => {
+                    match gs
+                        .player_state
+                        .trades
+                        .create_offer(*give, *want, &mut gs.inventory)
+                    {
+                        Ok(_) => vec![UpdateSignal::SaveGame, UpdateSignal::ExportTradeCode],
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(trade_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::AcceptTradeOffer { code } => {
+                    match gs.player_state.trades.accept_offer(code, &mut gs.inventory) {
+                        Ok(_) => {
+                            gs.toasts
+                                .push(Toast::new("Trade accepted! Send back the new code.".into()));
+                            vec![UpdateSignal::SaveGame, UpdateSignal::ExportTradeCode]
+                        }
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(trade_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::CompleteTrade { code } => {
+                    match gs
+                        .player_state
+                        .trades
+                        .complete_trade(code, &mut gs.inventory)
+                    {
+                        Ok(settle) => {
+                            let message = if settle.delivered {
+                                "Trade complete! Send back the new code."
+                            } else {
+                                "That offer is closed. Send back the refund code."
+                            };
+                            gs.toasts.push(Toast::new(message.into()));
+                            vec![UpdateSignal::SaveGame, UpdateSignal::ExportTradeCode]
+                        }
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(trade_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::RedeemTrade { code } => {
+                    match gs
+                        .player_state
+                        .trades
+                        .redeem_settle(code, &mut gs.inventory)
+                    {
+                        Ok(received) => {
+                            gs.toasts.push(Toast::new(format!(
+                                "Trade finished! Got {}",
+                                received.display()
+                            )));
+                            vec![UpdateSignal::SaveGame]
+                        }
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(trade_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::CancelTradeOffer { nonce } => {
+                    match gs
+                        .player_state
+                        .trades
+                        .cancel_offer(*nonce, &mut gs.inventory)
+                    {
+                        Ok(()) => vec![UpdateSignal::SaveGame],
+                        Err(error) => {
+                            println!("Error cancelling trade {:?}", error);
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::ExportTradeCode => {
+                    if let Some(code) = &gs.player_state.trades.last_code {
+                        gs.download = Some(Download {
+                            file_name: "pack_world_trade.txt".into(),
+                            contents: code.clone(),
+                        });
+                    }
+                    vec![]
+                }
+
+                UpdateSignal::ExportSave => {
+                    let mut data: Vec<u8> = vec![];
+                    let built =
+                        build_save_file(&gs.world, &gs.inventory, &gs.player_state, platform_api)
+                            .and_then(|save_file| save_file.write_file(&mut data));
+
+                    match built {
+                        Ok(()) => {
+                            let code = SaveCode::encode(&data);
+                            gs.download = Some(Download {
+                                file_name: "pack_world_save.txt".into(),
+                                contents: code,
+                            });
+                            gs.toasts.push(Toast::new("Save code ready.".into()));
+                        }
+                        Err(error) => {
+                            println!("Error exporting save {:?}", error);
+                        }
                     }
                     vec![]
                 }
+
+                UpdateSignal::ExportStats => {
+                    gs.download = Some(Download {
+                        file_name: "pack_world_stats.csv".into(),
+                        contents: gs.player_state.stats.export_csv(),
+                    });
+                    gs.toasts.push(Toast::new("Stats exported.".into()));
+                    vec![]
+                }
+
+                UpdateSignal::ImportSave { code } => {
+                    let imported = SaveCode::decode(code).and_then(|save_code| {
+                        rename_saves(&save_code.data, &gs.save_slots.active().name)
+                    });
+
+                    match imported {
+                        Ok(mut slot_data) => {
+                            // The current game goes behind the import as a backup
+                            slot_data.append(&mut gs.save_history.older_saves());
+                            gs.save_slots.set_active_data(slot_data);
+                            crate::load_active_slot(gs, es, platform_api);
+
+                            gs.toasts.push(Toast::new("Save imported.".into()));
+                            vec![UpdateSignal::PreviousPanel(), UpdateSignal::SaveGame]
+                        }
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(save_code_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::SwitchSaveSlot { index } => {
+                    if *index == gs.save_slots.active || *index >= gs.save_slots.slots.len() {
+                        continue;
+                    }
+
+                    // Kept in its slot, and written out with the one switched to
+                    if let Err(error) = store_game(
+                        &mut gs.world,
+                        &gs.inventory,
+                        &gs.player_state,
+                        &mut gs.save_history,
+                        &mut gs.save_slots,
+                        platform_api,
+                    ) {
+                        println!("Error saving before switching slots {:?}", error);
+                        continue;
+                    }
+
+                    gs.save_slots.active = *index;
+                    crate::load_active_slot(gs, es, platform_api);
+                    vec![UpdateSignal::SaveGame]
+                }
+
+                UpdateSignal::CreateSaveSlot { name } => match gs.save_slots.create(name) {
+                    Ok(index) => vec![UpdateSignal::SwitchSaveSlot { index }],
+                    Err(error) => {
+                        gs.toasts.push(Toast::new(save_slot_error_message(&error)));
+                        vec![]
+                    }
+                },
+
+                UpdateSignal::DuplicateSaveSlot { index } => {
+                    // Copy the game as it is now, not as it was last saved
+                    let copied = store_game(
+                        &mut gs.world,
+                        &gs.inventory,
+                        &gs.player_state,
+                        &mut gs.save_history,
+                        &mut gs.save_slots,
+                        platform_api,
+                    )
+                    .and_then(|()| {
+                        gs.save_slots
+                            .duplicate(*index, TradeLog::new_save_id(platform_api))
+                    });
+
+                    match copied {
+                        Ok(_) => vec![UpdateSignal::SaveGame],
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(save_slot_error_message(&error)));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::DeleteSaveSlot { index } => match gs.save_slots.delete(*index) {
+                    Ok(()) => vec![UpdateSignal::SaveGame],
+                    Err(error) => {
+                        gs.toasts.push(Toast::new(save_slot_error_message(&error)));
+                        vec![]
+                    }
+                },
+
+                UpdateSignal::RestoreSnapshot { index } => {
+                    // Before saving, which can take a new snapshot and shift the indices
+                    let Some(snapshot) = gs.save_history.snapshots.get(*index).cloned() else {
+                        continue;
+                    };
+
+                    // A full save, so the current game is kept whole as a backup
+                    gs.save_history.delta = None;
+
+                    if let Err(error) = save_game(
+                        &mut gs.world,
+                        &gs.inventory,
+                        &gs.player_state,
+                        &mut gs.save_history,
+                        &mut gs.save_slots,
+                        platform_api,
+                    ) {
+                        println!("Error saving before restoring a snapshot {:?}", error);
+                        continue;
+                    }
+
+                    match snapshot.restore((platform_api.epoch_time_ms)()) {
+                        Ok(mut slot_data) => {
+                            slot_data.append(&mut gs.save_history.older_saves());
+                            gs.save_slots.set_active_data(slot_data);
+                            crate::load_active_slot(gs, es, platform_api);
+
+                            gs.toasts.push(Toast::new("Snapshot restored.".into()));
+                            vec![UpdateSignal::PreviousPanel(), UpdateSignal::SaveGame]
+                        }
+                        Err(error) => {
+                            gs.toasts.push(Toast::new(format!(
+                                "Snapshot couldn't be restored {:?}",
+                                error
+                            )));
+                            vec![]
+                        }
+                    }
+                }
+
+                UpdateSignal::TilePlaced { tile_type } => {
+                    gs.player_state.stats.tiles_placed += 1;
+                    gs.player_state
+                        .quest_log
+                        .handle_event(QuestEvent::PlacedTile(*tile_type));
+                    vec![]
+                }
+
+                UpdateSignal::TileHarvested { tile_type } => {
+                    gs.player_state.stats.record_harvest(*tile_type);
+                    gs.player_state
+                        .quest_log
+                        .handle_event(QuestEvent::HarvestedTile(*tile_type));
+                    vec![]
+                }
+
+                UpdateSignal::ClaimQuest(quest_id) => {
+                    let Some(rewards) = gs.player_state.quest_log.claim(*quest_id) else {
+                        continue;
+                    };
+
+                    let mut ret: Vec<UpdateSignal> = rewards
+                        .iter()
+                        .map(|drop| UpdateSignal::GiveDrop {
+                            drop: *drop,
+                            source: DropSource::Quest(*quest_id),
+                        })
+                        .collect();
+                    ret.push(UpdateSignal::SaveGame);
+                    ret
+                }
             };
 
+            // achievements
+            for id in gs
+                .player_state
+                .achievements
+                .signal_handled(us, &gs.world, &gs.inventory)
+            {
+                gs.toasts
+                    .push(Toast::new(format!("Achievement unlocked! {}", id.title())));
+                sigs.push(UpdateSignal::SaveGame);
+            }
+
             new_signals.append(&mut sigs);
         }
 
@@ -1,12 +1,17 @@
 use crate::{drop_table::*, error::*, grid::*, item::*, tile::*, update_signal::*};
-use elara_engine::{change::*, error::Error as EngineError, vectors::*};
-use std::{collections::HashMap, fs::File, io::Write};
+use elara_engine::{change::*, error::Error as EngineError, platform_api::*, time::*, vectors::*};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::Write,
+};
 
 #[cfg(test)]
 pub mod tests;
 
 pub mod entity_id;
 pub mod global_mod;
+pub mod world_buff;
 pub mod world_cell;
 pub mod world_condition;
 pub mod world_condition_state;
@@ -14,6 +19,7 @@ pub mod world_layer;
 pub mod world_snapshot;
 
 pub use global_mod::*;
+pub use world_buff::*;
 
 pub use {
     entity_id::*, world_cell::*, world_condition::*, world_condition_state::*, world_layer::*,
@@ -27,6 +33,9 @@ const CONDITIONS_UPDATE_RANGE: i32 = 5;
 // Max value of global mod.
 const GLOBAL_MOD_MAX: f64 = 100.0;
 
+/// Removing a mod divides it back out, which can leave rounding error
+const GLOBAL_MOD_EPSILON: f64 = 0.000001;
+
 pub struct World {
     /// Get a WorldCell from grid pos.
     pub entity_map: HashMap<GridPos, WorldCell>,
@@ -41,6 +50,18 @@ pub struct World {
 
     // Global drop count modification. Applied to all tiles harvesting at this position
     pub drop_count_mod: HashMap<GridPos, f64>,
+
+    // Global harvest speed modification. Applied to all tiles harvesting at this position
+    pub harvest_speed_mod: HashMap<GridPos, f64>,
+
+    /// Temporary global mods, from potions
+    pub buffs: Vec<WorldBuff>,
+
+    /// Entities changed since the last save, including removed ones. Saves only write these.
+    pub changed_entities: HashSet<EntityID>,
+
+    /// Valids, global mods, buffs or the next entity id changed since the last save
+    pub globals_changed: bool,
 }
 
 impl World {
@@ -51,6 +72,10 @@ impl World {
             entities: HashMap::new(),
             next_entity_id: 0,
             drop_count_mod: HashMap::new(),
+            harvest_speed_mod: HashMap::new(),
+            buffs: vec![],
+            changed_entities: HashSet::new(),
+            globals_changed: false,
         }
     }
 
@@ -73,6 +98,7 @@ impl World {
             id: self.next_entity_id,
         };
         self.next_entity_id += 1;
+        self.globals_changed = true;
         ret
     }
 
@@ -104,7 +130,10 @@ impl World {
         let world_cell = self.get_entities(grid_pos);
         for (layer, eid) in &world_cell.layers {
             match self.entities.get_mut(&eid) {
-                Some(tile_inst) => tile_inst.tile_placed_ontop(tile, new_entity_id),
+                Some(tile_inst) => {
+                    tile_inst.tile_placed_ontop(tile, new_entity_id);
+                    self.changed_entities.insert(*eid);
+                }
 
                 // Nobody there to noify
                 None => {}
@@ -134,6 +163,7 @@ impl World {
                     ret.push(UpdateSignal::AddHarvestDrop {
                         drop: Drop::new_tile(t, 1),
                         origin: grid_pos,
+                        source: DropSource::Other,
                     });
                 }
             }
@@ -141,6 +171,8 @@ impl World {
 
         // add new entity
         self.entities.insert(new_entity_id, inst);
+        self.changed_entities.insert(new_entity_id);
+        self.globals_changed = true;
 
         // Add tile to grid map
         for p in &tile_def.footprint {
@@ -176,7 +208,16 @@ impl World {
         self.entities.clear();
         self.valids.clear();
         self.drop_count_mod.clear();
+        self.harvest_speed_mod.clear();
+        self.buffs.clear();
         self.next_entity_id = 0;
+        self.clear_changes();
+    }
+
+    /// Called after saving
+    pub fn clear_changes(&mut self) {
+        self.changed_entities.clear();
+        self.globals_changed = false;
     }
 
     /// Update all tile world conditions within the radius
@@ -194,35 +235,192 @@ impl World {
 
     /// Add or remove a global mod.
     pub fn update_global_mod(&mut self, origin: GridPos, global_mod: &GlobalMod, change: Change) {
+        let (mod_map, mod_val) = match global_mod.kind {
+            GlobalModKind::DropCount(drop_mod) => (&mut self.drop_count_mod, drop_mod),
+            GlobalModKind::HarvestSpeed(speed_mod) => (&mut self.harvest_speed_mod, speed_mod),
+        };
+        self.globals_changed = true;
+
         // set the modifications
-        match global_mod.kind {
-            GlobalModKind::DropCount(drop_mod) => {
-                for p_rel in &global_mod.positions {
-                    let pos = *p_rel + origin;
-                    let mut new_val: f64 = *self.drop_count_mod.get(&pos).unwrap_or(&1.0);
-
-                    match change {
-                        Change::Adding => new_val *= drop_mod.clamp(0.0, GLOBAL_MOD_MAX),
-                        Change::Removing => new_val /= drop_mod.clamp(0.0, GLOBAL_MOD_MAX),
-                    }
+        for p_rel in &global_mod.positions {
+            let pos = *p_rel + origin;
+            let mut new_val: f64 = *mod_map.get(&pos).unwrap_or(&1.0);
+
+            match change {
+                Change::Adding => new_val *= mod_val.clamp(0.0, GLOBAL_MOD_MAX),
+                Change::Removing => new_val /= mod_val.clamp(0.0, GLOBAL_MOD_MAX),
+            }
+
+            mod_map.insert(pos, new_val);
+        }
+    }
+
+    /// Start a buff and apply its global mod
+    pub fn add_buff(&mut self, kind: WorldBuffKind, origin: GridPos) {
+        let buff = WorldBuff::new(kind, origin);
+        self.update_global_mod(origin, &kind.global_mod(), Change::Adding);
+        self.buffs.push(buff);
+    }
+
+    /// Advance every tile and buff. Works the same online and offline.
+    /// delta_time is in seconds
+    #[must_use]
+    pub fn sim_update(&mut self, delta_time: f64, platform_api: &PlatformApi) -> Vec<UpdateSignal> {
+        let world_snapshot = self.get_world_snapshot();
+
+        // In id order so a seeded rand gives the same results every run
+        let mut ids: Vec<EntityID> = self.entities.keys().copied().collect();
+        ids.sort_by_key(|eid| eid.id);
+
+        let mut ret: Vec<UpdateSignal> = vec![];
+        for eid in ids {
+            let entity = self.entities.get_mut(&eid).unwrap();
+            let was_ready = entity.comp_harvest.as_ref().map(|h| h.can_harvest());
+
+            let mut sigs = entity.sim_update(delta_time, &world_snapshot, platform_api);
+
+            // Timers only counting on don't need saving again. Loading catches them up.
+            let ready = entity.comp_harvest.as_ref().map(|h| h.can_harvest());
+            if !sigs.is_empty() || ready != was_ready {
+                self.changed_entities.insert(eid);
+            }
+            ret.append(&mut sigs);
+        }
+        self.update_buffs(delta_time);
+
+        ret
+    }
+
+    /// Tick buff timers. Expired buffs are removed along with their global mod.
+    /// Only an expiry needs saving, as loading catches the timers up.
+    /// delta_time is in seconds
+    pub fn update_buffs(&mut self, delta_time: f64) {
+        for buff in &mut self.buffs {
+            buff.remaining = buff.remaining - Time::new(TimeUnit::Seconds(delta_time));
+        }
+
+        let (expired, active): (Vec<WorldBuff>, Vec<WorldBuff>) = std::mem::take(&mut self.buffs)
+            .into_iter()
+            .partition(|buff| buff.expired());
+        self.buffs = active;
+
+        for buff in expired {
+            self.update_global_mod(buff.origin, &buff.kind.global_mod(), Change::Removing);
+        }
+    }
 
-                    self.drop_count_mod.insert(pos, new_val);
+    /// Apply a potion at a position.
+    pub fn use_potion(&mut self, effect: PotionEffect, pos: GridPos) -> Result<(), Error> {
+        match effect {
+            PotionEffect::Buff(kind) => {
+                if !self.pos_valid(pos) {
+                    return Err(Error::NoPotionTarget);
                 }
+
+                self.add_buff(kind, pos);
+                Ok(())
+            }
+
+            PotionEffect::FinishHarvest => {
+                let world_cell = self.get_entities(pos);
+                for eid in world_cell.layers.values() {
+                    if let Some(harvest) = &mut self.get_entity_mut(eid).comp_harvest {
+                        if !harvest.can_harvest() {
+                            harvest.finish();
+                            return Ok(());
+                        }
+                    }
+                }
+
+                Err(Error::NoPotionTarget)
             }
         }
     }
 
-    /// Used for loading. Just insert the tile without running any global or local state updates
+    /// Used for loading. Just insert the tile without running any global or local state updates.
+    /// Call rebuild_derived once every tile is in.
     pub fn raw_insert_entity(&mut self, entity_id: EntityID, tile_instance: TileInstance) {
-        let tile_layer = tile_instance.tile_type.get_definition().world_layer;
+        self.entities.insert(entity_id, tile_instance);
+        self.changed_entities.insert(entity_id);
+    }
+
+    /// Rebuild the grid map, valids, global mods and world conditions from the entities and buffs.
+    /// Returns how the previous derived state differed. Valids are never removed, so only missing ones count.
+    pub fn rebuild_derived(&mut self) -> Vec<String> {
+        let prev_valids = std::mem::take(&mut self.valids);
+        let prev_drop_mod = std::mem::take(&mut self.drop_count_mod);
+        let prev_speed_mod = std::mem::take(&mut self.harvest_speed_mod);
+        self.entity_map.clear();
 
-        let mut world_cell: &mut WorldCell = self
-            .entity_map
-            .entry(tile_instance.grid_pos)
-            .or_insert(WorldCell::new());
-        world_cell.layers.insert(tile_layer, entity_id);
+        // In id order, so the newest tile wins a cell claimed twice
+        let mut ids: Vec<EntityID> = self.entities.keys().copied().collect();
+        ids.sort_by_key(|eid| eid.id);
 
-        self.entities.insert(entity_id, tile_instance);
+        let mut overlaps: usize = 0;
+        for eid in ids {
+            let grid_pos = self.entities[&eid].grid_pos;
+            let tile_def = self.entities[&eid].tile_type.get_definition();
+
+            for p in &tile_def.footprint {
+                let pos = grid_pos + *p;
+
+                self.valids.insert(pos, true);
+                self.valids.insert(GridPos::new(pos.x + 1, pos.y), true);
+                self.valids.insert(GridPos::new(pos.x - 1, pos.y), true);
+                self.valids.insert(GridPos::new(pos.x, pos.y + 1), true);
+                self.valids.insert(GridPos::new(pos.x, pos.y - 1), true);
+
+                let world_cell: &mut WorldCell =
+                    self.entity_map.entry(pos).or_insert(WorldCell::new());
+                if world_cell
+                    .layers
+                    .insert(tile_def.world_layer, eid)
+                    .is_some()
+                {
+                    overlaps += 1;
+                }
+            }
+
+            for gm in &tile_def.placement_global_mod {
+                self.update_global_mod(grid_pos, gm, Change::Adding);
+            }
+        }
+
+        for buff in self.buffs.clone() {
+            self.update_global_mod(buff.origin, &buff.kind.global_mod(), Change::Adding);
+        }
+
+        let snapshot = self.get_world_snapshot();
+        for tile in self.entities.values_mut() {
+            tile.update_world_conditions(&snapshot);
+        }
+
+        let missing_valids = self
+            .valids
+            .keys()
+            .filter(|pos| !prev_valids.contains_key(pos))
+            .count();
+        self.valids.extend(prev_valids);
+
+        let mut ret: Vec<String> = vec![];
+        let counts = [
+            ("tile cells claimed twice", overlaps),
+            ("valid positions missing", missing_valids),
+            (
+                "drop mods wrong",
+                global_mod_mismatches(&prev_drop_mod, &self.drop_count_mod),
+            ),
+            (
+                "speed mods wrong",
+                global_mod_mismatches(&prev_speed_mod, &self.harvest_speed_mod),
+            ),
+        ];
+        for (what, count) in counts {
+            if count > 0 {
+                ret.push(format!("{} {}", count, what));
+            }
+        }
+        ret
     }
 
     /// Returns list of tile types removed.
@@ -271,14 +469,21 @@ impl World {
         let mut types_removing: Vec<TileType> = vec![];
 
         if let Some(tile_inst_removed) = self.entities.remove(&eid) {
+            self.changed_entities.insert(eid);
+            let footprint = &tile_inst_removed.tile_type.get_definition().footprint;
+
             // remove the tile references from the grid map
-            for p in &tile_inst_removed.tile_type.get_definition().footprint {
+            for p in footprint {
                 let pos = tile_inst_removed.grid_pos + *p;
 
                 let mut world_cell: &mut WorldCell =
                     self.entity_map.entry(pos).or_insert(WorldCell::new());
                 world_cell.layers.remove(&layer_removing);
+            }
 
+            // Only after every reference is gone. Otherwise the validity checks see a half removed tile.
+            for p in footprint {
+                let pos = tile_inst_removed.grid_pos + *p;
                 types_removing.append(&mut self.remove_invalid(pos));
             }
 
@@ -296,10 +501,12 @@ impl World {
             entity_harvest_perc: HashMap::new(),
             valids: self.valids.clone(),
             drop_count_mod: HashMap::new(),
+            harvest_speed_mod: HashMap::new(),
         };
 
         ret.entity_map = self.entity_map.clone();
         ret.drop_count_mod = self.drop_count_mod.clone();
+        ret.harvest_speed_mod = self.harvest_speed_mod.clone();
 
         for (grid_pos, world_layer) in &self.entity_map {
             // for eid in value {
@@ -381,9 +588,25 @@ impl World {
     }
 
     /// Get an entity. Expects the entity to be valid. This is an assumption that must be upheld.
+    /// The entity is written with the next save.
     pub fn get_entity_mut(&mut self, eid: &EntityID) -> &mut TileInstance {
+        self.changed_entities.insert(*eid);
         self.entities
             .get_mut(eid)
             .expect(&format!("Invalid entity id {:?}", eid))
     }
 }
+
+/// Positions where two global mod maps differ. A missing position is the same as 1.0.
+fn global_mod_mismatches(a: &HashMap<GridPos, f64>, b: &HashMap<GridPos, f64>) -> usize {
+    let positions: HashSet<&GridPos> = a.keys().chain(b.keys()).collect();
+
+    positions
+        .into_iter()
+        .filter(|pos| {
+            let a_val = a.get(pos).unwrap_or(&1.0);
+            let b_val = b.get(pos).unwrap_or(&1.0);
+            (a_val - b_val).abs() > GLOBAL_MOD_EPSILON
+        })
+        .count()
+}
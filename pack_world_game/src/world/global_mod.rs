@@ -4,6 +4,9 @@ use crate::grid::*;
 pub enum GlobalModKind {
     /// Modify the drop count. This is multiplicative
     DropCount(f64),
+
+    /// Modify the harvest speed. This is multiplicative
+    HarvestSpeed(f64),
 }
 
 pub struct GlobalMod {
@@ -8,7 +8,7 @@ mod tests {
     pub fn signal_contains_drop(sigs: &Vec<UpdateSignal>, drop_type: DropType) -> bool {
         for sig in sigs {
             match sig {
-                UpdateSignal::AddHarvestDrop { drop, origin } => {
+                UpdateSignal::AddHarvestDrop { drop, .. } => {
                     if drop.drop_type == drop_type {
                         return true;
                     }
@@ -164,6 +164,31 @@ mod tests {
         validate_grid(&world);
     }
 
+    // Removing a tile can cascade into removing a larger one. Validity checks during that cascade must not see a half removed tile.
+    #[test]
+    pub fn cascade_remove_henge() {
+        let mut world = World::new();
+
+        for x in 0..5 {
+            for y in 0..5 {
+                let _ = world.insert_tile_type(GridPos::new(x, y), TileType::Dirt);
+            }
+        }
+
+        world
+            .try_place_tile(GridPos::new(0, 0), TileType::MudChicken)
+            .unwrap();
+        world
+            .try_place_tile(GridPos::new(1, 3), TileType::MudHenge)
+            .unwrap();
+
+        let _ = world
+            .try_place_tile(GridPos::new(2, 2), TileType::Dirt)
+            .unwrap();
+
+        validate_grid(&world);
+    }
+
     #[test]
     pub fn global_drop_count_mod() {
         let mut world = World::new();
@@ -0,0 +1,167 @@
+use crate::{error::*, grid::*, save_file::*, world::*};
+use elara_engine::time::*;
+
+/// Radius of the double drops buff
+const DOUBLE_DROPS_RADIUS: i32 = 2;
+
+/// Radius of the harvest speed buff
+const HARVEST_SPEED_RADIUS: i32 = 3;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WorldBuffKind {
+    DoubleDrops,
+    HarvestSpeed,
+}
+
+impl WorldBuffKind {
+    pub fn title(&self) -> &str {
+        match self {
+            WorldBuffKind::DoubleDrops => "Double Drops",
+            WorldBuffKind::HarvestSpeed => "Harvest Speed",
+        }
+    }
+
+    pub fn length(&self) -> Time {
+        match self {
+            WorldBuffKind::DoubleDrops => Time::new(TimeUnit::Seconds(30.0 * 60.0)),
+            WorldBuffKind::HarvestSpeed => Time::new(TimeUnit::Hours(1.0)),
+        }
+    }
+
+    /// The global mod this buff applies while active
+    pub fn global_mod(&self) -> GlobalMod {
+        match self {
+            WorldBuffKind::DoubleDrops => GlobalMod::new(
+                GlobalModKind::DropCount(2.0),
+                GridPos::new(0, 0)
+                    .to_radius_iter(DOUBLE_DROPS_RADIUS)
+                    .collect(),
+            ),
+            WorldBuffKind::HarvestSpeed => GlobalMod::new(
+                GlobalModKind::HarvestSpeed(2.0),
+                GridPos::new(0, 0)
+                    .to_radius_iter(HARVEST_SPEED_RADIUS)
+                    .collect(),
+            ),
+        }
+    }
+
+    pub fn to_index(&self) -> i32 {
+        match self {
+            WorldBuffKind::DoubleDrops => 0,
+            WorldBuffKind::HarvestSpeed => 1,
+        }
+    }
+
+    pub fn from_index(index: i32) -> Result<Self, Error> {
+        match index {
+            0 => Ok(WorldBuffKind::DoubleDrops),
+            1 => Ok(WorldBuffKind::HarvestSpeed),
+            _ => Err(Error::UnknownWorldBuffID(index)),
+        }
+    }
+}
+
+/// A temporary global mod. Removed from the world when the time runs out.
+#[derive(Debug, Clone)]
+pub struct WorldBuff {
+    pub kind: WorldBuffKind,
+    pub origin: GridPos,
+    pub remaining: Time,
+}
+
+impl WorldBuff {
+    pub fn new(kind: WorldBuffKind, origin: GridPos) -> Self {
+        Self {
+            kind,
+            origin,
+            remaining: kind.length(),
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        !self.remaining.greater_than_zero()
+    }
+
+    pub fn save_file_write(
+        &self,
+        key_parent: String,
+        save_file: &mut SaveFile,
+    ) -> Result<(), Error> {
+        save_file.save_i32(&format!("{}.k", key_parent), self.kind.to_index());
+        save_file.save_i32(&format!("{}.x", key_parent), self.origin.x);
+        save_file.save_i32(&format!("{}.y", key_parent), self.origin.y);
+        save_file.save_f64(
+            &format!("{}.r", key_parent),
+            self.remaining.as_milliseconds().value(),
+        );
+
+        Ok(())
+    }
+
+    pub fn save_file_load(key_parent: String, save_file: &SaveFile) -> Result<Self, Error> {
+        let kind = WorldBuffKind::from_index(save_file.load_i32(&format!("{}.k", key_parent))?)?;
+        let origin = GridPos::new(
+            save_file.load_i32(&format!("{}.x", key_parent))?,
+            save_file.load_i32(&format!("{}.y", key_parent))?,
+        );
+        let remaining = Time::new(TimeUnit::MilliSeconds(
+            save_file.load_f64(&format!("{}.r", key_parent))?,
+        ));
+
+        Ok(Self {
+            kind,
+            origin,
+            remaining,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tile::*;
+
+    #[test]
+    fn save_load() {
+        let mut save_file = SaveFile::new();
+
+        let mut orig = WorldBuff::new(WorldBuffKind::HarvestSpeed, GridPos::new(-3, 7));
+        orig.remaining = Time::new(TimeUnit::Seconds(120.0));
+        orig.save_file_write("buff".into(), &mut save_file).unwrap();
+
+        let loaded = WorldBuff::save_file_load("buff".into(), &save_file).unwrap();
+        assert_eq!(loaded.kind, orig.kind);
+        assert_eq!(loaded.origin, orig.origin);
+        assert_eq!(loaded.remaining.as_seconds().value(), 120.0);
+    }
+
+    #[test]
+    fn expiry_removes_mod() {
+        let mut world = World::new();
+
+        world.add_buff(WorldBuffKind::DoubleDrops, GridPos::new(0, 0));
+        assert_eq!(*world.drop_count_mod.get(&GridPos::new(1, 1)).unwrap(), 2.0);
+        assert!(world.drop_count_mod.get(&GridPos::new(5, 5)).is_none());
+
+        world.update_buffs(60.0);
+        assert_eq!(world.buffs.len(), 1);
+        assert_eq!(*world.drop_count_mod.get(&GridPos::new(1, 1)).unwrap(), 2.0);
+
+        world.update_buffs(60.0 * 60.0);
+        assert_eq!(world.buffs.len(), 0);
+        assert_eq!(*world.drop_count_mod.get(&GridPos::new(1, 1)).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn stacks_with_tiles() {
+        let mut world = World::new();
+
+        let _ = world.insert_tile_type(GridPos::new(2, 5), TileType::Spring);
+        world.add_buff(WorldBuffKind::DoubleDrops, GridPos::new(2, 4));
+        assert_eq!(*world.drop_count_mod.get(&GridPos::new(2, 4)).unwrap(), 4.0);
+
+        world.update_buffs(60.0 * 60.0);
+        assert_eq!(*world.drop_count_mod.get(&GridPos::new(2, 4)).unwrap(), 2.0);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{tile_type::TileType, world::*};
+use crate::{tile::TileType, world::*};
 
 /// A 'world query'.
 /// Used to check if something is true about the world.
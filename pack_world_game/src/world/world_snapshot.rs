@@ -1,31 +1,8 @@
 use crate::{grid::*, tile::*, world::*};
 use std::collections::HashMap;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum TileSnapshot {
-    Dirt,
-    Water,
-    Grass,
-    Boulder,
-    OakTree { has_nest: bool },
-    BirdNest,
-    Cave,
-    Shrub,
-    MudPit,
-    TallGrass,
-    Frog,
-    Newt,
-    Reed,
-    Clam,
-    MudFish,
-    Spring,
-    Kelp,
-    Crab,
-    MudHenge,
-    MudChicken,
-    Goblin,
-    MudPig,
-}
+// TileSnapshot is generated from design/tiles.txt
+pub use crate::tile::TileSnapshot;
 
 /// Snapshot of world state.
 /// Allows world entities to interact with eachother without needing references to eachother.
@@ -38,6 +15,7 @@ pub struct WorldSnapshot {
 
     pub valids: HashMap<GridPos, bool>,
     pub drop_count_mod: HashMap<GridPos, f64>,
+    pub harvest_speed_mod: HashMap<GridPos, f64>,
 }
 
 impl WorldSnapshot {
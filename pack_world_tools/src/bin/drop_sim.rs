@@ -0,0 +1,134 @@
+//! Monte Carlo report for a drop table or pack.
+//!
+//! drop_sim list
+//! drop_sim <table or pack> [--pulls N] [--trials N] [--seed N] [--weight INDEX=WEIGHT]...
+//!
+//! Any --weight runs the table a second time with the changed weights and prints a comparison.
+
+use pack_world_game::{drop_table::*, pack::pack_id::*};
+use pack_world_tools::{drop_sim::*, headless::*};
+
+const USAGE: &str =
+    "usage: drop_sim <table or pack> [--pulls N] [--trials N] [--seed N] [--weight INDEX=WEIGHT]...\n       drop_sim list";
+
+fn find_target(name: &str) -> Option<SimTarget> {
+    let name = name.to_lowercase();
+
+    for pack_id in ALL_PACKS.iter() {
+        if format!("{:?}", pack_id).to_lowercase() == name || pack_id.to_string_id() == name {
+            return Some(SimTarget::from_pack(*pack_id));
+        }
+    }
+
+    for table_id in ALL_FIXED_TABLES.iter() {
+        if format!("{:?}", table_id).to_lowercase() == name {
+            return Some(SimTarget::from_table(*table_id));
+        }
+    }
+
+    None
+}
+
+fn list() {
+    println!("packs");
+    for pack_id in ALL_PACKS.iter() {
+        println!("  {}", pack_id.to_string_id());
+    }
+
+    println!("tables");
+    for table_id in ALL_FIXED_TABLES.iter() {
+        if let FixedTableID::Pack(_) = table_id {
+            continue;
+        }
+
+        println!("  {:?}", table_id);
+        for (i, (output, weight)) in get_fixed_table(*table_id).list_entries().iter().enumerate() {
+            println!(
+                "    {} {:?} x{} weight {}",
+                i, output.ty, output.amount, weight
+            );
+        }
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next().and_then(|val| val.parse().ok()) {
+        Some(val) => val,
+        None => {
+            eprintln!("{} needs a value\n{}", flag, USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+
+    let Some(name) = args.next() else {
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    };
+    if name == "list" {
+        list();
+        return;
+    }
+
+    let Some(target) = find_target(&name) else {
+        eprintln!("Unknown table or pack {}. Run drop_sim list.", name);
+        std::process::exit(1);
+    };
+
+    let mut pulls: i64 = 100_000;
+    let mut trials: i64 = 2_000;
+    let mut seed: u64 = 1;
+    let mut weights: Vec<(usize, f64)> = vec![];
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--pulls" => pulls = parse_arg(&mut args, &flag),
+            "--trials" => trials = parse_arg(&mut args, &flag),
+            "--seed" => seed = parse_arg(&mut args, &flag),
+            "--weight" => {
+                let val: String = parse_arg(&mut args, &flag);
+                let parsed = val
+                    .split_once('=')
+                    .and_then(|(i, w)| Some((i.parse().ok()?, w.parse().ok()?)));
+
+                match parsed {
+                    Some((i, w)) if i < target.table.list_entries().len() => weights.push((i, w)),
+                    _ => {
+                        eprintln!(
+                            "Bad --weight {}. Use an entry index from drop_sim list.",
+                            val
+                        );
+                        std::process::exit(1);
+                    }
+                }
+            }
+            _ => {
+                eprintln!("Unknown option {}\n{}", flag, USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let platform_api = headless_platform_api();
+
+    seed_rng(seed);
+    let before = run(&target, pulls, trials, &platform_api);
+    println!("{}", before.render());
+
+    if !weights.is_empty() {
+        let mut changed = target.clone();
+        changed.name = format!("{} (changed)", target.name);
+        for (i, weight) in &weights {
+            changed.table.set_weight(*i, *weight);
+        }
+
+        // same seed so only the weights differ
+        seed_rng(seed);
+        let after = run(&changed, pulls, trials, &platform_api);
+        println!("{}", after.render());
+        println!("{}", before.render_compare(&after));
+    }
+}
@@ -0,0 +1,68 @@
+//! Plays the game headlessly with a scripted strategy and prints when progress happens.
+//!
+//! econ_sim [--strategy greedy|saver] [--days N] [--session-min N] [--away-h N] [--premium] [--seed N]
+
+use pack_world_tools::{econ_sim::*, headless::*};
+
+const USAGE: &str = "usage: econ_sim [--strategy greedy|saver] [--days N] [--session-min N] [--away-h N] [--premium] [--seed N]";
+
+fn parse_arg<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next().and_then(|val| val.parse().ok()) {
+        Some(val) => val,
+        None => {
+            eprintln!("{} needs a value\n{}", flag, USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run(strategy: &mut impl Strategy, config: &SimConfig, seed: u64) {
+    let platform_api = headless_platform_api();
+    seed_rng(seed);
+    set_time_ms(0.0);
+
+    println!(
+        "{} strategy, {} days, {} minute sessions every {} hours, {} offline cap\n",
+        strategy.name(),
+        config.days,
+        config.session_s / 60.0,
+        (config.session_s + config.away_s) / HOUR_S,
+        if config.premium { "premium" } else { "free" }
+    );
+
+    let mut sim = EconSim::new();
+    sim.run(strategy, config, &platform_api);
+    println!("{}", sim.render_timeline());
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+
+    let mut config = SimConfig::new();
+    let mut strategy = String::from("greedy");
+    let mut seed: u64 = 1;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--strategy" => strategy = parse_arg(&mut args, &flag),
+            "--days" => config.days = parse_arg(&mut args, &flag),
+            "--session-min" => config.session_s = parse_arg::<f64>(&mut args, &flag) * 60.0,
+            "--away-h" => config.away_s = parse_arg::<f64>(&mut args, &flag) * HOUR_S,
+            "--premium" => config.premium = true,
+            "--seed" => seed = parse_arg(&mut args, &flag),
+            _ => {
+                eprintln!("Unknown option {}\n{}", flag, USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    match strategy.as_str() {
+        "greedy" => run(&mut GreedyStrategy {}, &config, seed),
+        "saver" => run(&mut SaverStrategy::new(), &config, seed),
+        _ => {
+            eprintln!("Unknown strategy {}\n{}", strategy, USAGE);
+            std::process::exit(1);
+        }
+    }
+}
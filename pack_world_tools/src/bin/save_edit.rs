@@ -0,0 +1,119 @@
+//! Prints a save as a table or JSON, and optionally writes an edited copy.
+//!
+//! save_edit FILE [--slot N] [--json] [--give ITEM=N]... [--gold N] [--move ID=X,Y]... [--remove ID]... [--out FILE]
+
+use pack_world_game::grid::*;
+use pack_world_tools::{headless::*, save_edit::*};
+
+const USAGE: &str = "usage: save_edit FILE [--slot N] [--json] [--give ITEM=N]... [--gold N] [--move ID=X,Y]... [--remove ID]... [--out FILE]";
+
+fn fail(message: String) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    std::process::exit(1);
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next().and_then(|val| val.parse().ok()) {
+        Some(val) => val,
+        None => fail(format!("{} needs a value", flag)),
+    }
+}
+
+/// Splits NAME=VALUE
+fn parse_pair(input: &str, flag: &str) -> (String, String) {
+    match input.split_once('=') {
+        Some((name, value)) => (name.to_string(), value.to_string()),
+        None => fail(format!("{} expects NAME=VALUE, got {}", flag, input)),
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let Some(path) = args.next() else {
+        fail(String::from("No save file given"));
+    };
+
+    let platform_api = headless_platform_api();
+    let data = std::fs::read(&path).unwrap_or_else(|e| fail(format!("Can't read {}. {}", path, e)));
+
+    // The slot has to be known before loading, so it's only taken straight after the file
+    let mut args = args.peekable();
+    let mut slot: Option<usize> = None;
+    if args.peek().map(|flag| flag.as_str()) == Some("--slot") {
+        let flag = args.next().unwrap();
+        slot = Some(parse_arg(&mut args, &flag));
+    }
+
+    let mut edit = SaveEdit::load(&data, slot, &platform_api)
+        .unwrap_or_else(|e| fail(format!("Can't load {}. {:?}", path, e)));
+
+    let mut json = false;
+    let mut out: Option<String> = None;
+    let mut edited = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--json" => json = true,
+            "--out" => out = Some(parse_arg(&mut args, &flag)),
+            "--give" => {
+                let (name, count) = parse_pair(&parse_arg::<String>(&mut args, &flag), &flag);
+                let Some(item_type) = parse_item(&name) else {
+                    fail(format!("Unknown item {}", name));
+                };
+                let Ok(count) = count.parse::<i64>() else {
+                    fail(format!("Bad count {}", count));
+                };
+                if let Err(e) = edit.give_item(item_type, count) {
+                    fail(format!("Can't give {}. {:?}", name, e));
+                }
+                edited = true;
+            }
+            "--gold" => {
+                edit.set_gold(parse_arg(&mut args, &flag));
+                edited = true;
+            }
+            "--move" => {
+                let (id, pos) = parse_pair(&parse_arg::<String>(&mut args, &flag), &flag);
+                let parsed = pos
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((x.parse::<i32>().ok()?, y.parse::<i32>().ok()?)));
+                let (Ok(id), Some((x, y))) = (id.parse::<u64>(), parsed) else {
+                    fail(format!("{} expects ID=X,Y", flag));
+                };
+                if let Err(e) = edit.move_tile(id, GridPos::new(x, y)) {
+                    fail(format!("Can't move tile {}. {:?}", id, e));
+                }
+                edited = true;
+            }
+            "--remove" => {
+                let id: u64 = parse_arg(&mut args, &flag);
+                match edit.remove_tile(id) {
+                    Ok(removed) => eprintln!("Removed {:?}", removed),
+                    Err(e) => fail(format!("Can't remove tile {}. {:?}", id, e)),
+                }
+                edited = true;
+            }
+            _ => fail(format!("Unknown option {}", flag)),
+        }
+    }
+
+    if json {
+        println!("{}", edit.render_json());
+    } else {
+        println!("{}", edit.render_table());
+    }
+
+    match out {
+        Some(out) => {
+            let data = edit
+                .save(&platform_api)
+                .unwrap_or_else(|e| fail(format!("Can't build save. {:?}", e)));
+            if let Err(e) = std::fs::write(&out, data) {
+                fail(format!("Can't write {}. {}", out, e));
+            }
+            eprintln!("Wrote {}", out);
+        }
+        None if edited => fail(String::from("Edits need --out to be written")),
+        None => {}
+    }
+}
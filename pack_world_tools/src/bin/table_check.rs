@@ -0,0 +1,35 @@
+//! Static report on the fixed drop tables. Nothing is pulled, so the result is the same every run.
+//!
+//! table_check
+//!
+//! Exits with 1 if any problem is found.
+
+use pack_world_game::drop_table::*;
+
+fn main() {
+    if std::env::args().len() > 1 {
+        eprintln!("usage: table_check");
+        std::process::exit(1);
+    }
+
+    let graph = TableGraph::from_fixed_tables();
+    let problems = graph.validate(&obtainable_item_types());
+
+    println!(
+        "{} tables, {} roots, {} tile harvests",
+        graph.tables.len(),
+        graph.roots.len(),
+        graph.harvests.len()
+    );
+
+    if problems.is_empty() {
+        println!("no problems");
+        return;
+    }
+
+    println!("{} problems", problems.len());
+    for problem in &problems {
+        println!("  {}", problem.display());
+    }
+    std::process::exit(1);
+}
@@ -0,0 +1,459 @@
+use elara_engine::platform_api::*;
+use pack_world_game::{drop_table::*, pack::*};
+
+/// Drops at or under this chance per pull get percentile reports
+pub const RARE_CHANCE: f64 = 0.05;
+
+/// A trial gives up after this many pulls
+const MAX_TRIAL_PULLS: i64 = 1_000_000;
+
+/// Inclusion exclusion is 2^n, so only do it exactly for small tables
+const MAX_EXACT_COLLECT: usize = 20;
+
+/// Something to pull from
+#[derive(Clone)]
+pub struct SimTarget {
+    pub name: String,
+    pub table: DropTable,
+
+    /// Pulls from packs include the pack's pity rule
+    pub pack_id: Option<PackID>,
+}
+
+impl SimTarget {
+    pub fn from_table(table_id: FixedTableID) -> Self {
+        if let FixedTableID::Pack(pack_id) = table_id {
+            return Self::from_pack(pack_id);
+        }
+
+        Self {
+            name: format!("{:?}", table_id),
+            table: get_fixed_table(table_id).clone(),
+            pack_id: None,
+        }
+    }
+
+    pub fn from_pack(pack_id: PackID) -> Self {
+        let pack = pack_id.get_pack_info();
+        Self {
+            name: format!("{} Pack", pack.display_name),
+            table: get_fixed_table(pack.table_id).clone(),
+            pack_id: Some(pack_id),
+        }
+    }
+
+    /// Pulls in one pack, or one for plain tables
+    pub fn pulls_per_open(&self) -> i64 {
+        match self.pack_id {
+            Some(pack_id) => pack_id.get_pack_info().content_count as i64,
+            None => 1,
+        }
+    }
+
+    fn pull(&self, pity: &mut PityCounters, platform_api: &PlatformApi) -> Drop {
+        let rule = self.pack_id.and_then(|pack_id| {
+            pack_id
+                .get_pack_info()
+                .pity
+                .as_ref()
+                .map(|rule| (pack_id, rule))
+        });
+
+        match rule {
+            Some((pack_id, rule)) => {
+                let drop = rule.pull(&self.table, pity.misses(pack_id), platform_api);
+                pity.record_pull(pack_id, &drop);
+                drop
+            }
+            None => {
+                let mut tables_visited: Vec<FixedTableID> = vec![];
+                self.table.pull(&mut tables_visited, platform_api)
+            }
+        }
+    }
+}
+
+/// Pulls until a rare drop appears
+#[derive(Debug, Clone, PartialEq)]
+pub struct RareReport {
+    pub drop_type: DropType,
+    pub chance: f64,
+
+    /// Pulls needed for 50, 90 and 99 percent of players
+    pub p50: i64,
+    pub p90: i64,
+    pub p99: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimReport {
+    pub name: String,
+    pub pulls: i64,
+    pub trials: i64,
+    pub pulls_per_open: i64,
+
+    /// Exact odds of the table, before pity
+    pub expected: Vec<DropOdds>,
+
+    /// How many of each drop the pulls gave
+    pub counts: Vec<(Drop, i64)>,
+
+    pub gold_per_pull: f64,
+    pub expected_gold_per_pull: f64,
+
+    /// Exact pulls to see every drop type. None if the table is too big.
+    pub expected_collect_all: Option<f64>,
+    pub mean_collect_all: f64,
+
+    pub rare: Vec<RareReport>,
+}
+
+/// Gold the drop is worth to the merchant
+pub fn drop_value(drop: &Drop) -> i64 {
+    match drop.drop_type {
+        DropType::Gold => drop.amount,
+        DropType::Item { item_type } => item_type.sell_value() * drop.amount,
+    }
+}
+
+/// Chance of each drop type, merging different amounts
+pub fn drop_type_odds(odds: &Vec<DropOdds>) -> Vec<(DropType, f64)> {
+    let mut ret: Vec<(DropType, f64)> = vec![];
+    for o in odds {
+        match ret
+            .iter_mut()
+            .find(|(drop_type, _)| *drop_type == o.drop.drop_type)
+        {
+            Some((_, chance)) => *chance += o.chance,
+            None => ret.push((o.drop.drop_type, o.chance)),
+        }
+    }
+    ret
+}
+
+/// Expected pulls to see every outcome at least once, for unequal chances.
+/// Inclusion exclusion over every subset of outcomes.
+pub fn expected_collect_all(chances: &Vec<f64>) -> Option<f64> {
+    if chances.len() > MAX_EXACT_COLLECT || chances.iter().any(|chance| *chance <= 0.0) {
+        return None;
+    }
+
+    let mut ret = 0.0;
+    for mask in 1u32..(1u32 << chances.len()) {
+        let subset: f64 = (0..chances.len())
+            .filter(|i| mask & (1 << i) != 0)
+            .map(|i| chances[i])
+            .sum();
+
+        if mask.count_ones() % 2 == 1 {
+            ret += 1.0 / subset;
+        } else {
+            ret -= 1.0 / subset;
+        }
+    }
+
+    Some(ret)
+}
+
+/// Value at the percentile of an ascending list
+pub fn percentile(sorted: &Vec<i64>, pct: f64) -> i64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+
+    let i = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[i.clamp(1, sorted.len()) - 1]
+}
+
+pub fn run(target: &SimTarget, pulls: i64, trials: i64, platform_api: &PlatformApi) -> SimReport {
+    let expected = target.table.drop_odds();
+    let type_odds = drop_type_odds(&expected);
+
+    // distribution and value
+    let mut counts: Vec<(Drop, i64)> = vec![];
+    let mut gold: i64 = 0;
+    {
+        let mut pity = PityCounters::new();
+        for _ in 0..pulls {
+            let drop = target.pull(&mut pity, platform_api);
+            gold += drop_value(&drop);
+
+            match counts.iter_mut().find(|(d, _)| *d == drop) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((drop, 1)),
+            }
+        }
+    }
+
+    // trials of a fresh player pulling until they've seen everything
+    let rare_types: Vec<(DropType, f64)> = type_odds
+        .iter()
+        .filter(|(_, chance)| *chance <= RARE_CHANCE)
+        .copied()
+        .collect();
+    let mut rare_pulls: Vec<Vec<i64>> = vec![vec![]; rare_types.len()];
+    let mut collect_total: i64 = 0;
+
+    for _ in 0..trials {
+        let mut pity = PityCounters::new();
+        let mut seen: Vec<DropType> = vec![];
+        let mut trial_pulls: i64 = 0;
+
+        while seen.len() < type_odds.len() && trial_pulls < MAX_TRIAL_PULLS {
+            let drop = target.pull(&mut pity, platform_api);
+            trial_pulls += 1;
+
+            if !seen.contains(&drop.drop_type) {
+                seen.push(drop.drop_type);
+
+                if let Some(i) = rare_types
+                    .iter()
+                    .position(|(drop_type, _)| *drop_type == drop.drop_type)
+                {
+                    rare_pulls[i].push(trial_pulls);
+                }
+            }
+        }
+
+        collect_total += trial_pulls;
+    }
+
+    let rare = rare_types
+        .iter()
+        .zip(rare_pulls.iter_mut())
+        .map(|((drop_type, chance), samples)| {
+            samples.sort();
+            RareReport {
+                drop_type: *drop_type,
+                chance: *chance,
+                p50: percentile(samples, 50.0),
+                p90: percentile(samples, 90.0),
+                p99: percentile(samples, 99.0),
+            }
+        })
+        .collect();
+
+    let chances: Vec<f64> = type_odds.iter().map(|(_, chance)| *chance).collect();
+
+    SimReport {
+        name: target.name.clone(),
+        pulls,
+        trials,
+        pulls_per_open: target.pulls_per_open(),
+        expected_gold_per_pull: expected
+            .iter()
+            .map(|odds| odds.chance * drop_value(&odds.drop) as f64)
+            .sum(),
+        expected,
+        counts,
+        gold_per_pull: gold as f64 / pulls.max(1) as f64,
+        expected_collect_all: expected_collect_all(&chances),
+        mean_collect_all: collect_total as f64 / trials.max(1) as f64,
+        rare,
+    }
+}
+
+fn drop_name(drop_type: DropType) -> String {
+    match drop_type {
+        DropType::Gold => "Gold".into(),
+        DropType::Item { item_type } => item_type.user_title().to_string(),
+    }
+}
+
+impl SimReport {
+    fn count(&self, drop: &Drop) -> i64 {
+        self.counts
+            .iter()
+            .find(|(d, _)| d == drop)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        out += &format!(
+            "{}: {} pulls, {} trials, {} pulls per open\n\n",
+            self.name, self.pulls, self.trials, self.pulls_per_open
+        );
+
+        out += &format!(
+            "{:<20} {:>8} {:>10} {:>10} {:>8}\n",
+            "drop", "amount", "expected", "actual", "diff"
+        );
+        for odds in &self.expected {
+            let actual = self.count(&odds.drop) as f64 / self.pulls.max(1) as f64;
+            out += &format!(
+                "{:<20} {:>8} {:>9.3}% {:>9.3}% {:>+7.3}%\n",
+                drop_name(odds.drop.drop_type),
+                odds.drop.amount,
+                odds.chance * 100.0,
+                actual * 100.0,
+                (actual - odds.chance) * 100.0
+            );
+        }
+
+        out += &format!(
+            "\ngold value per pull: {:.2} (expected {:.2})\n",
+            self.gold_per_pull, self.expected_gold_per_pull
+        );
+        out += &format!(
+            "gold value per open: {:.2}\n",
+            self.gold_per_pull * self.pulls_per_open as f64
+        );
+
+        match self.expected_collect_all {
+            Some(exact) => {
+                out += &format!(
+                    "pulls to collect all: {:.1} (exact without pity {:.1})\n",
+                    self.mean_collect_all, exact
+                )
+            }
+            None => out += &format!("pulls to collect all: {:.1}\n", self.mean_collect_all),
+        }
+
+        if !self.rare.is_empty() {
+            out += &format!(
+                "\n{:<20} {:>8} {:>8} {:>8} {:>8}\n",
+                "rare drop", "chance", "p50", "p90", "p99"
+            );
+            for rare in &self.rare {
+                out += &format!(
+                    "{:<20} {:>7.3}% {:>8} {:>8} {:>8}\n",
+                    drop_name(rare.drop_type),
+                    rare.chance * 100.0,
+                    rare.p50,
+                    rare.p90,
+                    rare.p99
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Side by side of two runs, for checking a balance change
+    pub fn render_compare(&self, after: &SimReport) -> String {
+        let mut out = String::new();
+
+        out += &format!("{} -> {}\n\n", self.name, after.name);
+        out += &format!(
+            "{:<20} {:>10} {:>10} {:>8}\n",
+            "drop", "before", "after", "diff"
+        );
+
+        let before_types = drop_type_odds(&self.expected);
+        let after_types = drop_type_odds(&after.expected);
+
+        let mut all_types: Vec<DropType> = before_types.iter().map(|(t, _)| *t).collect();
+        for (drop_type, _) in &after_types {
+            if !all_types.contains(drop_type) {
+                all_types.push(*drop_type);
+            }
+        }
+
+        let chance_of = |list: &Vec<(DropType, f64)>, drop_type: DropType| {
+            list.iter()
+                .find(|(t, _)| *t == drop_type)
+                .map_or(0.0, |(_, chance)| *chance)
+        };
+
+        for drop_type in all_types {
+            let before = chance_of(&before_types, drop_type);
+            let after = chance_of(&after_types, drop_type);
+            out += &format!(
+                "{:<20} {:>9.3}% {:>9.3}% {:>+7.3}%\n",
+                drop_name(drop_type),
+                before * 100.0,
+                after * 100.0,
+                (after - before) * 100.0
+            );
+        }
+
+        out += &format!(
+            "\ngold value per pull: {:.2} -> {:.2}\n",
+            self.expected_gold_per_pull, after.expected_gold_per_pull
+        );
+        out += &format!(
+            "pulls to collect all: {:.1} -> {:.1}\n",
+            self.mean_collect_all, after.mean_collect_all
+        );
+
+        for rare in &after.rare {
+            let before = self.rare.iter().find(|r| r.drop_type == rare.drop_type);
+            out += &format!(
+                "{} p90: {} -> {}\n",
+                drop_name(rare.drop_type),
+                before.map_or("-".to_string(), |r| r.p90.to_string()),
+                rare.p90
+            );
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::headless::*;
+
+    #[test]
+    fn collect_all_math() {
+        // two even outcomes take 3 pulls on average
+        assert!((expected_collect_all(&vec![0.5, 0.5]).unwrap() - 3.0).abs() < 0.0001);
+
+        // one outcome is always 1 pull
+        assert_eq!(expected_collect_all(&vec![1.0]).unwrap(), 1.0);
+
+        assert_eq!(expected_collect_all(&vec![0.0, 1.0]), None);
+    }
+
+    #[test]
+    fn percentiles() {
+        let samples: Vec<i64> = (1..=100).collect();
+        assert_eq!(percentile(&samples, 50.0), 50);
+        assert_eq!(percentile(&samples, 99.0), 99);
+        assert_eq!(percentile(&vec![], 50.0), 0);
+    }
+
+    #[test]
+    fn seeded_runs_match() {
+        let plat_api = headless_platform_api();
+        let target = SimTarget::from_table(FixedTableID::Cave);
+
+        seed_rng(7);
+        let first = run(&target, 2000, 20, &plat_api);
+
+        seed_rng(7);
+        let second = run(&target, 2000, 20, &plat_api);
+
+        assert_eq!(first.counts, second.counts);
+        assert_eq!(first.rare, second.rare);
+
+        let total: i64 = first.counts.iter().map(|(_, count)| count).sum();
+        assert_eq!(total, 2000);
+    }
+
+    #[test]
+    fn pack_pity_counts() {
+        let plat_api = headless_platform_api();
+        seed_rng(1);
+
+        // pity makes the rare drop show up sooner than its base chance suggests
+        let report = run(&SimTarget::from_pack(PackID::Starter), 0, 200, &plat_api);
+        let rare = report
+            .rare
+            .iter()
+            .find(|rare| rare.drop_type != DropType::Gold)
+            .unwrap();
+        let Some(PityKind::Guarantee(guarantee)) = PackID::Starter
+            .get_pack_info()
+            .pity
+            .as_ref()
+            .map(|rule| rule.kind)
+        else {
+            panic!("Starter pack should have a guarantee");
+        };
+        assert!(rare.p99 <= guarantee);
+    }
+}
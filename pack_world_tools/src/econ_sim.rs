@@ -0,0 +1,571 @@
+use crate::headless::*;
+use elara_engine::platform_api::*;
+use pack_world_game::{
+    drop_table::*,
+    grid::*,
+    item::*,
+    pack::{pack_id::*, *},
+    save_file::{SIM_LIMIT_H_FREE, SIM_LIMIT_H_PREMIUM},
+    setup_initial,
+    state::inventory::*,
+    tile::*,
+    update_signal::*,
+    world::*,
+};
+
+pub const HOUR_S: f64 = 60.0 * 60.0;
+pub const DAY_S: f64 = HOUR_S * 24.0;
+
+/// Tiles placed per decision. A player places by hand, so thousands at once isn't realistic.
+const MAX_PLACEMENTS: usize = 5;
+
+/// Scripted player decisions. Asked every time the player checks the game.
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// Only asked about tiles that are ready to harvest
+    fn should_harvest(&mut self, tile: &TileInstance, inventory: &Inventory) -> bool;
+
+    /// Asked until it returns None or a pack the player can't afford
+    fn choose_pack(&mut self, inventory: &Inventory) -> Option<PackID>;
+
+    /// Asked until it returns None or a placement that isn't valid. At most MAX_PLACEMENTS per decision.
+    fn choose_placement(
+        &mut self,
+        world: &World,
+        inventory: &Inventory,
+    ) -> Option<(TileType, GridPos)>;
+}
+
+/// When and how the player plays
+#[derive(Debug, Clone, Copy)]
+pub struct SimConfig {
+    pub days: f64,
+
+    /// Seconds of each play session
+    pub session_s: f64,
+
+    /// Seconds away between sessions. Only simulated up to the offline cap.
+    pub away_s: f64,
+
+    /// Premium players have the longer offline cap
+    pub premium: bool,
+
+    /// Seconds per simulation step while playing
+    pub step_s: f64,
+
+    /// Seconds per simulation step while away
+    pub offline_step_s: f64,
+
+    /// Seconds between strategy decisions while playing
+    pub decide_s: f64,
+}
+
+impl SimConfig {
+    pub fn new() -> Self {
+        Self {
+            days: 14.0,
+            session_s: 30.0 * 60.0,
+            away_s: 8.0 * HOUR_S,
+            premium: false,
+            step_s: 5.0,
+            offline_step_s: 30.0,
+            decide_s: 60.0,
+        }
+    }
+
+    pub fn offline_cap_s(&self) -> f64 {
+        let hours = if self.premium {
+            SIM_LIMIT_H_PREMIUM
+        } else {
+            SIM_LIMIT_H_FREE
+        };
+        hours as f64 * HOUR_S
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MilestoneKind {
+    PackAffordable(PackID),
+    TileObtained(TileType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Milestone {
+    /// Game time in seconds since the start
+    pub time_s: f64,
+    pub kind: MilestoneKind,
+}
+
+/// A headless game. The same world and inventory code as the real game, without rendering.
+pub struct EconSim {
+    pub world: World,
+    pub inventory: Inventory,
+    pub pity: PityCounters,
+
+    pub time_s: f64,
+    pub timeline: Vec<Milestone>,
+    pub packs_opened: i64,
+    pub harvests: i64,
+}
+
+impl EconSim {
+    pub fn new() -> Self {
+        let mut ret = Self {
+            world: World::new(),
+            inventory: Inventory::new(),
+            pity: PityCounters::new(),
+            time_s: 0.0,
+            timeline: vec![],
+            packs_opened: 0,
+            harvests: 0,
+        };
+
+        setup_initial(&mut ret.world, &mut ret.inventory);
+        ret.check_milestones();
+        ret
+    }
+
+    pub fn run(
+        &mut self,
+        strategy: &mut impl Strategy,
+        config: &SimConfig,
+        platform_api: &PlatformApi,
+    ) {
+        let end_s = config.days * DAY_S;
+
+        while self.time_s < end_s {
+            // play
+            let session_end = (self.time_s + config.session_s).min(end_s);
+            let mut next_decision = self.time_s;
+            while self.time_s < session_end {
+                if self.time_s >= next_decision {
+                    self.decide(strategy, platform_api);
+                    next_decision += config.decide_s;
+                }
+
+                self.step(config.step_s, platform_api);
+                self.deliver_drops(platform_api);
+                self.check_milestones();
+            }
+
+            // away. The game only catches up to the offline cap.
+            let away_s = config.away_s.min(end_s - self.time_s);
+            let mut to_sim = away_s.min(config.offline_cap_s());
+            while to_sim > 0.0 {
+                let step = to_sim.min(config.offline_step_s);
+                self.step(step, platform_api);
+                to_sim -= step;
+            }
+            self.time_s = self.time_s.max(session_end + away_s);
+        }
+    }
+
+    fn step(&mut self, seconds: f64, platform_api: &PlatformApi) {
+        advance_time_ms(seconds * 1000.0);
+        self.time_s += seconds;
+
+        let sigs = self.world.sim_update(seconds, platform_api);
+        self.handle_signals(sigs, platform_api);
+    }
+
+    fn decide(&mut self, strategy: &mut impl Strategy, platform_api: &PlatformApi) {
+        // harvest
+        let world_snapshot = self.world.get_world_snapshot();
+        let mut sigs: Vec<UpdateSignal> = vec![];
+        for eid in self.entity_ids() {
+            let tile = self.world.entities.get_mut(&eid).unwrap();
+            if tile.can_harvest() && strategy.should_harvest(tile, &self.inventory) {
+                sigs.append(&mut tile.harvest(&world_snapshot, platform_api));
+            }
+        }
+        self.handle_signals(sigs, platform_api);
+        self.deliver_drops(platform_api);
+
+        // packs
+        while let Some(pack_id) = strategy.choose_pack(&self.inventory) {
+            let pack = pack_id.get_pack_info();
+            if !pack.can_afford(&self.inventory) {
+                break;
+            }
+
+            pack.spend(&mut self.inventory);
+            self.packs_opened += 1;
+            for _ in 0..pack.content_count {
+                let drop = pack.pull(self.pity.misses(pack_id), platform_api);
+                self.pity.record_pull(pack_id, &drop);
+                let _ = self.inventory.give_drop(drop);
+            }
+            self.check_milestones();
+        }
+
+        // placing
+        for _ in 0..MAX_PLACEMENTS {
+            let Some((tile, pos)) = strategy.choose_placement(&self.world, &self.inventory) else {
+                break;
+            };
+
+            if !self.inventory.has_atleast(ItemType::Tile(tile), 1) {
+                break;
+            }
+
+            match self.world.try_place_tile(pos, tile) {
+                Ok(sigs) => {
+                    let _ = self.inventory.give_item(ItemType::Tile(tile), -1);
+                    self.handle_signals(sigs, platform_api);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Sorted so seeded runs repeat
+    fn entity_ids(&self) -> Vec<EntityID> {
+        let mut ids: Vec<EntityID> = self.world.entities.keys().copied().collect();
+        ids.sort_by_key(|eid| eid.id);
+        ids
+    }
+
+    /// The game hands out queued drops over a few frames. Here they all arrive at once.
+    fn deliver_drops(&mut self, platform_api: &PlatformApi) {
+        let mut sigs: Vec<UpdateSignal> = vec![];
+
+        for eid in self.entity_ids() {
+            let tile = self.world.entities.get_mut(&eid).unwrap();
+            while let Some(drop) = tile.drops_queue.pop() {
+                sigs.push(UpdateSignal::AddHarvestDrop {
+                    drop,
+                    origin: tile.grid_pos,
+                    source: DropSource::Tile(tile.tile_type),
+                });
+            }
+
+            if tile.destroy_after_drops {
+                sigs.push(UpdateSignal::DestroyTile {
+                    pos: tile.grid_pos,
+                    layer: tile.tile_type.get_definition().world_layer,
+                });
+            }
+        }
+
+        self.handle_signals(sigs, platform_api);
+    }
+
+    /// The parts of the game's signal handling that change the world or inventory
+    fn handle_signals(&mut self, sigs: Vec<UpdateSignal>, platform_api: &PlatformApi) {
+        let mut queue = sigs;
+
+        while let Some(sig) = queue.pop() {
+            match sig {
+                UpdateSignal::AddHarvestDrop { drop, .. } | UpdateSignal::GiveDrop { drop, .. } => {
+                    let _ = self.inventory.give_drop(drop);
+                }
+                UpdateSignal::GiveItem { item_type, count } => {
+                    let _ = self.inventory.give_item(item_type, count);
+                }
+                UpdateSignal::GiveGold { amount } => {
+                    let _ = self.inventory.give_gold(amount);
+                }
+                UpdateSignal::DestroyTile { pos, layer } => {
+                    self.world.destroy_tile(pos, layer);
+                }
+                UpdateSignal::TryHarvestTile { entity_id } => {
+                    let world_snapshot = self.world.get_world_snapshot();
+                    if let Some(tile) = self.world.entities.get_mut(&entity_id) {
+                        queue.append(&mut tile.harvest(&world_snapshot, platform_api));
+                    }
+                }
+                UpdateSignal::TileHarvested { .. } => {
+                    self.harvests += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_milestones(&mut self) {
+        for pack_id in ALL_PACKS.iter() {
+            let kind = MilestoneKind::PackAffordable(*pack_id);
+            if !self.reached(kind) && pack_id.get_pack_info().can_afford(&self.inventory) {
+                self.timeline.push(Milestone {
+                    time_s: self.time_s,
+                    kind,
+                });
+            }
+        }
+
+        for tile_type in ALL_TILE_TYPES.iter() {
+            let kind = MilestoneKind::TileObtained(*tile_type);
+            if !self.reached(kind)
+                && self
+                    .inventory
+                    .items_seen
+                    .contains_key(&ItemType::Tile(*tile_type))
+            {
+                self.timeline.push(Milestone {
+                    time_s: self.time_s,
+                    kind,
+                });
+            }
+        }
+    }
+
+    fn reached(&self, kind: MilestoneKind) -> bool {
+        self.timeline.iter().any(|milestone| milestone.kind == kind)
+    }
+
+    pub fn render_timeline(&self) -> String {
+        let mut out = String::new();
+
+        for milestone in &self.timeline {
+            let what = match milestone.kind {
+                MilestoneKind::PackAffordable(pack_id) => {
+                    format!("can afford {} pack", pack_id.get_pack_info().display_name)
+                }
+                MilestoneKind::TileObtained(tile_type) => {
+                    format!("got {}", tile_type.get_definition().title)
+                }
+            };
+            out += &format!("{:>14}  {}\n", display_time(milestone.time_s), what);
+        }
+
+        let missing: Vec<String> = ALL_PACKS
+            .iter()
+            .map(|pack_id| MilestoneKind::PackAffordable(*pack_id))
+            .chain(
+                ALL_TILE_TYPES
+                    .iter()
+                    .map(|tile_type| MilestoneKind::TileObtained(*tile_type)),
+            )
+            .filter(|kind| !self.reached(*kind))
+            .map(|kind| match kind {
+                MilestoneKind::PackAffordable(pack_id) => {
+                    format!("{} pack", pack_id.get_pack_info().display_name)
+                }
+                MilestoneKind::TileObtained(tile_type) => {
+                    tile_type.get_definition().title.to_string()
+                }
+            })
+            .collect();
+        if !missing.is_empty() {
+            out += &format!("\nnever reached: {}\n", missing.join(", "));
+        }
+
+        out += &format!(
+            "\nafter {}: {} gold, {} tiles placed, {} packs opened, {} harvests\n",
+            display_time(self.time_s),
+            self.inventory.gold,
+            self.world.entities.len(),
+            self.packs_opened,
+            self.harvests
+        );
+
+        out
+    }
+}
+
+/// day 3 04:15
+pub fn display_time(time_s: f64) -> String {
+    let day = (time_s / DAY_S).floor();
+    let rem = time_s - (day * DAY_S);
+    let hours = (rem / HOUR_S).floor();
+    let minutes = ((rem - hours * HOUR_S) / 60.0).floor();
+    format!(
+        "day {} {:02}:{:02}",
+        day as i64, hours as i64, minutes as i64
+    )
+}
+
+/// First position near the origin where the tile fits without replacing anything
+pub fn first_open_pos(
+    world: &World,
+    world_snapshot: &WorldSnapshot,
+    tile: TileType,
+) -> Option<GridPos> {
+    let def = tile.get_definition();
+
+    // Only positions next to existing tiles can be valid. Closest to the origin first.
+    let mut candidates: Vec<GridPos> = world.valids.keys().copied().collect();
+    candidates.sort_by_key(|pos| (pos.x.abs().max(pos.y.abs()), pos.x, pos.y));
+
+    candidates.into_iter().find(|pos| {
+        def.footprint.iter().all(|p| {
+            let cell_pos = *pos + *p;
+
+            let occupied = world
+                .entity_map
+                .get(&cell_pos)
+                .map(|cell| cell.layers.contains_key(&def.world_layer))
+                .unwrap_or(false);
+
+            !occupied
+                && def
+                    .placement_constraints
+                    .iter()
+                    .all(|cond| cond.valid(cell_pos, world_snapshot))
+        })
+    })
+}
+
+/// Any tile the player has that fits somewhere
+fn any_placement(world: &World, inventory: &Inventory) -> Option<(TileType, GridPos)> {
+    let world_snapshot = world.get_world_snapshot();
+
+    for tile_type in ALL_TILE_TYPES.iter() {
+        if !inventory.has_atleast(ItemType::Tile(*tile_type), 1) {
+            continue;
+        }
+
+        if let Some(pos) = first_open_pos(world, &world_snapshot, *tile_type) {
+            return Some((*tile_type, pos));
+        }
+    }
+
+    None
+}
+
+/// Harvests everything, places everything, and opens the best pack it can afford
+pub struct GreedyStrategy {}
+
+impl Strategy for GreedyStrategy {
+    fn name(&self) -> String {
+        "greedy".into()
+    }
+
+    fn should_harvest(&mut self, _tile: &TileInstance, _inventory: &Inventory) -> bool {
+        true
+    }
+
+    fn choose_pack(&mut self, inventory: &Inventory) -> Option<PackID> {
+        ALL_PACKS
+            .iter()
+            .rev()
+            .find(|pack_id| pack_id.get_pack_info().can_afford(inventory))
+            .copied()
+    }
+
+    fn choose_placement(
+        &mut self,
+        world: &World,
+        inventory: &Inventory,
+    ) -> Option<(TileType, GridPos)> {
+        any_placement(world, inventory)
+    }
+}
+
+/// Opens each pack once, in shop order, and saves up for the next one instead of buying cheaper packs
+pub struct SaverStrategy {
+    opened: Vec<PackID>,
+}
+
+impl SaverStrategy {
+    pub fn new() -> Self {
+        Self { opened: vec![] }
+    }
+}
+
+impl Strategy for SaverStrategy {
+    fn name(&self) -> String {
+        "saver".into()
+    }
+
+    fn should_harvest(&mut self, _tile: &TileInstance, _inventory: &Inventory) -> bool {
+        true
+    }
+
+    fn choose_pack(&mut self, inventory: &Inventory) -> Option<PackID> {
+        let next = ALL_PACKS
+            .iter()
+            .find(|pack_id| !self.opened.contains(pack_id))
+            .copied()
+            .unwrap_or(*ALL_PACKS.last().unwrap());
+
+        if next.get_pack_info().can_afford(inventory) {
+            self.opened.push(next);
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    fn choose_placement(
+        &mut self,
+        world: &World,
+        inventory: &Inventory,
+    ) -> Option<(TileType, GridPos)> {
+        any_placement(world, inventory)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn short_config() -> SimConfig {
+        let mut config = SimConfig::new();
+        config.days = 1.0;
+        config.session_s = 10.0 * 60.0;
+        config.away_s = 4.0 * HOUR_S;
+        config
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(display_time(0.0), "day 0 00:00");
+        assert_eq!(
+            display_time(DAY_S * 2.0 + HOUR_S * 3.0 + 90.0),
+            "day 2 03:01"
+        );
+    }
+
+    #[test]
+    fn offline_cap() {
+        let mut config = SimConfig::new();
+        assert_eq!(config.offline_cap_s(), SIM_LIMIT_H_FREE as f64 * HOUR_S);
+
+        config.premium = true;
+        assert_eq!(config.offline_cap_s(), SIM_LIMIT_H_PREMIUM as f64 * HOUR_S);
+    }
+
+    #[test]
+    fn starting_tiles_are_milestones() {
+        let sim = EconSim::new();
+        assert!(sim.reached(MilestoneKind::TileObtained(TileType::Dirt)));
+        assert!(sim.reached(MilestoneKind::TileObtained(TileType::Grass)));
+        assert!(sim.timeline.iter().all(|milestone| milestone.time_s == 0.0));
+    }
+
+    #[test]
+    fn greedy_progresses() {
+        let plat_api = headless_platform_api();
+        seed_rng(3);
+
+        let mut sim = EconSim::new();
+        sim.run(&mut GreedyStrategy {}, &short_config(), &plat_api);
+
+        assert!(sim.time_s >= DAY_S);
+        assert!(sim.harvests > 0);
+        assert!(sim.world.entities.len() > 1);
+
+        // timeline is in order
+        for pair in sim.timeline.windows(2) {
+            assert!(pair[0].time_s <= pair[1].time_s);
+        }
+    }
+
+    #[test]
+    fn seeded_runs_match() {
+        let plat_api = headless_platform_api();
+
+        seed_rng(9);
+        let mut first = EconSim::new();
+        first.run(&mut SaverStrategy::new(), &short_config(), &plat_api);
+
+        seed_rng(9);
+        let mut second = EconSim::new();
+        second.run(&mut SaverStrategy::new(), &short_config(), &plat_api);
+
+        assert_eq!(first.timeline, second.timeline);
+        assert_eq!(first.inventory.gold, second.inventory.gold);
+    }
+}
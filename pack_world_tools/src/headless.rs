@@ -0,0 +1,101 @@
+use elara_engine::{analytics::*, error::Error, platform_api::*};
+use std::cell::Cell;
+
+thread_local! {
+    static RNG_STATE: Cell<u64> = Cell::new(0);
+
+    /// Simulated clock. Only moves when the tool advances it.
+    static TIME_MS: Cell<f64> = Cell::new(0.0);
+}
+
+/// Same seed gives the same run
+pub fn seed_rng(seed: u64) {
+    RNG_STATE.with(|state| state.set(seed));
+}
+
+pub fn set_time_ms(ms: f64) {
+    TIME_MS.with(|time| time.set(ms));
+}
+
+pub fn advance_time_ms(ms: f64) {
+    TIME_MS.with(|time| time.set(time.get() + ms));
+}
+
+/// splitmix64 mapped into 0..1
+fn rand() -> f64 {
+    RNG_STATE.with(|state| {
+        let next = state.get().wrapping_add(0x9E3779B97F4A7C15);
+        state.set(next);
+
+        let mut z = next;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    })
+}
+
+fn epoch_time_ms() -> f64 {
+    TIME_MS.with(|time| time.get())
+}
+
+fn send_event(_event: AnalyticsEvent) {}
+
+fn write_save_game_data(_data: Vec<u8>) -> Result<(), Error> {
+    Ok(())
+}
+
+fn fetch_game_save() {}
+
+fn open_url(_url: String, _same_tab: bool) {}
+
+fn local_persist_get(_key: &str) -> Option<String> {
+    None
+}
+
+fn local_persist_set(_key: &str, _data: &str) {}
+
+fn local_persist_delete(_key: &str) {}
+
+fn plat_print(output: &str) {
+    println!("{}", output);
+}
+
+/// Platform for running the game code without a window. Saves and analytics go nowhere.
+pub fn headless_platform_api() -> PlatformApi {
+    PlatformApi {
+        rand: rand,
+        send_event: send_event,
+
+        write_save_game_data: write_save_game_data,
+        fetch_game_save: fetch_game_save,
+        epoch_time_ms: epoch_time_ms,
+        open_url: open_url,
+
+        local_persist_get: local_persist_get,
+        local_persist_set: local_persist_set,
+        local_persist_delete: local_persist_delete,
+
+        println: plat_print,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded() {
+        let plat_api = headless_platform_api();
+
+        seed_rng(42);
+        let first: Vec<f64> = (0..10).map(|_| (plat_api.rand)()).collect();
+
+        seed_rng(42);
+        let second: Vec<f64> = (0..10).map(|_| (plat_api.rand)()).collect();
+
+        assert_eq!(first, second);
+        assert!(first.iter().all(|val| *val >= 0.0 && *val < 1.0));
+    }
+}
@@ -0,0 +1,6 @@
+//! Command line tools for balancing and debugging. Everything here runs without a window.
+
+pub mod drop_sim;
+pub mod econ_sim;
+pub mod headless;
+pub mod save_edit;
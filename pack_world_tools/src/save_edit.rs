@@ -0,0 +1,612 @@
+use elara_engine::platform_api::*;
+use pack_world_game::{
+    account_system::*,
+    error::Error,
+    grid::*,
+    item::*,
+    save_file::*,
+    state::{inventory::*, player_state::*},
+    tile::*,
+    world::*,
+};
+
+use crate::headless::*;
+
+/// A save loaded with the game's own loaders, ready to print or change
+pub struct SaveEdit {
+    pub world: World,
+    pub inventory: Inventory,
+    pub player_state: PlayerState,
+    pub history: SaveHistory,
+
+    /// Every slot in the file, and which one is being edited
+    pub slots: SaveSlots,
+    pub slot: usize,
+
+    /// Schema version before migrating
+    pub version: i32,
+    pub unix_time_saved: f64,
+}
+
+impl SaveEdit {
+    /// Edits the active slot if no slot is given
+    pub fn load(
+        data: &Vec<u8>,
+        slot: Option<usize>,
+        platform_api: &PlatformApi,
+    ) -> Result<Self, Error> {
+        let slots = SaveSlots::read(data);
+        let slot = slot.unwrap_or(slots.active);
+        if slot >= slots.slots.len() {
+            return Err(Error::UnknownSaveSlot(slot));
+        }
+
+        let mut ret = Self {
+            world: World::new(),
+            inventory: Inventory::new(),
+            player_state: PlayerState::new(),
+            history: SaveHistory::new(),
+            slots,
+            slot,
+            version: 0,
+            unix_time_saved: 0.0,
+        };
+
+        let slot_data = ret.slots.slots[slot].saves();
+        load_game(
+            &mut ret.world,
+            &mut ret.inventory,
+            &mut ret.player_state,
+            &slot_data,
+            &AccountSystem::new(),
+            &mut ret.history,
+            platform_api,
+        )?;
+
+        // The loaders don't keep these, so read them from the save that was used
+        let save_used = ret.history.last_load.as_ref().map_or(0, |r| r.save_used);
+        let saves: Vec<&[u8]> = split_saves(&slot_data)
+            .into_iter()
+            .filter(|save| Snapshot::read(save).is_err())
+            .collect();
+        let raw = read_save(&saves, save_used)?;
+        ret.version = save_version(&raw);
+        ret.unix_time_saved = raw.load_f64("unix_time_saved").unwrap_or(0.0);
+
+        Ok(ret)
+    }
+
+    /// Data to write back, with every slot. Keeps the original save time, so offline progress isn't lost.
+    pub fn save(&mut self, platform_api: &PlatformApi) -> Result<Vec<u8>, Error> {
+        set_time_ms(self.unix_time_saved);
+        let mut save_file = build_save_file(
+            &self.world,
+            &self.inventory,
+            &self.player_state,
+            platform_api,
+        )?;
+        save_file.save_str(SAVE_SLOT_KEY, &self.slots.slots[self.slot].name);
+
+        let slot_data = self.history.write(save_file)?;
+        self.slots.set_data(self.slot, slot_data);
+        self.slots
+            .set_snapshots(self.slot, self.history.snapshot_data());
+        self.slots.write()
+    }
+
+    pub fn give_item(&mut self, item_type: ItemType, count: i64) -> Result<(), Error> {
+        self.inventory.give_item(item_type, count)?;
+        Ok(())
+    }
+
+    pub fn set_gold(&mut self, gold: i64) {
+        self.inventory.gold = gold;
+    }
+
+    /// Moves the tile and keeps its state. Fails without changing anything if the tile can't be placed there,
+    /// another tile on its layer is in the way, or a tile depending on it would be left invalid.
+    pub fn move_tile(&mut self, id: u64, pos: GridPos) -> Result<(), Error> {
+        let eid = EntityID { id };
+        let misplaced_before = self.misplaced_tiles();
+
+        // Take it out while checking, so it isn't in its own way
+        let Some(mut tile) = self.world.entities.remove(&eid) else {
+            return Err(Error::InvalidTilePosition);
+        };
+        self.world.rebuild_derived();
+
+        let def = tile.tile_type.get_definition();
+        let blocked = def.footprint.iter().any(|p| {
+            self.world
+                .get_entities(pos + *p)
+                .layers
+                .contains_key(&def.world_layer)
+        });
+        let placeable = !blocked && tile.tile_type.can_place_here(pos, &self.world);
+
+        let from = tile.grid_pos;
+        if placeable {
+            tile.grid_pos = pos;
+        }
+        self.world.raw_insert_entity(eid, tile);
+        self.world.rebuild_derived();
+
+        if !placeable {
+            return Err(Error::InvalidTilePosition);
+        }
+
+        // Tiles which needed it where it was
+        let stranded = self
+            .misplaced_tiles()
+            .iter()
+            .any(|other| !misplaced_before.contains(other));
+        if stranded {
+            self.world.get_entity_mut(&eid).grid_pos = from;
+            self.world.rebuild_derived();
+            return Err(Error::InvalidTilePosition);
+        }
+
+        Ok(())
+    }
+
+    /// Tiles that fail their placement constraints where they are
+    fn misplaced_tiles(&self) -> Vec<EntityID> {
+        self.world
+            .entities
+            .iter()
+            .filter(|(_, tile)| !tile.tile_type.can_place_here(tile.grid_pos, &self.world))
+            .map(|(eid, _)| *eid)
+            .collect()
+    }
+
+    /// Returns every tile removed, including tiles that were sitting on it
+    pub fn remove_tile(&mut self, id: u64) -> Result<Vec<TileType>, Error> {
+        let eid = EntityID { id };
+        let Some(tile) = self.world.entities.get(&eid) else {
+            return Err(Error::InvalidTilePosition);
+        };
+
+        let layer = tile.tile_type.get_definition().world_layer;
+        Ok(self.world.remove_entity(eid, layer))
+    }
+
+    fn sorted_tiles(&self) -> Vec<(&EntityID, &TileInstance)> {
+        let mut ret: Vec<(&EntityID, &TileInstance)> = self.world.entities.iter().collect();
+        ret.sort_by_key(|(eid, _)| eid.id);
+        ret
+    }
+
+    fn sorted_items(&self) -> Vec<(ItemType, i64)> {
+        let mut ret: Vec<(ItemType, i64)> = self
+            .inventory
+            .items
+            .iter()
+            .map(|(item_type, count)| (*item_type, *count))
+            .collect();
+        ret.sort_by_key(|(item_type, _)| item_name(*item_type));
+        ret
+    }
+
+    fn sorted_positions(map: impl Iterator<Item = GridPos>) -> Vec<GridPos> {
+        let mut ret: Vec<GridPos> = map.collect();
+        ret.sort_by_key(|pos| (pos.x, pos.y));
+        ret
+    }
+
+    fn report_lines(&self) -> Vec<String> {
+        match &self.history.last_load {
+            Some(report) => report.display().lines().map(|l| l.to_string()).collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn render_table(&self) -> String {
+        let mut ret = String::new();
+
+        for (i, slot) in self.slots.slots.iter().enumerate() {
+            ret.push_str(&format!(
+                "slot {} {}{}{}\n",
+                i,
+                slot.name,
+                if i == self.slots.active {
+                    ", active"
+                } else {
+                    ""
+                },
+                if i == self.slot { ", editing" } else { "" }
+            ));
+        }
+
+        ret.push_str(&format!(
+            "version {}, saved at {}, {} backups, {} snapshots, {} quarantined entries\n",
+            self.version,
+            self.unix_time_saved,
+            self.history.backups.len().saturating_sub(1),
+            self.history.snapshots.len(),
+            self.history.quarantine.entries.len()
+        ));
+        for line in self.report_lines() {
+            ret.push_str(&format!("  {}\n", line));
+        }
+
+        ret.push_str(&format!("\ntiles {}\n", self.world.entities.len()));
+        for (eid, tile) in self.sorted_tiles() {
+            ret.push_str(&format!(
+                "  {:>5} {:<12} ({}, {})",
+                eid.id,
+                format!("{:?}", tile.tile_type),
+                tile.grid_pos.x,
+                tile.grid_pos.y
+            ));
+            if let Some(harvest) = &tile.comp_harvest {
+                ret.push_str(&format!(
+                    " harvest {:.1}/{:.1}s",
+                    harvest.time,
+                    harvest.length()
+                ));
+            }
+            if let Some(auto_death) = &tile.comp_auto_death {
+                ret.push_str(&format!(
+                    " dies in {:.1}s",
+                    auto_death.remaining_ms() / 1000.0
+                ));
+            }
+            if let Some(harvest_others) = &tile.comp_harvest_others {
+                ret.push_str(&format!(
+                    " harvests others in {:.1}s",
+                    harvest_others.timer.as_milliseconds().value() / 1000.0
+                ));
+            }
+            ret.push_str(&format!(" {:?}\n", tile.methods()));
+        }
+
+        ret.push_str(&format!(
+            "\ninventory {} gold, {}/{} slots\n",
+            self.inventory.gold,
+            self.inventory.items.len(),
+            self.inventory.limit
+        ));
+        for (item_type, count) in self.sorted_items() {
+            let protected = self.inventory.protected.contains_key(&item_type);
+            ret.push_str(&format!(
+                "  {:<16} {}{}\n",
+                item_name(item_type),
+                count,
+                if protected { " protected" } else { "" }
+            ));
+        }
+
+        ret.push_str(&format!("\nvalids {}\n", self.world.valids.len()));
+        for pos in Self::sorted_positions(self.world.valids.keys().copied()) {
+            ret.push_str(&format!("  ({}, {})\n", pos.x, pos.y));
+        }
+
+        ret.push_str(&format!(
+            "\ndrop mods {}\n",
+            self.world.drop_count_mod.len()
+        ));
+        for pos in Self::sorted_positions(self.world.drop_count_mod.keys().copied()) {
+            ret.push_str(&format!(
+                "  ({}, {}) x{}\n",
+                pos.x, pos.y, self.world.drop_count_mod[&pos]
+            ));
+        }
+
+        ret.push_str(&format!(
+            "\nspeed mods {}\n",
+            self.world.harvest_speed_mod.len()
+        ));
+        for pos in Self::sorted_positions(self.world.harvest_speed_mod.keys().copied()) {
+            ret.push_str(&format!(
+                "  ({}, {}) x{}\n",
+                pos.x, pos.y, self.world.harvest_speed_mod[&pos]
+            ));
+        }
+
+        ret.push_str(&format!("\nbuffs {}\n", self.world.buffs.len()));
+        for buff in &self.world.buffs {
+            ret.push_str(&format!(
+                "  {:?} at ({}, {}) {:.1}s left\n",
+                buff.kind,
+                buff.origin.x,
+                buff.origin.y,
+                buff.remaining.as_milliseconds().value() / 1000.0
+            ));
+        }
+
+        ret
+    }
+
+    pub fn render_json(&self) -> String {
+        let tiles: Vec<String> = self
+            .sorted_tiles()
+            .iter()
+            .map(|(eid, tile)| {
+                let mut fields = vec![
+                    format!("\"id\": {}", eid.id),
+                    format!("\"type\": {}", json_str(&format!("{:?}", tile.tile_type))),
+                    format!("\"x\": {}", tile.grid_pos.x),
+                    format!("\"y\": {}", tile.grid_pos.y),
+                    format!(
+                        "\"methods\": {}",
+                        json_str(&format!("{:?}", tile.methods()))
+                    ),
+                ];
+                if let Some(harvest) = &tile.comp_harvest {
+                    fields.push(format!(
+                        "\"harvest\": {{\"time_s\": {}, \"length_s\": {}}}",
+                        harvest.time,
+                        harvest.length()
+                    ));
+                }
+                if let Some(auto_death) = &tile.comp_auto_death {
+                    fields.push(format!("\"auto_death_ms\": {}", auto_death.remaining_ms()));
+                }
+                if let Some(harvest_others) = &tile.comp_harvest_others {
+                    fields.push(format!(
+                        "\"harvest_others_ms\": {}",
+                        harvest_others.timer.as_milliseconds().value()
+                    ));
+                }
+                format!("{{{}}}", fields.join(", "))
+            })
+            .collect();
+
+        let items: Vec<String> = self
+            .sorted_items()
+            .iter()
+            .map(|(item_type, count)| {
+                format!(
+                    "{{\"item\": {}, \"count\": {}, \"protected\": {}}}",
+                    json_str(&item_name(*item_type)),
+                    count,
+                    self.inventory.protected.contains_key(item_type)
+                )
+            })
+            .collect();
+
+        let positions =
+            |map: Vec<GridPos>, values: Option<&std::collections::HashMap<GridPos, f64>>| {
+                map.iter()
+                    .map(|pos| match values {
+                        Some(values) => format!(
+                            "{{\"x\": {}, \"y\": {}, \"value\": {}}}",
+                            pos.x, pos.y, values[pos]
+                        ),
+                        None => format!("{{\"x\": {}, \"y\": {}}}", pos.x, pos.y),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            };
+
+        let buffs: Vec<String> = self
+            .world
+            .buffs
+            .iter()
+            .map(|buff| {
+                format!(
+                    "{{\"kind\": {}, \"x\": {}, \"y\": {}, \"remaining_ms\": {}}}",
+                    json_str(&format!("{:?}", buff.kind)),
+                    buff.origin.x,
+                    buff.origin.y,
+                    buff.remaining.as_milliseconds().value()
+                )
+            })
+            .collect();
+
+        let report: Vec<String> = self.report_lines().iter().map(|l| json_str(l)).collect();
+
+        let slots: Vec<String> = self
+            .slots
+            .slots
+            .iter()
+            .map(|slot| json_str(&slot.name))
+            .collect();
+
+        let fields = vec![
+            format!("\"slots\": [{}]", slots.join(", ")),
+            format!("\"active_slot\": {}", self.slots.active),
+            format!("\"slot\": {}", self.slot),
+            format!("\"version\": {}", self.version),
+            format!("\"unix_time_saved\": {}", self.unix_time_saved),
+            format!("\"load_report\": [{}]", report.join(", ")),
+            format!(
+                "\"quarantined_keys\": {}",
+                self.history.quarantine.entries.len()
+            ),
+            format!("\"next_entity_id\": {}", self.world.next_entity_id),
+            format!("\"tiles\": [{}]", tiles.join(", ")),
+            format!(
+                "\"inventory\": {{\"gold\": {}, \"limit\": {}, \"items\": [{}]}}",
+                self.inventory.gold,
+                self.inventory.limit,
+                items.join(", ")
+            ),
+            format!(
+                "\"valids\": [{}]",
+                positions(
+                    Self::sorted_positions(self.world.valids.keys().copied()),
+                    None
+                )
+            ),
+            format!(
+                "\"drop_mods\": [{}]",
+                positions(
+                    Self::sorted_positions(self.world.drop_count_mod.keys().copied()),
+                    Some(&self.world.drop_count_mod)
+                )
+            ),
+            format!(
+                "\"speed_mods\": [{}]",
+                positions(
+                    Self::sorted_positions(self.world.harvest_speed_mod.keys().copied()),
+                    Some(&self.world.harvest_speed_mod)
+                )
+            ),
+            format!("\"buffs\": [{}]", buffs.join(", ")),
+        ];
+
+        format!("{{\n  {}\n}}", fields.join(",\n  "))
+    }
+}
+
+/// Items by their type name, tiles as tile.Name
+pub fn item_name(item_type: ItemType) -> String {
+    match item_type {
+        ItemType::Tile(tile_type) => format!("tile.{:?}", tile_type),
+        _ => format!("{:?}", item_type),
+    }
+}
+
+/// Inverse of item_name. Ignores case.
+pub fn parse_item(name: &str) -> Option<ItemType> {
+    let name = name.to_lowercase();
+
+    let mut all: Vec<ItemType> = ALL_ITEM_TYPES.clone();
+    all.extend(
+        ALL_TILE_TYPES
+            .iter()
+            .map(|tile_type| ItemType::Tile(*tile_type)),
+    );
+
+    all.into_iter()
+        .find(|item_type| item_name(*item_type).to_lowercase() == name)
+}
+
+fn json_str(input: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in input.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            c if (c as u32) < 0x20 => ret.push_str(&format!("\\u{:04x}", c as u32)),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pack_world_game::setup_initial;
+
+    fn starter_save(platform_api: &PlatformApi) -> Vec<u8> {
+        let mut world = World::new();
+        let mut inventory = Inventory::new();
+        setup_initial(&mut world, &mut inventory);
+        let _ = world.insert_tile_type(GridPos::new(0, 0), TileType::Grass);
+
+        set_time_ms(5000.0);
+        let save_file =
+            build_save_file(&world, &inventory, &PlayerState::new(), platform_api).unwrap();
+        SaveHistory::new().write(save_file).unwrap()
+    }
+
+    #[test]
+    fn edit_round_trip() {
+        let platform_api = headless_platform_api();
+        let data = starter_save(&platform_api);
+
+        let mut edit = SaveEdit::load(&data, None, &platform_api).unwrap();
+        assert_eq!(edit.version, SAVE_VERSION);
+        assert_eq!(edit.unix_time_saved, 5000.0);
+        assert_eq!(edit.world.entities.len(), 2);
+
+        edit.give_item(parse_item("stick").unwrap(), 7).unwrap();
+        edit.give_item(parse_item("TILE.GRASS").unwrap(), 1)
+            .unwrap();
+        edit.set_gold(123);
+
+        // Dirt 0 has the grass on it, and a second dirt to move the grass onto
+        let _ = edit
+            .world
+            .insert_tile_type(GridPos::new(1, 0), TileType::Dirt);
+        assert!(edit.move_tile(0, GridPos::new(4, 5)).is_err());
+        assert!(edit.move_tile(0, GridPos::new(0, 1)).is_err());
+        assert!(edit.move_tile(2, GridPos::new(0, 0)).is_err());
+        assert!(edit.move_tile(1, GridPos::new(4, 5)).is_err());
+        assert!(edit.move_tile(99, GridPos::new(0, 0)).is_err());
+        assert_eq!(
+            edit.world.entities[&EntityID { id: 0 }].grid_pos,
+            GridPos::new(0, 0)
+        );
+
+        edit.move_tile(1, GridPos::new(1, 0)).unwrap();
+        edit.move_tile(0, GridPos::new(0, 1)).unwrap();
+
+        set_time_ms(9000.0);
+        let data = edit.save(&platform_api).unwrap();
+
+        let edit = SaveEdit::load(&data, None, &platform_api).unwrap();
+        assert_eq!(edit.unix_time_saved, 5000.0);
+        assert_eq!(edit.inventory.gold, 123);
+        assert!(edit.inventory.has_atleast(ItemType::Stick, 7));
+        assert!(edit
+            .inventory
+            .has_atleast(ItemType::Tile(TileType::Grass), 5));
+        assert_eq!(
+            edit.world.entities[&EntityID { id: 1 }].grid_pos,
+            GridPos::new(1, 0)
+        );
+        assert_eq!(
+            edit.world.entities[&EntityID { id: 0 }].grid_pos,
+            GridPos::new(0, 1)
+        );
+        assert!(edit.world.valids.contains_key(&GridPos::new(0, 2)));
+
+        // The edited save is written in front of the original
+        assert_eq!(edit.history.backups.len(), 2);
+    }
+
+    #[test]
+    fn edit_other_slot() {
+        let platform_api = headless_platform_api();
+        let main = starter_save(&platform_api);
+        let data = [rename_saves(&main, "Sandbox").unwrap(), main].concat();
+
+        // Main sorts first, but Sandbox was written first so it's active
+        let mut edit = SaveEdit::load(&data, Some(0), &platform_api).unwrap();
+        assert_eq!(edit.slots.active, 1);
+        edit.set_gold(77);
+        let data = edit.save(&platform_api).unwrap();
+
+        let sandbox = SaveEdit::load(&data, None, &platform_api).unwrap();
+        assert_eq!(sandbox.slots.active().name, "Sandbox");
+        assert_eq!(sandbox.inventory.gold, 0);
+
+        let main = SaveEdit::load(&data, Some(0), &platform_api).unwrap();
+        assert_eq!(main.inventory.gold, 77);
+
+        assert!(SaveEdit::load(&data, Some(2), &platform_api).is_err());
+    }
+
+    #[test]
+    fn remove_tile_cascades() {
+        let platform_api = headless_platform_api();
+        let mut edit = SaveEdit::load(&starter_save(&platform_api), None, &platform_api).unwrap();
+
+        // Grass sits on the dirt, so it goes too
+        let removed = edit.remove_tile(0).unwrap();
+        assert!(removed.contains(&TileType::Dirt));
+        assert!(removed.contains(&TileType::Grass));
+        assert!(edit.world.entities.is_empty());
+    }
+
+    #[test]
+    fn render() {
+        let platform_api = headless_platform_api();
+        let edit = SaveEdit::load(&starter_save(&platform_api), None, &platform_api).unwrap();
+
+        let table = edit.render_table();
+        assert!(table.contains("tiles 2"));
+        assert!(table.contains("tile.Grass"));
+
+        let json = edit.render_json();
+        assert!(json.contains("\"type\": \"Grass\""));
+        assert!(json.contains("\"item\": \"tile.Dirt\", \"count\": 4"));
+        assert_eq!(json_str("a\"b\\\n"), "\"a\\\"b\\\\\\n\"");
+    }
+}
@@ -77,3 +77,21 @@ pub fn paste_handler(event: ClipboardEvent) {
 pub fn mouse_wheel_handler(event: WheelEvent) {
     elara_platform_web::mouse_wheel_handler(event);
 }
+
+/// A file for the page to download
+#[wasm_bindgen(getter_with_clone)]
+pub struct WebDownload {
+    pub file_name: String,
+    pub contents: String,
+}
+
+/// File the game wants downloaded, if one is waiting. Taking it clears it.
+#[wasm_bindgen]
+pub fn take_download() -> Option<WebDownload> {
+    let download = unsafe { GAME_STATE.as_mut()?.download.take()? };
+
+    Some(WebDownload {
+        file_name: download.file_name,
+        contents: download.contents,
+    })
+}